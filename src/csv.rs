@@ -0,0 +1,205 @@
+use crate::dhtxx::{Dht11Response, Dht22Response, Response};
+use crate::ds18b20::Temperature;
+use crate::timestamp::Timestamped;
+use core::fmt::{self, Display, Write};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// A reading that can be written as a row of CSV values.
+pub trait CsvRecord {
+    /// The names of the columns written by `write_fields`, separated by commas.
+    const HEADER: &'static str;
+
+    /// Writes this reading's values, separated by commas, without a trailing newline.
+    fn write_fields<TWrite: Write>(&self, out: &mut TWrite) -> fmt::Result;
+}
+
+impl CsvRecord for Dht11Response {
+    const HEADER: &'static str = "temperature,humidity";
+
+    fn write_fields<TWrite: Write>(&self, out: &mut TWrite) -> fmt::Result {
+        write_tenths(out, self.temperature_tenths() as i32)?;
+        out.write_char(',')?;
+        write_tenths(out, self.humidity_tenths() as i32)
+    }
+}
+
+impl CsvRecord for Dht22Response {
+    const HEADER: &'static str = "temperature,humidity";
+
+    fn write_fields<TWrite: Write>(&self, out: &mut TWrite) -> fmt::Result {
+        write_tenths(out, self.temperature_tenths() as i32)?;
+        out.write_char(',')?;
+        write_tenths(out, self.humidity_tenths() as i32)
+    }
+}
+
+impl CsvRecord for Temperature {
+    const HEADER: &'static str = "temperature";
+
+    fn write_fields<TWrite: Write>(&self, out: &mut TWrite) -> fmt::Result {
+        self.write_fixed(out, 0, 4)
+    }
+}
+
+/// Writes a value given in tenths with one decimal place, e.g. -5 as "-0.5".
+///
+/// This matches formatting the value as a float with `{:.1}`, without needing floating point.
+fn write_tenths<TWrite: Write>(out: &mut TWrite, tenths: i32) -> fmt::Result {
+    if tenths < 0 {
+        out.write_char('-')?;
+    }
+    let magnitude = tenths.unsigned_abs();
+    write!(out, "{}.{}", magnitude / 10, magnitude % 10)
+}
+
+/// Writes the readings as CSV, with a `timestamp` column followed by the reading's columns.
+///
+/// A header row is written first, and every row ends with a newline. Since timestamps can be any
+/// type, `format_time_fn` converts each one to a displayable value, e.g. milliseconds since
+/// startup.
+pub fn write_csv<T, TTime, TWrite, FormatTimeFn, TDisplay>(
+    out: &mut TWrite,
+    readings: &[Timestamped<T, TTime>],
+    format_time_fn: FormatTimeFn,
+) -> fmt::Result
+where
+    T: CsvRecord,
+    TWrite: Write,
+    FormatTimeFn: Fn(&TTime) -> TDisplay,
+    TDisplay: Display,
+{
+    writeln!(out, "timestamp,{}", T::HEADER)?;
+    for reading in readings.iter() {
+        write!(out, "{},", format_time_fn(&reading.at))?;
+        reading.value.write_fields(out)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Formats the readings as CSV, in the same way as [`write_csv`].
+#[cfg(feature = "alloc")]
+pub fn to_csv<T, TTime, FormatTimeFn, TDisplay>(
+    readings: &[Timestamped<T, TTime>],
+    format_time_fn: FormatTimeFn,
+) -> String
+where
+    T: CsvRecord,
+    FormatTimeFn: Fn(&TTime) -> TDisplay,
+    TDisplay: Display,
+{
+    let mut out = String::new();
+    // Writing to a String can't fail.
+    write_csv(&mut out, readings, format_time_fn).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Buffer {
+        data: [u8; 128],
+        len: usize,
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            if end > self.data.len() {
+                return Err(fmt::Error);
+            }
+            self.data[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.data[..self.len]).unwrap()
+        }
+    }
+
+    #[test]
+    fn write_csv_dht22() {
+        let mut out = Buffer {
+            data: [0; 128],
+            len: 0,
+        };
+        let readings = [
+            // 21.5°C, 40.2%
+            Timestamped::new(Dht22Response::from_bytes([0x01, 0x92, 0x00, 0xD7]), 1000u32),
+            // -3.1°C, 85.0%
+            Timestamped::new(Dht22Response::from_bytes([0x03, 0x52, 0x80, 0x1F]), 2000u32),
+            // -0.5°C, 0.0%
+            Timestamped::new(Dht22Response::from_bytes([0x00, 0x00, 0x80, 0x05]), 3000u32),
+        ];
+
+        write_csv(&mut out, &readings, |at| *at).unwrap();
+
+        assert_eq!(
+            out.as_str(),
+            "timestamp,temperature,humidity\n1000,21.5,40.2\n2000,-3.1,85.0\n3000,-0.5,0.0\n"
+        );
+    }
+
+    #[test]
+    fn write_csv_ds18b20() {
+        let mut out = Buffer {
+            data: [0; 128],
+            len: 0,
+        };
+        let readings = [
+            Timestamped::new(Temperature::from_bytes(0x91, 0x01), 5u8),
+            Timestamped::new(Temperature::from_bytes(0x5E, 0xFF), 6u8),
+        ];
+
+        write_csv(&mut out, &readings, |at| *at).unwrap();
+
+        assert_eq!(
+            out.as_str(),
+            "timestamp,temperature\n5,25.0625\n6,-10.1250\n"
+        );
+    }
+
+    #[test]
+    fn write_csv_empty_has_header() {
+        let mut out = Buffer {
+            data: [0; 128],
+            len: 0,
+        };
+        let readings: [Timestamped<Dht11Response, u32>; 0] = [];
+
+        write_csv(&mut out, &readings, |at| *at).unwrap();
+
+        assert_eq!(out.as_str(), "timestamp,temperature,humidity\n");
+    }
+
+    #[test]
+    fn write_csv_full_buffer_fails() {
+        let mut out = Buffer {
+            data: [0; 128],
+            len: 120,
+        };
+        let readings = [Timestamped::new(Temperature::from_bytes(0x91, 0x01), 5u8)];
+
+        assert_eq!(write_csv(&mut out, &readings, |at| *at), Err(fmt::Error));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn to_csv_dht11() {
+        let readings = [Timestamped::new(
+            Dht11Response::from_bytes([45, 0, 22, 0]),
+            7u32,
+        )];
+
+        assert_eq!(
+            to_csv(&readings, |at| *at),
+            "timestamp,temperature,humidity\n7,22.0,45.0\n"
+        );
+    }
+}
@@ -1,4 +1,11 @@
+use crate::pulse;
+#[cfg(not(feature = "no-float"))]
+use crate::temperature::TemperatureSource;
+use crate::timestamp::Timestamped;
+use crate::util::retry_async;
+use core::cell::RefCell;
 use core::time::Duration;
+use embedded_hal::delay::blocking::DelayUs;
 use embedded_hal::digital::PinState;
 use embedded_hal::digital::blocking::{InputPin, IoPin, OutputPin};
 
@@ -12,6 +19,30 @@ pub enum Error<TIoError> {
     BadData,
     /// No response was received.
     NoResponse,
+    /// The pin was lost after a previous error while switching it between input and output mode.
+    /// The sensor must be reconstructed with a new pin.
+    InvalidState,
+    /// The line wasn't idle during the [`Options::quiet_period`] before the request.
+    LineBusy,
+    /// The line stayed high for the whole time the sensor should have started responding.
+    ///
+    /// This usually means the data wire is disconnected, so the line is only held up by the
+    /// pull-up resistor. It can also mean the sensor is unpowered. This is detected well before
+    /// the full ACK timeout, so it's reported quickly. If the line went low at all, then
+    /// [`Error::NoResponse`] is returned instead.
+    Disconnected,
+    /// The time source disagreed with the delay function about how long a delay took. See
+    /// `verify_clock`.
+    ClockMismatch,
+    /// Receiving the response took longer than [`Options::receive_deadline`].
+    Deadline,
+    /// The time source didn't advance while waiting before the first read, e.g. because `time_fn`
+    /// returns a constant.
+    ///
+    /// This is only checked on the first wait of at least 2ms, which is usually the first read, so
+    /// that a misconfigured time source is obvious rather than making every read wait for the full
+    /// minimum read interval.
+    StalledClock,
 }
 
 impl<TIoError> From<TIoError> for Error<TIoError> {
@@ -21,17 +52,218 @@ impl<TIoError> From<TIoError> for Error<TIoError> {
 }
 
 pub trait Response {
+    /// The relative humidity in tenths of a percent, e.g. 652 for 65.2%.
+    fn humidity_tenths(&self) -> u16;
+    /// The temperature in tenths of a degree Celsius, e.g. -51 for -5.1°C.
+    fn temperature_tenths(&self) -> i16;
+
+    #[cfg(not(feature = "no-float"))]
     fn get_humidity(&self) -> f32;
+    #[cfg(not(feature = "no-float"))]
     fn get_temperature(&self) -> f32;
+
+    /// Whether both values are within the given tolerances of another response's values.
+    ///
+    /// This is useful for only publishing readings that changed meaningfully, e.g. by more than
+    /// 0.5°C or 2% humidity. Both tolerances are inclusive.
+    #[cfg(not(feature = "no-float"))]
+    fn approx_eq(&self, other: &Self, temperature_tolerance: f32, humidity_tolerance: f32) -> bool
+    where
+        Self: Sized,
+    {
+        (self.get_temperature() - other.get_temperature()).abs() <= temperature_tolerance
+            && (self.get_humidity() - other.get_humidity()).abs() <= humidity_tolerance
+    }
+
+    /// Compares both values against the given thresholds, and reports which were violated.
+    #[cfg(not(feature = "no-float"))]
+    fn check_alarms(&self, config: &AlarmConfig) -> AlarmStatus {
+        let temperature = self.get_temperature();
+        let humidity = self.get_humidity();
+        AlarmStatus {
+            temperature_low: temperature < config.min_temperature,
+            temperature_high: temperature > config.max_temperature,
+            humidity_low: humidity < config.min_humidity,
+            humidity_high: humidity > config.max_humidity,
+        }
+    }
+}
+
+/// Software alarm thresholds for [`Response::check_alarms`].
+///
+/// Each bound is inclusive, so a value equal to a bound doesn't trigger an alarm. Use infinite
+/// values to disable a bound, e.g. `f32::NEG_INFINITY` for `min_temperature`.
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AlarmConfig {
+    /// The minimum temperature, in degrees Celsius.
+    pub min_temperature: f32,
+    /// The maximum temperature, in degrees Celsius.
+    pub max_temperature: f32,
+    /// The minimum relative humidity, as a percentage.
+    pub min_humidity: f32,
+    /// The maximum relative humidity, as a percentage.
+    pub max_humidity: f32,
+}
+
+/// Which of the thresholds in an [`AlarmConfig`] a response violated.
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AlarmStatus {
+    pub temperature_low: bool,
+    pub temperature_high: bool,
+    pub humidity_low: bool,
+    pub humidity_high: bool,
+}
+
+#[cfg(not(feature = "no-float"))]
+impl AlarmStatus {
+    /// Whether any threshold was violated.
+    pub fn is_triggered(&self) -> bool {
+        self.temperature_low || self.temperature_high || self.humidity_low || self.humidity_high
+    }
+}
+
+/// Drops consecutive responses that haven't changed, to avoid reprocessing slow-moving data.
+///
+/// Each response is compared with [`Response::approx_eq`] against the last response that was
+/// passed through, rather than the previous one. This means a slow drift is still reported once
+/// it exceeds the tolerances.
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug)]
+pub struct DedupConsecutive<TResponse> {
+    temperature_tolerance: f32,
+    humidity_tolerance: f32,
+    last_changed: Option<TResponse>,
+}
+
+/// The outcome of passing a response to [`DedupConsecutive::filter`].
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Deduped<TResponse> {
+    /// The response differs from the last one that was passed through.
+    Changed(TResponse),
+    /// The response is within the tolerances of the last one that was passed through.
+    Unchanged,
+}
+
+#[cfg(not(feature = "no-float"))]
+impl<TResponse> DedupConsecutive<TResponse>
+where
+    TResponse: Response + Copy,
+{
+    /// Only drops responses that are exactly the same as the last one that was passed through.
+    pub fn new() -> Self {
+        Self::with_tolerances(0.0, 0.0)
+    }
+
+    /// Drops responses whose values are both within the given tolerances of the last one that was
+    /// passed through. Both tolerances are inclusive.
+    pub fn with_tolerances(temperature_tolerance: f32, humidity_tolerance: f32) -> Self {
+        DedupConsecutive {
+            temperature_tolerance,
+            humidity_tolerance,
+            last_changed: None,
+        }
+    }
+
+    /// Compares the response with the last one that was passed through. The first response is
+    /// always passed through.
+    pub fn filter(&mut self, response: TResponse) -> Deduped<TResponse> {
+        if let Some(last_changed) = self.last_changed {
+            if response.approx_eq(
+                &last_changed,
+                self.temperature_tolerance,
+                self.humidity_tolerance,
+            ) {
+                return Deduped::Unchanged;
+            }
+        }
+        self.last_changed = Some(response);
+        Deduped::Changed(response)
+    }
+}
+
+#[cfg(not(feature = "no-float"))]
+impl<TResponse> Default for DedupConsecutive<TResponse>
+where
+    TResponse: Response + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The offset between degrees Celsius and Kelvin.
+#[cfg(not(feature = "no-float"))]
+const CELSIUS_TO_KELVIN: f32 = 273.15;
+
+/// A response converted to SI units, for systems that don't work in Celsius or percent.
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SiReading {
+    /// The temperature, in Kelvin.
+    pub temperature_kelvin: f32,
+    /// The relative humidity, as a fraction from 0 to 1.
+    pub relative_humidity: f32,
+}
+
+#[cfg(not(feature = "no-float"))]
+impl SiReading {
+    /// Converts a response's Celsius and percentage values to SI units.
+    pub fn from_response<TResponse: Response>(response: &TResponse) -> SiReading {
+        SiReading {
+            temperature_kelvin: response.get_temperature() + CELSIUS_TO_KELVIN,
+            relative_humidity: response.get_humidity() / 100.0,
+        }
+    }
+}
+
+/// A response whose temperature and humidity were range checked separately, as returned by
+/// `read_partial`.
+///
+/// Each value is `Err` if it's outside the range that the sensor can measure.
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValidatedReading {
+    /// The temperature, in degrees Celsius.
+    pub temperature: Result<f32, ()>,
+    /// The relative humidity, as a percentage.
+    pub humidity: Result<f32, ()>,
+}
+
+#[cfg(not(feature = "no-float"))]
+impl ValidatedReading {
+    fn from_response<TResponse: Response + ResponseInternal>(
+        response: &TResponse,
+    ) -> ValidatedReading {
+        ValidatedReading {
+            temperature: if response.is_temperature_valid() {
+                Ok(response.get_temperature())
+            } else {
+                Err(())
+            },
+            humidity: if response.is_humidity_valid() {
+                Ok(response.get_humidity())
+            } else {
+                Err(())
+            },
+        }
+    }
 }
 
 trait ResponseInternal {
     fn from_raw_bytes(bytes: [u8; 4]) -> Self;
-    fn is_valid(&self) -> bool;
+    fn is_humidity_valid(&self) -> bool;
+    fn is_temperature_valid(&self) -> bool;
+
+    fn is_valid(&self) -> bool {
+        self.is_humidity_valid() && self.is_temperature_valid()
+    }
 }
 
 /// Data read from the DHT11.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Dht11Response {
     pub humidity: u8,
     pub humidity_decimal: u8,
@@ -39,11 +271,96 @@ pub struct Dht11Response {
     pub temperature_decimal: u8,
 }
 
+impl Dht11Response {
+    /// Constructs a response from human-readable values, e.g. to simulate a sensor in tests.
+    ///
+    /// Values are rounded to the nearest tenth. Since the DHT11 cannot represent negative values,
+    /// these are clamped to 0, and values above 255.9 are clamped to 255.9.
+    #[cfg(not(feature = "no-float"))]
+    pub fn from_values(temperature_c: f32, humidity_pct: f32) -> Dht11Response {
+        let humidity_x10 = to_x10(humidity_pct, MAX_DHT11_X10);
+        let temperature_x10 = to_x10(temperature_c, MAX_DHT11_X10);
+        Dht11Response {
+            humidity: (humidity_x10 / 10) as u8,
+            humidity_decimal: (humidity_x10 % 10) as u8,
+            temperature: (temperature_x10 / 10) as u8,
+            temperature_decimal: (temperature_x10 % 10) as u8,
+        }
+    }
+
+    /// Decodes a response from the four data bytes sent by the sensor, e.g. from a captured frame.
+    ///
+    /// The bytes are not validated, so check the result with [`Dht11Response::is_valid`].
+    pub fn from_bytes(bytes: [u8; 4]) -> Dht11Response {
+        Dht11Response::from_raw_bytes(bytes)
+    }
+
+    /// Encodes the response as the five bytes sent by the sensor: the four data bytes, then the
+    /// parity byte.
+    ///
+    /// This is the inverse of [`Dht11Response::from_bytes`], e.g. to simulate a sensor with
+    /// `testing::dht_frame`.
+    pub fn to_frame(&self) -> [u8; 5] {
+        to_frame([
+            self.humidity,
+            self.humidity_decimal,
+            self.temperature,
+            self.temperature_decimal,
+        ])
+    }
+
+    /// Whether the response is within the range that a DHT11 can measure.
+    ///
+    /// Responses returned by `Dht11::read` have already been validated.
+    pub fn is_valid(&self) -> bool {
+        ResponseInternal::is_valid(self)
+    }
+
+    /// The humidity, or `None` if the decimal part is outside the valid range of 0-9.
+    ///
+    /// Responses returned by `Dht11::read` are always valid, but a response constructed directly
+    /// may not be, in which case [`Response::get_humidity`] would carry the decimal part into the
+    /// integer part (e.g. 50 with a decimal of 12 would be 51.2).
+    #[cfg(not(feature = "no-float"))]
+    pub fn checked_get_humidity(&self) -> Option<f32> {
+        if self.humidity_decimal >= 10 {
+            return None;
+        }
+        Some(self.get_humidity())
+    }
+
+    /// A copy of this response with both decimal parts clamped to the valid range of 0-9.
+    ///
+    /// Some DHT11 clones send decimal bytes of 10 or more, which `get_humidity` and
+    /// `get_temperature` would otherwise carry into the integer part. This assumes the extra bits
+    /// are noise, so it reports the largest valid decimal instead. Responses from `Dht11::read`
+    /// with the default options are already valid, so are unchanged. This is mainly useful for
+    /// unvalidated responses, i.e. from `Dht11::read_partial` or when [`Options::enforce_range`]
+    /// is false.
+    pub fn normalized(&self) -> Dht11Response {
+        Dht11Response {
+            humidity_decimal: self.humidity_decimal.min(9),
+            temperature_decimal: self.temperature_decimal.min(9),
+            ..*self
+        }
+    }
+}
+
 impl Response for Dht11Response {
+    fn humidity_tenths(&self) -> u16 {
+        self.humidity as u16 * 10 + self.humidity_decimal as u16
+    }
+
+    fn temperature_tenths(&self) -> i16 {
+        self.temperature as i16 * 10 + self.temperature_decimal as i16
+    }
+
+    #[cfg(not(feature = "no-float"))]
     fn get_humidity(&self) -> f32 {
         self.humidity as f32 + (self.humidity_decimal as f32 * 0.1)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn get_temperature(&self) -> f32 {
         self.temperature as f32 + (self.temperature_decimal as f32 * 0.1)
     }
@@ -59,28 +376,113 @@ impl ResponseInternal for Dht11Response {
         }
     }
 
-    fn is_valid(&self) -> bool {
+    fn is_humidity_valid(&self) -> bool {
+        (self.humidity < 100 && self.humidity_decimal < 10)
+            || (self.humidity == 100 && self.humidity_decimal == 0)
+    }
+
+    fn is_temperature_valid(&self) -> bool {
         // DHT11 sensors should only be able to read temperatures from 0-50 degrees Celsius.
         // Validate that + 50% for some wiggle-room in case some sensors can go beyond this.
-        ((self.humidity < 100 && self.humidity_decimal < 10)
-            || (self.humidity == 100 && self.humidity_decimal == 0))
-            && ((self.temperature < 75 && self.temperature_decimal < 10)
-                || (self.temperature == 75 && self.temperature_decimal == 0))
+        (self.temperature < 75 && self.temperature_decimal < 10)
+            || (self.temperature == 75 && self.temperature_decimal == 0)
     }
 }
 
 /// Data read from the DHT22.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Dht22Response {
     pub humidity_x10: u16,
     pub temperature_x10: u16,
 }
 
+impl Dht22Response {
+    /// Constructs a response from human-readable values, e.g. to simulate a sensor in tests.
+    ///
+    /// Values are rounded to the nearest tenth. Negative temperatures are encoded with the sign
+    /// bit, as sent by the sensor, unless they round to zero. Temperatures are clamped to ±3276.7,
+    /// and humidity to \[0, 6553.5\].
+    #[cfg(not(feature = "no-float"))]
+    pub fn from_values(temperature_c: f32, humidity_pct: f32) -> Dht22Response {
+        let magnitude_x10 = to_x10(temperature_c.abs(), 0x7FFF);
+        let temperature_x10 = if temperature_c < 0.0 && magnitude_x10 != 0 {
+            magnitude_x10 | 0x8000
+        } else {
+            magnitude_x10
+        };
+        Dht22Response {
+            humidity_x10: to_x10(humidity_pct, u16::MAX),
+            temperature_x10,
+        }
+    }
+
+    /// Decodes a response from the four data bytes sent by the sensor, e.g. from a captured frame.
+    ///
+    /// The bytes are not validated, so check the result with [`Dht22Response::is_valid`].
+    ///
+    /// ```
+    /// use simple_sensors::dhtxx::Dht22Response;
+    ///
+    /// let response = Dht22Response::from_bytes([0x02, 0x8C, 0x01, 0x5F]);
+    /// assert!(response.is_valid());
+    /// # #[cfg(not(feature = "no-float"))]
+    /// assert_eq!(response, Dht22Response::from_values(35.1, 65.2));
+    /// ```
+    pub fn from_bytes(bytes: [u8; 4]) -> Dht22Response {
+        Dht22Response::from_raw_bytes(bytes)
+    }
+
+    /// Encodes the response as the five bytes sent by the sensor: the four data bytes, then the
+    /// parity byte.
+    ///
+    /// This is the inverse of [`Dht22Response::from_bytes`], e.g. to simulate a sensor with
+    /// `testing::dht_frame`.
+    pub fn to_frame(&self) -> [u8; 5] {
+        let (high_humidity, low_humidity) = self.humidity_bytes();
+        let (high_temp, low_temp) = self.temperature_bytes();
+        to_frame([high_humidity, low_humidity, high_temp, low_temp])
+    }
+
+    /// Whether the response is within the range that a DHT22 can measure.
+    ///
+    /// Responses returned by `Dht22::read` have already been validated.
+    pub fn is_valid(&self) -> bool {
+        ResponseInternal::is_valid(self)
+    }
+
+    /// The humidity bytes as sent by the sensor, i.e. the high byte then the low byte.
+    pub fn humidity_bytes(&self) -> (u8, u8) {
+        let [high, low] = self.humidity_x10.to_be_bytes();
+        (high, low)
+    }
+
+    /// The temperature bytes as sent by the sensor, i.e. the high byte (including the sign bit)
+    /// then the low byte.
+    pub fn temperature_bytes(&self) -> (u8, u8) {
+        let [high, low] = self.temperature_x10.to_be_bytes();
+        (high, low)
+    }
+}
+
 impl Response for Dht22Response {
+    fn humidity_tenths(&self) -> u16 {
+        self.humidity_x10
+    }
+
+    fn temperature_tenths(&self) -> i16 {
+        let result = (self.temperature_x10 & 0x7FFF) as i16;
+        if (self.temperature_x10 & 0x8000) != 0 {
+            return -result;
+        }
+        result
+    }
+
+    #[cfg(not(feature = "no-float"))]
     fn get_humidity(&self) -> f32 {
         (self.humidity_x10 as f32) * 0.1
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn get_temperature(&self) -> f32 {
         let result = (self.temperature_x10 & 0x7FFF) as f32 * 0.1;
         if (self.temperature_x10 & 0x8000) != 0 {
@@ -98,16 +500,75 @@ impl ResponseInternal for Dht22Response {
         }
     }
 
-    fn is_valid(&self) -> bool {
+    fn is_humidity_valid(&self) -> bool {
+        self.humidity_x10 <= 1000
+    }
+
+    fn is_temperature_valid(&self) -> bool {
         // DHT22 sensors should only be able to read temperatures from -40 - 125 degrees Celsius.
         // Validate that + a some wiggle-room in case some sensors can go beyond this.
         let temp_is_negative = self.temperature_x10 & 0x8000 != 0;
-        let temp_is_valid = (temp_is_negative && (self.temperature_x10 & 0x7FFF) < 601)
-            || (!temp_is_negative && (self.temperature_x10 < 1501));
-        self.humidity_x10 <= 1000 && temp_is_valid
+        (temp_is_negative && (self.temperature_x10 & 0x7FFF) < 601)
+            || (!temp_is_negative && (self.temperature_x10 < 1501))
+    }
+}
+
+/// The parity byte that the sensor sends after the given data bytes, i.e. the last 8 bits of their
+/// sum.
+fn parity(bytes: [u8; 4]) -> u8 {
+    let sum: u16 = bytes.iter().map(|byte| *byte as u16).sum();
+    sum.to_be_bytes()[1]
+}
+
+/// Appends the parity byte to the given data bytes.
+fn to_frame(bytes: [u8; 4]) -> [u8; 5] {
+    [bytes[0], bytes[1], bytes[2], bytes[3], parity(bytes)]
+}
+
+/// Adapts a blocking delay into a `delay_fn` for `Dht11::read` or `Dht22::read`.
+///
+/// The returned function blocks for the full duration, and then returns a future that is already
+/// complete. This makes it easy to use the DHT drivers without an async runtime, but it does not
+/// allow other work to happen during the delay.
+///
+/// Durations longer than `u32::MAX` microseconds are split into several calls to `delay_us`. A
+/// `delay_fn` has no way to report an error, so if the underlying delay fails, the rest of the
+/// duration is skipped and the error is dropped.
+///
+/// The delay is borrowed through a `RefCell` so that the returned function can be copied, as
+/// required by `read`.
+pub fn blocking_delay_fn<TDelay>(
+    delay: &RefCell<TDelay>,
+) -> impl Copy + Fn(Duration) -> core::future::Ready<()> + '_
+where
+    TDelay: DelayUs,
+{
+    move |duration| {
+        let mut delay = delay.borrow_mut();
+        let mut remaining_us = duration.as_micros();
+        while remaining_us > 0 {
+            let chunk_us = remaining_us.min(u32::MAX as u128) as u32;
+            if delay.delay_us(chunk_us).is_err() {
+                break;
+            }
+            remaining_us -= chunk_us as u128;
+        }
+        core::future::ready(())
     }
 }
 
+/// The largest value a DHT11 response can hold, multiplied by 10.
+#[cfg(not(feature = "no-float"))]
+const MAX_DHT11_X10: u16 = 2559;
+
+/// Rounds a non-negative value to the nearest tenth, and returns it multiplied by 10.
+///
+/// The result is clamped to `max`. Negative values and NaN saturate to 0.
+#[cfg(not(feature = "no-float"))]
+fn to_x10(value: f32, max: u16) -> u16 {
+    ((value * 10.0 + 0.5) as u16).min(max)
+}
+
 /// The minimum read interval of a DHT11.
 ///
 /// Note that this can vary a bit by device, so check your device's datasheet to be sure. Try
@@ -121,7 +582,18 @@ pub const MIN_DHT11_READ_INTERVAL: Duration = Duration::from_millis(1000);
 pub const MIN_DHT22_READ_INTERVAL: Duration = Duration::from_millis(2000);
 
 /// Options to modify the behavior of the DHT driver.
+///
+/// New options may be added in future releases, so start from [`DEFAULT_DHT11_OPTIONS`] or
+/// [`DEFAULT_DHT22_OPTIONS`] and change the fields you need:
+///
+/// ```
+/// use simple_sensors::dhtxx;
+///
+/// let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+/// options.max_attempts = 3;
+/// ```
 #[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
 pub struct Options {
     /// The minimum time interval that must pass between reads. Cannot be below this sensor's
     /// absolute minimum read interval (i.e. [`MIN_DHT11_READ_INTERVAL`] or
@@ -131,18 +603,116 @@ pub struct Options {
     ///
     /// Keep in mind the `min_read_interval` when setting this option. For example, if the
     /// `min_read_interval` is set to 2 seconds, and this is set to 3 attempts, each read
-    /// could take over 6 seconds.
+    /// could take over 6 seconds. The total worst case duration of a read (see
+    /// `Dht11::worst_case_read_duration`) must fit in a [`Duration`]. Cannot be above
+    /// [`MAX_ATTEMPTS`].
     pub max_attempts: u8,
+    /// Overrides how long the line is held low to request data from the sensor.
+    ///
+    /// Some sensor clones need a longer start pulse than the datasheet specifies in order to
+    /// respond reliably. Cannot be below this sensor's default start pulse (i.e.
+    /// [`DHT11_START_PULSE`] or [`DHT22_START_PULSE`]). If `None`, the default is used.
+    pub start_pulse: Option<Duration>,
+    /// The minimum time that must pass after the driver is constructed before the first read.
+    ///
+    /// DHT sensors need about a second after power-up before they respond reliably. The first read
+    /// always waits at least `min_read_interval` after construction, so this only needs to be set
+    /// if the driver is constructed at the same time as the sensor is powered on, and the sensor
+    /// needs longer than `min_read_interval` to settle.
+    pub power_on_delay: Duration,
+    /// Whether to reject responses that don't end with a short end pulse.
+    ///
+    /// Some sensor clones don't send a clean end pulse after the data. Setting this to `false`
+    /// accepts these responses, as long as they pass the parity and range checks, but slightly
+    /// weakens the detection of corrupt data.
+    pub require_end_pulse: bool,
+    /// Whether to treat a response of all zeros as [`Error::BadData`].
+    ///
+    /// Wiring glitches can produce an all-zero response, which passes the parity check. Since a
+    /// genuine reading of 0% humidity at exactly 0 degrees is extremely unlikely, enabling this
+    /// rejects such responses (retrying them if [`Options::max_attempts`] allows).
+    pub reject_all_zero: bool,
+    /// How many times longer than the sensor's ACK a single bit may take before the read is
+    /// abandoned with [`Error::BadData`].
+    ///
+    /// The default of 4 suits most hardware. Increase this if reads of valid data fail because bits
+    /// time out, or decrease it to detect a stuck line sooner. Cannot be 0.
+    pub bit_timeout_multiplier: u32,
+    /// The most times the line may be sampled for a single bit before the read is abandoned with
+    /// [`Error::BadData`], regardless of how long the ACK took.
+    ///
+    /// An abnormally long ACK inflates the timeout derived from `bit_timeout_multiplier`, which
+    /// would let a stuck line be sampled for far longer than any real bit takes. This caps that
+    /// timeout. How many samples fit in a bit depends on the platform, so the default of
+    /// [`DEFAULT_MAX_BIT_TICKS`] is deliberately generous. Cannot be 0.
+    pub max_bit_ticks: u32,
+    /// Whether the line idles low rather than high, e.g. due to an inverting transistor on the
+    /// board.
+    ///
+    /// This inverts how the sensor's response is read. The start pulse is still sent as usual.
+    pub inverted_line: bool,
+    /// How long to check that the line is idle before each request, if at all.
+    ///
+    /// If the line shares a path with a noise source, reading while it's disturbed will likely
+    /// fail. When this is set, the line is sampled 11 times across this period before sending the
+    /// start pulse, waiting between samples with the `delay_fn`, and the attempt fails with
+    /// [`Error::LineBusy`] if the line ever leaves its idle state. If `None`, no check is made.
+    pub quiet_period: Option<Duration>,
+    /// The most time that receiving the response may block for, if limited.
+    ///
+    /// Receiving the response normally blocks for about 4ms, but a bad line can stretch this out
+    /// until each bit times out. When this is set, the time source is checked after the ACK and
+    /// after every byte, and the attempt is abandoned with [`Error::Deadline`] once this has passed,
+    /// without retrying. This bounds the blocking time for hard real-time tasks, but a tight deadline
+    /// may abort reads that would otherwise have succeeded. If `None`, there is no deadline.
+    pub receive_deadline: Option<Duration>,
+    /// Whether to reject responses outside the range that the sensor can measure as
+    /// [`Error::BadData`].
+    ///
+    /// The parity check already shows that the response arrived intact, so setting this to `false`
+    /// keeps readings that are slightly out of range but genuine, e.g. a DHT22 near a heat source.
+    /// The parity and end pulse checks still apply.
+    pub enforce_range: bool,
 }
 
+/// The most attempts that [`Options::max_attempts`] allows.
+///
+/// Each attempt waits for the minimum read interval, so a large value can make a single read block
+/// for minutes, e.g. 25 attempts at 2 seconds apart. If a sensor needs more than this many attempts,
+/// it's better to surface the error than to keep retrying.
+pub const MAX_ATTEMPTS: u8 = 10;
+
+/// The default for [`Options::max_bit_ticks`].
+pub const DEFAULT_MAX_BIT_TICKS: u32 = 1_000_000;
+
 pub const DEFAULT_DHT11_OPTIONS: Options = Options {
     min_read_interval: MIN_DHT11_READ_INTERVAL,
     max_attempts: 1,
+    start_pulse: None,
+    power_on_delay: Duration::from_millis(0),
+    require_end_pulse: true,
+    reject_all_zero: false,
+    bit_timeout_multiplier: 4,
+    max_bit_ticks: DEFAULT_MAX_BIT_TICKS,
+    inverted_line: false,
+    quiet_period: None,
+    receive_deadline: None,
+    enforce_range: true,
 };
 
 pub const DEFAULT_DHT22_OPTIONS: Options = Options {
     min_read_interval: MIN_DHT22_READ_INTERVAL,
     max_attempts: 1,
+    start_pulse: None,
+    power_on_delay: Duration::from_millis(0),
+    require_end_pulse: true,
+    reject_all_zero: false,
+    bit_timeout_multiplier: 4,
+    max_bit_ticks: DEFAULT_MAX_BIT_TICKS,
+    inverted_line: false,
+    quiet_period: None,
+    receive_deadline: None,
+    enforce_range: true,
 };
 
 macro_rules! dhtxx_impl {
@@ -161,6 +731,9 @@ macro_rules! dhtxx_impl {
         {
             base: DhtBase<TInputPin, TOutputPin, TimeFn, ElapsedFn, TTime>,
             options: Options,
+            last_reading: Option<($response_type, TTime)>,
+            last_read_retried: bool,
+            stats: ReadStats,
         }
 
         impl<TInputPin, TOutputPin, TError, TimeFn, ElapsedFn, TTime>
@@ -181,9 +754,10 @@ macro_rules! dhtxx_impl {
             ///
             /// Setting [`Options::max_attempts`] to a value greater than 1 will enable this
             /// function to seamlessly retry [`Error::BadData`] errors. Note that any
-            /// [`Error::NoResponse`] errors will be returned immediately. Keep in mind that the
-            /// minimum read interval must pass between each attempt, so each attempt adds
-            /// significantly to the duration of this function.
+            /// [`Error::NoResponse`], [`Error::Disconnected`], [`Error::Deadline`],
+            /// [`Error::StalledClock`] or [`Error::InvalidState`] errors will be returned
+            /// immediately. Keep in mind that the minimum read interval must pass between each
+            /// attempt, so each attempt adds significantly to the duration of this function.
             ///
             /// The provided `time_fn` closure should provide some representation of a given instant that
             /// can be used with `elapsed_since_fn` to determine how much time has passed since then. It
@@ -195,22 +769,210 @@ macro_rules! dhtxx_impl {
                 elapsed_since_fn: ElapsedFn,
                 options: Option<Options>,
             ) -> Result<$name<TInputPin, TOutputPin, TimeFn, ElapsedFn, TTime>, Error<TError>> {
+                let options = if options.is_none() {
+                    $default_options
+                } else {
+                    let options = options.unwrap();
+                    if options.min_read_interval < $min_read_interval
+                        || options.max_attempts < 1
+                        || options.max_attempts > MAX_ATTEMPTS
+                        || options.bit_timeout_multiplier < 1
+                        || options.max_bit_ticks < 1
+                        || options
+                            .start_pulse
+                            .is_some_and(|pulse| pulse < $ping_duration)
+                        || checked_worst_case_read_duration(&options, $ping_duration).is_none()
+                    {
+                        return Err(Error::InvalidArgument);
+                    }
+                    options
+                };
                 Ok($name {
-                    base: DhtBase::new(pin, time_fn, elapsed_since_fn)?,
-                    options: if options.is_none() {
-                        $default_options
-                    } else {
-                        let options = options.unwrap();
-                        if options.min_read_interval < $min_read_interval
-                            || options.max_attempts < 1
-                        {
-                            return Err(Error::InvalidArgument);
-                        }
-                        options
-                    },
+                    base: DhtBase::new(pin, time_fn, elapsed_since_fn, options.power_on_delay)?,
+                    options,
+                    last_reading: None,
+                    last_read_retried: false,
+                    stats: ReadStats::default(),
                 })
             }
 
+            /// The longest time that a single call to `read` can take, given this sensor's options.
+            ///
+            /// This assumes every attempt waits the full minimum read interval, and then sends the
+            /// start pulse and blocks for [`MAX_BLOCKING_READ_DURATION`]. It does not include the
+            /// one-time [`Options::power_on_delay`], nor any latency added by the `delay_fn`.
+            pub fn worst_case_read_duration(&self) -> Duration {
+                // The options were checked when the sensor was constructed, so this can't
+                // overflow, but saturate just in case.
+                checked_worst_case_read_duration(&self.options, $ping_duration)
+                    .unwrap_or(Duration::MAX)
+            }
+
+            /// The minimum time that this sensor waits between reads.
+            ///
+            /// This is [`Options::min_read_interval`] from the options the sensor was constructed
+            /// with, or from the default options if none were given. It doesn't include the
+            /// one-time [`Options::power_on_delay`].
+            pub fn effective_min_read_interval(&self) -> Duration {
+                self.options.min_read_interval
+            }
+
+            /// The most recent successful reading, and the time at which it was read.
+            ///
+            /// This is `None` until the first successful read. It's up to the caller to decide
+            /// whether the reading is recent enough to use, e.g. by passing the time to the
+            /// `elapsed_since_fn`. Reusing a recent reading avoids waiting for the minimum read
+            /// interval when temperature and humidity are needed at different times.
+            pub fn last_reading(&self) -> Option<($response_type, TTime)> {
+                self.last_reading
+            }
+
+            /// Whether the most recent successful read needed more than one attempt.
+            ///
+            /// This is a quick indicator of the line's health, without the detail of `stats`. It's
+            /// false until the first successful read, and is unaffected by failed reads.
+            pub fn last_read_retried(&self) -> bool {
+                self.last_read_retried
+            }
+
+            /// Counts of the outcomes of every read attempt since the sensor was constructed, or
+            /// since the last call to `reset_stats`.
+            pub fn stats(&self) -> ReadStats {
+                self.stats
+            }
+
+            /// Resets all of the counts returned by `stats` to zero.
+            pub fn reset_stats(&mut self) {
+                self.stats = ReadStats::default();
+            }
+
+            /// How long the next `read` will wait before requesting data from the sensor.
+            ///
+            /// This is zero once the minimum read interval (or the power-on delay, before the first
+            /// read) has passed since the last read.
+            pub fn time_until_ready(&self) -> Duration {
+                self.base.time_until_ready(self.options.min_read_interval)
+            }
+
+            /// Restarts the minimum read interval from now, as if a read just finished.
+            ///
+            /// Use this to resynchronize the sensor after the clock used by `time_fn` was reset, or
+            /// after the host slept, since the last read time may otherwise be far in the past or
+            /// the future.
+            pub fn reset_timer(&mut self) {
+                self.base.reset_timer();
+            }
+
+            /// Returns the line to its idle state, driven high in output mode, regardless of the
+            /// current mode.
+            ///
+            /// Use this to un-wedge the sensor if a `request` was abandoned without calling
+            /// `receive`, or if a read was otherwise interrupted, e.g. by cancelling its future.
+            /// This restarts the minimum read interval if the pin was in input mode. Returns
+            /// [`Error::InvalidState`] if the pin was already lost.
+            pub fn recover(&mut self) -> Result<(), Error<TError>> {
+                self.base.recover()
+            }
+
+            /// Makes sure the sensor has released the line to output mode, e.g. before another
+            /// peripheral uses a shared line.
+            ///
+            /// If the pin is in input mode, this swaps it back to output mode, driven high, and
+            /// restarts the minimum read interval. Otherwise, this does nothing, so it's cheap to
+            /// call between uses. Unlike `recover`, this doesn't drive the line high if it's
+            /// already in output mode. Returns [`Error::InvalidState`] if the pin was lost.
+            pub fn ensure_idle(&mut self) -> Result<(), Error<TError>> {
+                self.base.ensure_idle()
+            }
+
+            /// Samples the idle line `samples` times, and returns the fraction of samples that read
+            /// high, as a health check of the wiring.
+            ///
+            /// A healthy pulled-up line returns 1.0, while a lower value suggests a weak pull-up,
+            /// noise, or a degrading connection. The samples are taken 1ms apart, waiting with the
+            /// provided `delay_fn`. This doesn't request data from the sensor or change the pin's
+            /// mode, so the idle pin must be readable while it's an output, as open-drain pins
+            /// usually are. Returns [`Error::InvalidArgument`] if `samples` is 0.
+            #[cfg(not(feature = "no-float"))]
+            pub async fn probe_line<DelayFn, EmptyFuture>(
+                &self,
+                samples: u16,
+                delay_fn: DelayFn,
+            ) -> Result<f32, Error<TError>>
+            where
+                TOutputPin: InputPin<Error = TError>,
+                DelayFn: Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                if samples == 0 {
+                    return Err(Error::InvalidArgument);
+                }
+                let high_samples = self
+                    .base
+                    .count_high_samples(samples, self.options.inverted_line, delay_fn)
+                    .await?;
+                Ok(high_samples as f32 / samples as f32)
+            }
+
+            /// Checks that the time source agrees with `delay_fn`, to catch misconfigured closures
+            /// early.
+            ///
+            /// This delays for [`CLOCK_CHECK_DURATION`], and returns [`Error::ClockMismatch`] if
+            /// the time source measured less than three quarters or more than one and a half times
+            /// that duration. A mismatched time source can make the minimum read interval too
+            /// short, or the response timeouts fire incorrectly. This doesn't use the line, and
+            /// doesn't affect the minimum read interval.
+            pub async fn verify_clock<DelayFn, EmptyFuture>(
+                &self,
+                delay_fn: DelayFn,
+            ) -> Result<(), Error<TError>>
+            where
+                DelayFn: Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                self.base.verify_clock(delay_fn).await
+            }
+
+            /// Requests data from the sensor, which must then be read with `receive`.
+            ///
+            /// Together, these perform a single attempt of `read`, without any retries. This is
+            /// useful for schedulers that want to yield while the start pulse is sent, which
+            /// waits using the provided `delay_fn`. Like `read`, this first waits for the minimum
+            /// read interval if necessary.
+            ///
+            /// The line is held low until `receive` is called, so any time spent in between extends
+            /// the start pulse. Call `receive` as soon as this completes: some sensors (especially
+            /// the DHT22) won't respond to a start pulse that is much longer than expected.
+            pub async fn request<DelayFn, EmptyFuture>(
+                &mut self,
+                delay_fn: DelayFn,
+            ) -> Result<(), Error<TError>>
+            where
+                DelayFn: Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                self.base
+                    .request(
+                        self.options.start_pulse.unwrap_or($ping_duration),
+                        &self.options,
+                        delay_fn,
+                    )
+                    .await
+            }
+
+            /// Receives the sensor's response to a `request`.
+            ///
+            /// This performs blocking I/O reads for about 4ms, in the same way as `read`.
+            pub fn receive(&mut self) -> Result<$response_type, Error<TError>> {
+                let result = self.base.receive::<$response_type>(&self.options, None);
+                self.stats.record(&result);
+                if let Ok(response) = result {
+                    self.last_reading = Some((response, self.base.last_read_time));
+                    self.last_read_retried = false;
+                }
+                result
+            }
+
             /// Reads data from the DHT sensor using the minimum read interval.
             ///
             /// This will asynchronously sleep using the provided `delay_fn` if `read` is called within the
@@ -218,8 +980,9 @@ macro_rules! dhtxx_impl {
             /// millisecond precision or better.
             ///
             /// Due to the tight timing necessary to distinguish bits in the DHT's response, this performs
-            /// blocking I/O reads while receiving data. This blocking portion takes about 4ms (full range:
-            /// 3200-4800us, depending on the data).
+            /// blocking I/O reads while receiving data. This blocking portion typically takes
+            /// [`TYPICAL_BLOCKING_RECEIVE`], and at most [`MAX_BLOCKING_RECEIVE`], depending on the
+            /// data.
             pub async fn read<DelayFn, EmptyFuture>(
                 &mut self,
                 delay_fn: DelayFn,
@@ -228,40 +991,360 @@ macro_rules! dhtxx_impl {
                 DelayFn: Copy + Fn(Duration) -> EmptyFuture,
                 EmptyFuture: core::future::Future<Output = ()>,
             {
-                let mut last_result: Option<Result<$response_type, Error<TError>>> = None;
-                for _ in 0..self.options.max_attempts {
-                    last_result = Some(
-                        self.base
+                self.read_with_observer(delay_fn, |_, _| {}).await
+            }
+
+            /// Reads data from the DHT sensor in the same way as `read`, calling `observer` after
+            /// each attempt.
+            ///
+            /// The observer receives the zero-based attempt index and that attempt's result. This
+            /// is useful for debugging intermittent [`Error::BadData`] errors that would otherwise
+            /// be hidden by retries. It doesn't affect the returned value.
+            pub async fn read_with_observer<DelayFn, EmptyFuture, ObserverFn>(
+                &mut self,
+                delay_fn: DelayFn,
+                observer: ObserverFn,
+            ) -> Result<$response_type, Error<TError>>
+            where
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+                ObserverFn: FnMut(u8, &Result<$response_type, Error<TError>>),
+            {
+                self.read_attempts(delay_fn, self.options, None, observer)
+                    .await
+            }
+
+            /// Reads data from the DHT sensor in the same way as `read`, but decodes each bit with
+            /// the given tick threshold instead of detecting one from the response.
+            ///
+            /// Bits that took more than `threshold` ticks are read as 1s. This is intended for
+            /// calibration, e.g. to try several thresholds against the same hardware on successive
+            /// reads. See [`analyze_bit_ticks`] for how the threshold is normally detected.
+            pub async fn read_with_threshold<DelayFn, EmptyFuture>(
+                &mut self,
+                delay_fn: DelayFn,
+                threshold: u32,
+            ) -> Result<$response_type, Error<TError>>
+            where
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                self.read_attempts(delay_fn, self.options, Some(threshold), |_, _| {})
+                    .await
+            }
+
+            async fn read_attempts<DelayFn, EmptyFuture, ObserverFn>(
+                &mut self,
+                delay_fn: DelayFn,
+                options: Options,
+                threshold: Option<u32>,
+                mut observer: ObserverFn,
+            ) -> Result<$response_type, Error<TError>>
+            where
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+                ObserverFn: FnMut(u8, &Result<$response_type, Error<TError>>),
+            {
+                let ping_duration = options.start_pulse.unwrap_or($ping_duration);
+                retry_async(
+                    options.max_attempts,
+                    (self, &mut observer),
+                    |err| {
+                        !matches!(
+                            err,
+                            Error::NoResponse
+                                | Error::Disconnected
+                                | Error::Deadline
+                                | Error::StalledClock
+                                | Error::InvalidState
+                        )
+                    },
+                    move |(sensor, observer), attempt| async move {
+                        let result = sensor
+                            .base
                             .read::<DelayFn, EmptyFuture, $response_type>(
-                                $ping_duration,
-                                self.options.min_read_interval,
+                                ping_duration,
+                                &options,
+                                threshold,
                                 delay_fn,
                             )
-                            .await,
-                    );
-                    match last_result.as_ref().unwrap() {
-                        &Ok(_) => return last_result.unwrap(),
-                        &Err(Error::NoResponse::<TError>) => return last_result.unwrap(),
-                        _ => {}
-                    };
-                }
-                if let Some(final_result) = last_result {
-                    return final_result;
-                }
-                panic!("DHT had no response after all attempts. This should not be possible.");
+                            .await;
+                        sensor.stats.record(&result);
+                        observer(attempt, &result);
+                        if let Ok(response) = result {
+                            sensor.last_reading = Some((response, sensor.base.last_read_time));
+                            sensor.last_read_retried = attempt > 0;
+                        }
+                        ((sensor, observer), result)
+                    },
+                )
+                .await
+            }
+
+            /// Reads the four data bytes and the parity byte sent by the sensor, without checking
+            /// them.
+            ///
+            /// This makes a single attempt, and leaves the parity, range, and end pulse checks to
+            /// the caller, e.g. to apply a custom checksum policy. Errors are still returned if the
+            /// sensor doesn't respond, or a bit times out. The bytes can be decoded with
+            /// `from_bytes` once they're accepted. Like `read`, this first waits for the minimum
+            /// read interval if necessary, but the result isn't recorded in the read stats.
+            pub async fn read_unvalidated<DelayFn, EmptyFuture>(
+                &mut self,
+                delay_fn: DelayFn,
+            ) -> Result<([u8; 4], u8), Error<TError>>
+            where
+                DelayFn: Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                self.base
+                    .read_unvalidated(
+                        self.options.start_pulse.unwrap_or($ping_duration),
+                        &self.options,
+                        delay_fn,
+                    )
+                    .await
+            }
+
+            /// Reads data from a sensor that is shared between tasks, holding the lock for the
+            /// duration of the read.
+            ///
+            /// This serializes reads from all tasks that share the lock, so they can't use the line
+            /// at the same time, and the minimum read interval is enforced across all of them. The
+            /// read is otherwise the same as `read`.
+            pub async fn read_shared<TLock, DelayFn, EmptyFuture>(
+                sensor: &TLock,
+                delay_fn: DelayFn,
+            ) -> Result<$response_type, Error<TError>>
+            where
+                TLock: Lock<Target = Self>,
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                let mut sensor = sensor.lock().await;
+                sensor.read(delay_fn).await
+            }
+
+            /// Reads data from the DHT sensor in the same way as `read`, and tags it with the time
+            /// at which it was read, as given by `time_fn`.
+            pub async fn read_timestamped<DelayFn, EmptyFuture>(
+                &mut self,
+                delay_fn: DelayFn,
+            ) -> Result<Timestamped<$response_type, TTime>, Error<TError>>
+            where
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                let response = self.read(delay_fn).await?;
+                Ok(Timestamped::new(response, self.base.last_read_time))
+            }
+
+            /// Reads data from the DHT sensor in the same way as `read`, and returns the
+            /// temperature in tenths of a degree Celsius and the relative humidity in tenths of a
+            /// percent.
+            ///
+            /// This avoids both the response type and floating point, e.g. a reading of -5.1°C and
+            /// 65.2% is returned as `(-51, 652)`.
+            pub async fn read_deci<DelayFn, EmptyFuture>(
+                &mut self,
+                delay_fn: DelayFn,
+            ) -> Result<(i16, u16), Error<TError>>
+            where
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                let response = self.read(delay_fn).await?;
+                Ok((response.temperature_tenths(), response.humidity_tenths()))
+            }
+
+            /// Reads data from the DHT sensor in the same way as `read`, and converts it to SI
+            /// units. See [`SiReading`].
+            #[cfg(not(feature = "no-float"))]
+            pub async fn read_si<DelayFn, EmptyFuture>(
+                &mut self,
+                delay_fn: DelayFn,
+            ) -> Result<SiReading, Error<TError>>
+            where
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                let response = self.read(delay_fn).await?;
+                Ok(SiReading::from_response(&response))
+            }
+
+            /// Reads data from the DHT sensor in the same way as `read`, but checks the range of
+            /// the temperature and humidity separately. See [`ValidatedReading`].
+            ///
+            /// The parity and end pulse checks still apply to the whole response, so a corrupt
+            /// response fails (and is retried) as usual. This is useful for keeping the temperature
+            /// when the humidity sensing element has failed, which is a common way for these
+            /// sensors to age. Like `read`, the response is recorded as the last reading.
+            #[cfg(not(feature = "no-float"))]
+            pub async fn read_partial<DelayFn, EmptyFuture>(
+                &mut self,
+                delay_fn: DelayFn,
+            ) -> Result<ValidatedReading, Error<TError>>
+            where
+                DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+                EmptyFuture: core::future::Future<Output = ()>,
+            {
+                let options = Options {
+                    enforce_range: false,
+                    ..self.options
+                };
+                let response = self
+                    .read_attempts(delay_fn, options, None, |_, _| {})
+                    .await?;
+                Ok(ValidatedReading::from_response(&response))
+            }
+        }
+
+        #[cfg(not(feature = "no-float"))]
+        impl<TInputPin, TOutputPin, TError, TimeFn, ElapsedFn, TTime, DelayFn, EmptyFuture>
+            TemperatureSource
+            for TemperatureReader<$name<TInputPin, TOutputPin, TimeFn, ElapsedFn, TTime>, DelayFn>
+        where
+            TInputPin: InputPin<Error = TError> + IoPin<TInputPin, TOutputPin, Error = TError>,
+            TOutputPin: OutputPin<Error = TError> + IoPin<TInputPin, TOutputPin, Error = TError>,
+            TimeFn: Fn() -> TTime,
+            ElapsedFn: Fn(TTime) -> Duration,
+            TTime: Copy,
+            DelayFn: Copy + Fn(Duration) -> EmptyFuture,
+            EmptyFuture: core::future::Future<Output = ()>,
+        {
+            type Error = Error<TError>;
+
+            async fn read_temperature_celsius(&mut self) -> Result<f32, Self::Error> {
+                let response = self.sensor.read(self.delay_fn).await?;
+                Ok(response.get_temperature())
             }
         }
     };
 }
 
-const DHT11_PING_DURATION: Duration = Duration::from_millis(18);
-const DHT22_PING_DURATION: Duration = Duration::from_millis(1);
+/// The worst case duration of a `read` with the given options, or `None` if it would overflow.
+fn checked_worst_case_read_duration(
+    options: &Options,
+    ping_duration: Duration,
+) -> Option<Duration> {
+    options
+        .min_read_interval
+        .checked_add(options.start_pulse.unwrap_or(ping_duration))?
+        .checked_add(MAX_BLOCKING_READ_DURATION)?
+        .checked_mul(options.max_attempts as u32)
+}
+
+/// A lock that provides exclusive access to a shared sensor, for use with `read_shared`.
+///
+/// The guard is held for the whole read, including while the read awaits its `delay_fn`. Either
+/// kind of mutex is supported:
+///
+/// - With an async mutex (e.g. `tokio::sync::Mutex`), `lock` awaits the mutex, so waiting for it
+///   yields to the executor, and any `delay_fn` can be used.
+/// - With a blocking mutex (e.g. `std::sync::Mutex`), `lock` acquires the mutex directly. The
+///   `delay_fn` must then complete without yielding, e.g. one from [`blocking_delay_fn`], so that
+///   no other task can run while the guard is held. If the delay yielded, another task could block
+///   on the mutex, which would deadlock a single-threaded executor.
+#[allow(async_fn_in_trait)]
+pub trait Lock {
+    /// The value protected by the lock.
+    type Target;
+    /// Provides access to the value until it's dropped, at which point the lock is released.
+    type Guard<'a>: core::ops::DerefMut<Target = Self::Target>
+    where
+        Self: 'a;
+
+    /// Waits until the lock is available, then acquires it.
+    async fn lock(&self) -> Self::Guard<'_>;
+}
+
+/// Counts of read attempt outcomes, for tracking a sensor's reliability over time.
+///
+/// Each attempt is counted separately, so a `read` that retries [`Error::BadData`] may increment
+/// several counts. Counts saturate rather than overflowing.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReadStats {
+    /// Attempts that returned a valid response.
+    pub successes: u32,
+    /// Attempts that failed with [`Error::BadData`].
+    pub bad_data: u32,
+    /// Attempts that failed with [`Error::NoResponse`] or [`Error::Disconnected`].
+    pub no_response: u32,
+}
+
+impl ReadStats {
+    fn record<T, TError>(&mut self, result: &Result<T, Error<TError>>) {
+        let count = match result {
+            Ok(_) => &mut self.successes,
+            Err(Error::BadData) => &mut self.bad_data,
+            Err(Error::NoResponse) | Err(Error::Disconnected) => &mut self.no_response,
+            Err(_) => return,
+        };
+        *count = count.saturating_add(1);
+    }
+}
+
+/// Pairs a DHT sensor with the `delay_fn` to read it with, so that it can be used as a
+/// [`TemperatureSource`].
+#[cfg(not(feature = "no-float"))]
+#[derive(Debug)]
+pub struct TemperatureReader<TSensor, DelayFn> {
+    pub sensor: TSensor,
+    pub delay_fn: DelayFn,
+}
+
+#[cfg(not(feature = "no-float"))]
+impl<TSensor, DelayFn> TemperatureReader<TSensor, DelayFn> {
+    pub fn new(sensor: TSensor, delay_fn: DelayFn) -> Self {
+        TemperatureReader { sensor, delay_fn }
+    }
+}
+
+/// An upper bound on how long each read attempt blocks while receiving the sensor's response.
+///
+/// The response itself takes at most [`MAX_BLOCKING_RECEIVE`], so this leaves some headroom for a
+/// slow HAL. This is what [`Dht11::worst_case_read_duration`] and
+/// [`Dht22::worst_case_read_duration`] budget for each attempt.
+pub const MAX_BLOCKING_READ_DURATION: Duration = Duration::from_millis(5);
+
+/// The longest the sensor's response takes to transmit, during which `read` blocks without
+/// yielding.
+///
+/// A response of all 1 bits takes this long, while one of all 0 bits takes about 3200us.
+pub const MAX_BLOCKING_RECEIVE: Duration = Duration::from_micros(4800);
+
+/// How long the sensor's response typically takes to transmit, during which `read` blocks without
+/// yielding.
+pub const TYPICAL_BLOCKING_RECEIVE: Duration = Duration::from_micros(4000);
+
+/// How long the line is held low to request data from a DHT11, unless overridden by
+/// [`Options::start_pulse`].
+pub const DHT11_START_PULSE: Duration = Duration::from_millis(18);
+
+/// How long the line is held low to request data from a DHT22, unless overridden by
+/// [`Options::start_pulse`].
+pub const DHT22_START_PULSE: Duration = Duration::from_millis(1);
+
+/// How long `verify_clock` delays for while comparing the delay function to the time source.
+pub const CLOCK_CHECK_DURATION: Duration = Duration::from_millis(20);
+
+/// How long `probe_line` waits between samples.
+#[cfg(not(feature = "no-float"))]
+const PROBE_LINE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The shortest wait before a read that's used to check for [`Error::StalledClock`]. This is
+/// long enough to register on a time source with millisecond precision.
+const MIN_STALL_CHECK_WAIT: Duration = Duration::from_millis(2);
+
+/// How many evenly spaced waits [`Options::quiet_period`] is split into. The line is sampled
+/// before and after each one.
+const QUIET_PERIOD_STEPS: u32 = 10;
 
 dhtxx_impl!(
     Dht11,
     default_options: DEFAULT_DHT11_OPTIONS,
     min_read_interval: MIN_DHT11_READ_INTERVAL,
-    ping_duration: DHT11_PING_DURATION,
+    ping_duration: DHT11_START_PULSE,
     response_type: Dht11Response
 );
 
@@ -269,10 +1352,64 @@ dhtxx_impl!(
     Dht22,
     default_options: DEFAULT_DHT22_OPTIONS,
     min_read_interval: MIN_DHT22_READ_INTERVAL,
-    ping_duration: DHT22_PING_DURATION,
+    ping_duration: DHT22_START_PULSE,
     response_type: Dht22Response
 );
 
+/// Constructs DHT sensors that share a single time source.
+///
+/// This is useful when reading from multiple sensors, so that the `time_fn` and `elapsed_since_fn`
+/// closures only need to be defined once. Each sensor borrows the closures from this bus, and is
+/// still read independently.
+#[derive(Debug)]
+pub struct DhtBus<TimeFn, ElapsedFn> {
+    time_fn: TimeFn,
+    elapsed_since_fn: ElapsedFn,
+}
+
+impl<TimeFn, ElapsedFn, TTime> DhtBus<TimeFn, ElapsedFn>
+where
+    TimeFn: Fn() -> TTime,
+    ElapsedFn: Fn(TTime) -> Duration,
+    TTime: Copy,
+{
+    /// Constructs a bus with the given time source.
+    ///
+    /// See [`Dht11::new`] for the requirements of `time_fn` and `elapsed_since_fn`.
+    pub fn new(time_fn: TimeFn, elapsed_since_fn: ElapsedFn) -> Self {
+        DhtBus {
+            time_fn,
+            elapsed_since_fn,
+        }
+    }
+
+    /// Constructs a DHT11 sensor that reads from the given pin. See [`Dht11::new`].
+    pub fn dht11<TInputPin, TOutputPin, TError>(
+        &self,
+        pin: TOutputPin,
+        options: Option<Options>,
+    ) -> Result<Dht11<TInputPin, TOutputPin, &TimeFn, &ElapsedFn, TTime>, Error<TError>>
+    where
+        TInputPin: InputPin<Error = TError> + IoPin<TInputPin, TOutputPin, Error = TError>,
+        TOutputPin: OutputPin<Error = TError> + IoPin<TInputPin, TOutputPin, Error = TError>,
+    {
+        Dht11::new(pin, &self.time_fn, &self.elapsed_since_fn, options)
+    }
+
+    /// Constructs a DHT22 sensor that reads from the given pin. See [`Dht22::new`].
+    pub fn dht22<TInputPin, TOutputPin, TError>(
+        &self,
+        pin: TOutputPin,
+        options: Option<Options>,
+    ) -> Result<Dht22<TInputPin, TOutputPin, &TimeFn, &ElapsedFn, TTime>, Error<TError>>
+    where
+        TInputPin: InputPin<Error = TError> + IoPin<TInputPin, TOutputPin, Error = TError>,
+        TOutputPin: OutputPin<Error = TError> + IoPin<TInputPin, TOutputPin, Error = TError>,
+    {
+        Dht22::new(pin, &self.time_fn, &self.elapsed_since_fn, options)
+    }
+}
+
 #[derive(Debug)]
 struct DhtBase<TInputPin, TOutputPin, TimeFn, ElapsedFn, TTime>
 where
@@ -283,8 +1420,15 @@ where
     input_pin: Option<TInputPin>,
     output_pin: Option<TOutputPin>,
     last_read_time: TTime,
+    // Extra time to wait before the first read, cleared once the first read begins.
+    power_on_delay: Duration,
+    // Whether the time source has been checked for advancing, which happens on the first read.
+    clock_checked: bool,
     time_fn: TimeFn,
     elapsed_since_fn: ElapsedFn,
+    // Tick counts for each bit of the current response. Every read overwrites all 40 entries
+    // before parsing them, so this is reused rather than growing the stack on each read.
+    bit_ticks: [u32; 40],
 }
 
 impl<TInputPin, TOutputPin, TError, TimeFn, ElapsedFn, TTime>
@@ -307,55 +1451,246 @@ where
         pin: TOutputPin,
         time_fn: TimeFn,
         elapsed_since_fn: ElapsedFn,
+        power_on_delay: Duration,
     ) -> Result<DhtBase<TInputPin, TOutputPin, TimeFn, ElapsedFn, TTime>, Error<TError>> {
         Ok(DhtBase {
             input_pin: None,
             output_pin: Some(pin),
             last_read_time: time_fn(),
+            power_on_delay,
+            clock_checked: false,
             time_fn: time_fn,
             elapsed_since_fn: elapsed_since_fn,
+            bit_ticks: [0; 40],
         })
     }
 
-    /// Reads data from the DHT sensor using the minimum read interval.
-    ///
-    /// This will asynchronously sleep using the provided `delay_fn` if `read`
-    /// is called within the minimum read interval of this DHT sensor. The
-    /// provided function needs to be capable of millisecond precision or
-    /// better.
-    ///
-    /// Due to the tight timing necessary to distinguish bits in the DHT's
-    /// response, this performs blocking I/O reads while receiving data. This
-    /// takes about 4ms (full range: 3200-4800us, depending on the data).
-    async fn read<DelayFn, EmptyFuture, TResponse>(
-        &mut self,
-        ping_duration: Duration,
-        min_read_interval: Duration,
+    /// Reads data from the DHT sensor using the minimum read interval.
+    ///
+    /// This will asynchronously sleep using the provided `delay_fn` if `read`
+    /// is called within the minimum read interval of this DHT sensor. The
+    /// provided function needs to be capable of millisecond precision or
+    /// better.
+    ///
+    /// Due to the tight timing necessary to distinguish bits in the DHT's
+    /// response, this performs blocking I/O reads while receiving data. This
+    /// takes about 4ms (full range: 3200-4800us, depending on the data).
+    ///
+    /// If `threshold` is given, it's used to decode the bits instead of detecting one.
+    async fn read<DelayFn, EmptyFuture, TResponse>(
+        &mut self,
+        ping_duration: Duration,
+        options: &Options,
+        threshold: Option<u32>,
+        delay_fn: DelayFn,
+    ) -> Result<TResponse, Error<TError>>
+    where
+        DelayFn: Fn(Duration) -> EmptyFuture,
+        EmptyFuture: core::future::Future<Output = ()>,
+        TResponse: Response + ResponseInternal,
+    {
+        self.request(ping_duration, options, delay_fn).await?;
+        self.receive(options, threshold)
+    }
+
+    /// Reads the four data bytes and the parity byte, without validating them.
+    async fn read_unvalidated<DelayFn, EmptyFuture>(
+        &mut self,
+        ping_duration: Duration,
+        options: &Options,
+        delay_fn: DelayFn,
+    ) -> Result<([u8; 4], u8), Error<TError>>
+    where
+        DelayFn: Fn(Duration) -> EmptyFuture,
+        EmptyFuture: core::future::Future<Output = ()>,
+    {
+        self.request(ping_duration, options, delay_fn).await?;
+        let ([high_humidity, low_humidity, high_temp, low_temp, parity], _) =
+            self.receive_frame(options, None)?;
+        Ok(([high_humidity, low_humidity, high_temp, low_temp], parity))
+    }
+
+    /// Waits for the minimum read interval, then sends the start pulse to request data.
+    ///
+    /// This leaves the line low, so it must be followed immediately by `receive`.
+    async fn request<DelayFn, EmptyFuture>(
+        &mut self,
+        ping_duration: Duration,
+        options: &Options,
+        delay_fn: DelayFn,
+    ) -> Result<(), Error<TError>>
+    where
+        DelayFn: Fn(Duration) -> EmptyFuture,
+        EmptyFuture: core::future::Future<Output = ()>,
+    {
+        // Double check that the output is driven high so the DHT is ready to send data.
+        if self.output_pin.is_none() {
+            self.swap_to_output_mode()?;
+        }
+
+        let to_wait = self.time_until_ready(options.min_read_interval);
+        self.power_on_delay = Duration::from_millis(0);
+        if to_wait > Duration::from_millis(0) {
+            let start = (self.time_fn)();
+            delay_fn(to_wait).await;
+            // A shorter wait may not register on a clock with millisecond precision, so the check
+            // is deferred until a wait that's long enough.
+            if !self.clock_checked && to_wait >= MIN_STALL_CHECK_WAIT {
+                self.clock_checked = true;
+                if (self.elapsed_since_fn)(start) == Duration::from_millis(0) {
+                    return Err(Error::StalledClock);
+                }
+            }
+        }
+
+        if let Some(quiet_period) = options.quiet_period {
+            self.check_line_quiet(quiet_period, options.inverted_line, &delay_fn)
+                .await?;
+        }
+
+        self.request_data(ping_duration, delay_fn).await
+    }
+
+    /// Samples the idle line the given number of times, [`PROBE_LINE_INTERVAL`] apart, and returns
+    /// how many samples were high (or low, if the line is inverted).
+    #[cfg(not(feature = "no-float"))]
+    async fn count_high_samples<DelayFn, EmptyFuture>(
+        &self,
+        samples: u16,
+        inverted: bool,
+        delay_fn: DelayFn,
+    ) -> Result<u16, Error<TError>>
+    where
+        TOutputPin: InputPin<Error = TError>,
+        DelayFn: Fn(Duration) -> EmptyFuture,
+        EmptyFuture: core::future::Future<Output = ()>,
+    {
+        let pin = self.output_pin.as_ref().ok_or(Error::InvalidState)?;
+        let mut high_samples = 0;
+        for sample in 0..samples {
+            if sample > 0 {
+                delay_fn(PROBE_LINE_INTERVAL).await;
+            }
+            if is_line_high(pin, inverted)? {
+                high_samples += 1;
+            }
+        }
+        Ok(high_samples)
+    }
+
+    /// Samples the line across the given period, and fails with [`Error::LineBusy`] if it ever
+    /// leaves its idle (high) state.
+    ///
+    /// The samples are spaced with `delay_fn` rather than timed with the time source, so this
+    /// always finishes, even if the clock is stalled. No request is sent, so this doesn't reset the
+    /// minimum read interval.
+    async fn check_line_quiet<DelayFn, EmptyFuture>(
+        &mut self,
+        period: Duration,
+        inverted: bool,
+        delay_fn: &DelayFn,
+    ) -> Result<(), Error<TError>>
+    where
+        DelayFn: Fn(Duration) -> EmptyFuture,
+        EmptyFuture: core::future::Future<Output = ()>,
+    {
+        let last_read_time = self.last_read_time;
+        let output_pin = self.output_pin.take().ok_or(Error::InvalidState)?;
+        let input_pin: &TInputPin = self
+            .input_pin
+            .insert(output_pin.into_input_pin().map_err(Error::Wrapped)?);
+
+        let interval = period / QUIET_PERIOD_STEPS;
+        let mut result = Ok(());
+        for step in 0..=QUIET_PERIOD_STEPS {
+            if step > 0 {
+                delay_fn(interval).await;
+            }
+            match is_line_low(input_pin, inverted) {
+                Ok(false) => {}
+                Ok(true) => {
+                    result = Err(Error::LineBusy);
+                    break;
+                }
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+
+        self.swap_to_output_mode()?;
+        self.last_read_time = last_read_time;
+        result
+    }
+
+    /// Receives and validates the sensor's response to a `request`.
+    fn receive<TResponse>(
+        &mut self,
+        options: &Options,
+        threshold: Option<u32>,
+    ) -> Result<TResponse, Error<TError>>
+    where
+        TResponse: Response + ResponseInternal,
+    {
+        let bytes = self.receive_data(options, threshold)?;
+        if options.reject_all_zero && bytes == [0; 4] {
+            return Err(Error::BadData);
+        }
+        let result = TResponse::from_raw_bytes(bytes);
+        if options.enforce_range && !result.is_valid() {
+            return Err(Error::BadData);
+        }
+        Ok(result)
+    }
+
+    fn recover(&mut self) -> Result<(), Error<TError>> {
+        if self.input_pin.is_some() {
+            return self.swap_to_output_mode();
+        }
+        self.output_pin
+            .as_mut()
+            .ok_or(Error::InvalidState)?
+            .set_high()
+            .map_err(Error::Wrapped)
+    }
+
+    fn ensure_idle(&mut self) -> Result<(), Error<TError>> {
+        if self.input_pin.is_some() {
+            return self.swap_to_output_mode();
+        }
+        if self.output_pin.is_none() {
+            return Err(Error::InvalidState);
+        }
+        Ok(())
+    }
+
+    fn time_until_ready(&self, min_read_interval: Duration) -> Duration {
+        let min_read_interval = core::cmp::max(min_read_interval, self.power_on_delay);
+        let elapsed_since_last_read = (self.elapsed_since_fn)(self.last_read_time);
+        // Saturate in case a custom clock isn't monotonic.
+        min_read_interval.saturating_sub(elapsed_since_last_read)
+    }
+
+    fn reset_timer(&mut self) {
+        self.last_read_time = (self.time_fn)();
+    }
+
+    async fn verify_clock<DelayFn, EmptyFuture>(
+        &self,
         delay_fn: DelayFn,
-    ) -> Result<TResponse, Error<TError>>
+    ) -> Result<(), Error<TError>>
     where
         DelayFn: Fn(Duration) -> EmptyFuture,
         EmptyFuture: core::future::Future<Output = ()>,
-        TResponse: Response + ResponseInternal,
     {
-        // Double check that the output is driven high so the DHT is ready to send data.
-        if self.output_pin.is_none() {
-            self.swap_to_output_mode()?;
-        }
-
-        let elapsed_since_last_read = (self.elapsed_since_fn)(self.last_read_time);
-        if elapsed_since_last_read < min_read_interval {
-            let to_wait = min_read_interval - elapsed_since_last_read;
-            delay_fn(to_wait).await;
+        let start = (self.time_fn)();
+        delay_fn(CLOCK_CHECK_DURATION).await;
+        let elapsed = (self.elapsed_since_fn)(start);
+        if elapsed < CLOCK_CHECK_DURATION * 3 / 4 || elapsed > CLOCK_CHECK_DURATION * 3 / 2 {
+            return Err(Error::ClockMismatch);
         }
-
-        self.request_data(ping_duration, delay_fn).await?;
-        let bytes = self.receive_data()?;
-        let result = TResponse::from_raw_bytes(bytes);
-        if !result.is_valid() {
-            return Err(Error::BadData);
-        }
-        Ok(result)
+        Ok(())
     }
 
     async fn request_data<DelayFn, EmptyFuture>(
@@ -369,36 +1704,70 @@ where
     {
         self.output_pin
             .as_mut()
-            .unwrap()
+            .ok_or(Error::InvalidState)?
             .set_low()
             .map_err(Error::Wrapped)?;
         delay_fn(ping_duration).await;
         Ok(())
     }
 
-    fn receive_data(&mut self) -> Result<[u8; 4], Error<TError>> {
-        let mut bit_ticks = [0u32; 40];
-        self.input_pin = Some(
-            self.output_pin
-                .take()
-                .unwrap()
-                .into_input_pin()
-                .map_err(Error::Wrapped)?,
-        );
-        let input_pin: &TInputPin = &mut self.input_pin.as_ref().unwrap();
+    fn receive_data(
+        &mut self,
+        options: &Options,
+        threshold: Option<u32>,
+    ) -> Result<[u8; 4], Error<TError>> {
+        let (frame, has_end_pulse) = self.receive_frame(options, threshold)?;
+        let [high_humidity, low_humidity, high_temp, low_temp, received_parity] = frame;
+        let bytes = [high_humidity, low_humidity, high_temp, low_temp];
+
+        if received_parity != parity(bytes) || !has_end_pulse {
+            return Err(Error::BadData);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Receives all five bytes of the response, including the parity byte, and whether the
+    /// response ended with a short end pulse (or `require_end_pulse` is false). Nothing else is
+    /// validated.
+    ///
+    /// If anything fails after the pin is swapped to input mode, including a HAL error partway
+    /// through the frame, the pin is swapped back to output mode before the error is returned, so
+    /// the sensor is left ready for another read.
+    fn receive_frame(
+        &mut self,
+        options: &Options,
+        threshold: Option<u32>,
+    ) -> Result<([u8; 5], bool), Error<TError>> {
+        let inverted = options.inverted_line;
+        let output_pin = self.output_pin.take().ok_or(Error::InvalidState)?;
+        let input_pin: &TInputPin = self
+            .input_pin
+            .insert(output_pin.into_input_pin().map_err(Error::Wrapped)?);
+        let start_time = (self.time_fn)();
 
         // Block for the ACK, and use this to estimate a timeout.
-        let ack_counter = match read_ack(input_pin, &self.time_fn, &self.elapsed_since_fn) {
-            Err(err) => {
-                self.swap_to_output_mode()?;
-                return Err(err);
-            }
-            Ok(count) => count,
-        };
-        let bit_timeout = ack_counter << 2;
+        let (ack_counter, ack_low_counter) =
+            match read_ack(input_pin, inverted, &self.time_fn, &self.elapsed_since_fn) {
+                Err(err) => {
+                    self.swap_to_output_mode()?;
+                    return Err(err);
+                }
+                Ok(count) => count,
+            };
+        // The sensor holds the line low for a fixed 80us during the ACK, and the full ACK should take
+        // at least twice this. Use this as a floor in case the high portions were cut short, which
+        // would otherwise make the timeout too short for the bits that follow.
+        let bit_timeout = core::cmp::max(ack_counter, ack_low_counter << 1)
+            .saturating_mul(options.bit_timeout_multiplier)
+            .min(options.max_bit_ticks);
 
         for i in 0..40 {
-            bit_ticks[i] = match read_bit_with_timeout(input_pin, bit_timeout) {
+            if i % 8 == 0 && is_past_deadline(options, &self.elapsed_since_fn, start_time) {
+                self.swap_to_output_mode()?;
+                return Err(Error::Deadline);
+            }
+            self.bit_ticks[i] = match read_bit_with_timeout(input_pin, inverted, bit_timeout) {
                 Err(err) => {
                     self.swap_to_output_mode()?;
                     return Err(err);
@@ -406,41 +1775,49 @@ where
                 Ok(count) => count,
             };
         }
-        let end_ticks = match read_end_with_timeout(input_pin, bit_timeout) {
-            Err(err) => {
-                self.swap_to_output_mode()?;
-                return Err(err);
-            }
-            Ok(count) => count,
+        if is_past_deadline(options, &self.elapsed_since_fn, start_time) {
+            self.swap_to_output_mode()?;
+            return Err(Error::Deadline);
+        }
+        let end_ticks = if options.require_end_pulse {
+            Some(
+                match read_end_with_timeout(input_pin, inverted, bit_timeout) {
+                    Err(err) => {
+                        self.swap_to_output_mode()?;
+                        return Err(err);
+                    }
+                    Ok(count) => count,
+                },
+            )
+        } else {
+            None
         };
 
         self.swap_to_output_mode()?;
 
-        let threshold = determine_tick_threshold(&bit_ticks);
+        let bit_ticks = &self.bit_ticks;
+        let threshold = threshold.unwrap_or_else(|| determine_tick_threshold(bit_ticks));
         let high_humidity = parse_byte(&bit_ticks[0..8], threshold);
         let low_humidity = parse_byte(&bit_ticks[8..16], threshold);
         let high_temp = parse_byte(&bit_ticks[16..24], threshold);
         let low_temp = parse_byte(&bit_ticks[24..32], threshold);
         let parity = parse_byte(&bit_ticks[32..40], threshold);
 
-        let sum: u16 =
-            high_humidity as u16 + low_humidity as u16 + high_temp as u16 + low_temp as u16;
-        // The last 8 bits should match the parity byte.
-        let expected_parity = sum.to_be_bytes()[1];
-
-        let end_bit = if end_ticks > threshold { 1 } else { 0 };
-        if parity != expected_parity || end_bit == 1 {
-            return Err(Error::BadData);
-        }
+        let has_end_pulse = match end_ticks {
+            Some(ticks) => ticks <= threshold,
+            None => true,
+        };
 
-        Ok([high_humidity, low_humidity, high_temp, low_temp])
+        Ok((
+            [high_humidity, low_humidity, high_temp, low_temp, parity],
+            has_end_pulse,
+        ))
     }
 
     fn swap_to_output_mode(&mut self) -> Result<(), Error<TError>> {
+        let input_pin = self.input_pin.take().ok_or(Error::InvalidState)?;
         self.output_pin = Some(
-            self.input_pin
-                .take()
-                .unwrap()
+            input_pin
                 .into_output_pin(PinState::High)
                 .map_err(Error::Wrapped)?,
         );
@@ -449,54 +1826,129 @@ where
     }
 }
 
+/// Whether [`Options::receive_deadline`] has passed since `start_time`.
+fn is_past_deadline<ElapsedFn, TTime>(
+    options: &Options,
+    elapsed_since_fn: ElapsedFn,
+    start_time: TTime,
+) -> bool
+where
+    ElapsedFn: Fn(TTime) -> Duration,
+{
+    match options.receive_deadline {
+        Some(deadline) => elapsed_since_fn(start_time) > deadline,
+        None => false,
+    }
+}
+
+/// Whether the line is high, or low if the line is inverted.
 #[inline]
-fn read_bit_with_timeout<TInput, TError>(
+fn is_line_high<TInput, TError>(input_pin: &TInput, inverted: bool) -> Result<bool, Error<TError>>
+where
+    TInput: InputPin<Error = TError>,
+{
+    let is_high = if inverted {
+        input_pin.is_low()
+    } else {
+        input_pin.is_high()
+    };
+    is_high.map_err(Error::Wrapped)
+}
+
+/// Whether the line is low, or high if the line is inverted.
+#[inline]
+fn is_line_low<TInput, TError>(input_pin: &TInput, inverted: bool) -> Result<bool, Error<TError>>
+where
+    TInput: InputPin<Error = TError>,
+{
+    let is_low = if inverted {
+        input_pin.is_high()
+    } else {
+        input_pin.is_low()
+    };
+    is_low.map_err(Error::Wrapped)
+}
+
+/// Measures how many ticks the line stays low for, or high if the line is inverted.
+#[inline]
+fn measure_line_low<TInput, TError>(
     input_pin: &TInput,
+    inverted: bool,
     timeout: u32,
 ) -> Result<u32, Error<TError>>
 where
     TInput: InputPin<Error = TError>,
 {
-    let mut counter = 0u32;
-    while input_pin.is_low().map_err(|err| Error::Wrapped(err))? {
-        counter += 1;
-        if counter > timeout {
-            return Err(Error::BadData);
-        }
-    }
-    while input_pin.is_high().map_err(|err| Error::Wrapped(err))? {
-        counter += 1;
-        if counter > timeout {
-            return Err(Error::BadData);
-        }
+    let ticks = if inverted {
+        pulse::measure_high(input_pin, timeout)
+    } else {
+        pulse::measure_low(input_pin, timeout)
+    };
+    ticks.map_err(from_pulse_error)
+}
+
+/// Measures how many ticks the line stays high for, or low if the line is inverted.
+#[inline]
+fn measure_line_high<TInput, TError>(
+    input_pin: &TInput,
+    inverted: bool,
+    timeout: u32,
+) -> Result<u32, Error<TError>>
+where
+    TInput: InputPin<Error = TError>,
+{
+    let ticks = if inverted {
+        pulse::measure_low(input_pin, timeout)
+    } else {
+        pulse::measure_high(input_pin, timeout)
+    };
+    ticks.map_err(from_pulse_error)
+}
+
+/// A pulse that times out while receiving data means the response can't be trusted.
+fn from_pulse_error<TError>(error: pulse::Error<TError>) -> Error<TError> {
+    match error {
+        pulse::Error::Wrapped(error) => Error::Wrapped(error),
+        pulse::Error::Timeout => Error::BadData,
     }
-    Ok(counter)
+}
+
+/// Reads a bit, and returns the number of ticks taken by its low and high portions together.
+#[inline]
+fn read_bit_with_timeout<TInput, TError>(
+    input_pin: &TInput,
+    inverted: bool,
+    timeout: u32,
+) -> Result<u32, Error<TError>>
+where
+    TInput: InputPin<Error = TError>,
+{
+    let low_ticks = measure_line_low(input_pin, inverted, timeout)?;
+    let high_ticks = measure_line_high(input_pin, inverted, timeout - low_ticks)?;
+    Ok(low_ticks + high_ticks)
 }
 
 #[inline]
 fn read_end_with_timeout<TInput, TError>(
     input_pin: &TInput,
+    inverted: bool,
     timeout: u32,
 ) -> Result<u32, Error<TError>>
 where
     TInput: InputPin<Error = TError>,
 {
-    let mut counter = 0u32;
-    while input_pin.is_low().map_err(|err| Error::Wrapped(err))? {
-        counter += 1;
-        if counter > timeout {
-            return Err(Error::BadData);
-        }
-    }
-    Ok(counter)
+    measure_line_low(input_pin, inverted, timeout)
 }
 
+/// Reads the sensor's ACK, and returns the number of ticks taken by the full ACK and by the low
+/// portion of the ACK.
 #[inline]
 fn read_ack<TInput, TError, TimeFn, ElapsedFn, TTime>(
     input_pin: &TInput,
+    inverted: bool,
     time_fn: TimeFn,
     elapsed_since_fn: ElapsedFn,
-) -> Result<u32, Error<TError>>
+) -> Result<(u32, u32), Error<TError>>
 where
     TInput: InputPin<Error = TError>,
     TimeFn: Fn() -> TTime,
@@ -504,18 +1956,29 @@ where
     TTime: Copy,
 {
     const TIMEOUT: Duration = Duration::from_millis(2);
+    // The sensor starts its ACK within 200us of the line being released, so a line that stays
+    // high for much longer than this isn't connected to a sensor.
+    const RESPONSE_TIMEOUT: Duration = Duration::from_micros(500);
     const WATCHDOG_COUNTS: u32 = 1000;
+    // The line should still be pulled high when the sensor is about to respond. If it's already
+    // low, then the line is shorted or the sensor skipped the start of the ACK, so the timing
+    // can't be trusted.
+    if is_line_low(input_pin, inverted)? {
+        return Err(Error::NoResponse);
+    }
     let start_time = time_fn();
     let mut counter: u32 = 0;
-    while input_pin.is_high().map_err(|err| Error::Wrapped(err))? {
+    while is_line_high(input_pin, inverted)? {
         counter += 1;
         if counter % WATCHDOG_COUNTS == 0 {
-            if elapsed_since_fn(start_time) > TIMEOUT {
-                return Err(Error::NoResponse);
+            if elapsed_since_fn(start_time) > RESPONSE_TIMEOUT {
+                // The line never dropped, so nothing is driving it.
+                return Err(Error::Disconnected);
             }
         }
     }
-    while input_pin.is_low().map_err(|err| Error::Wrapped(err))? {
+    let low_start = counter;
+    while is_line_low(input_pin, inverted)? {
         counter += 1;
         if counter % WATCHDOG_COUNTS == 0 {
             if elapsed_since_fn(start_time) > TIMEOUT {
@@ -523,10 +1986,11 @@ where
             }
         }
     }
-    while input_pin.is_high().map_err(|err| Error::Wrapped(err))? {
+    let low_counter = counter - low_start;
+    while is_line_high(input_pin, inverted)? {
         counter += 1;
     }
-    Ok(counter)
+    Ok((counter, low_counter))
 }
 
 // (index, count)
@@ -534,6 +1998,21 @@ where
 struct Peak(i8, u8);
 
 fn determine_tick_threshold(bit_ticks: &[u32]) -> u32 {
+    determine_tick_peaks(bit_ticks).2
+}
+
+/// Finds the typical tick counts of the zero and one bits in a response, and the threshold used to
+/// distinguish them.
+///
+/// Returns `(low_peak_ticks, high_peak_ticks, threshold)`. This is intended for calibration and
+/// diagnostics: if the two peaks are close together, then the timing is marginal and reads are
+/// likely to fail. If the ticks don't have two distinct peaks (e.g. all bits were zero), then the
+/// minimum and maximum tick counts are returned as the peaks, with the threshold at their midpoint.
+pub fn analyze_bit_ticks(bit_ticks: &[u32; 40]) -> (u32, u32, u32) {
+    determine_tick_peaks(bit_ticks)
+}
+
+fn determine_tick_peaks(bit_ticks: &[u32]) -> (u32, u32, u32) {
     let mut min = u32::MAX;
     let mut max = 0;
 
@@ -574,7 +2053,7 @@ fn determine_tick_threshold(bit_ticks: &[u32]) -> u32 {
 
     // Take the midpoint if two peaks were not found.
     if num_peaks < 2 {
-        return min + range / 2;
+        return (min, max, min + range / 2);
     }
 
     // Determine the two highest peaks. These should correspond to the expected
@@ -611,7 +2090,7 @@ fn determine_tick_threshold(bit_ticks: &[u32]) -> u32 {
         high_ticks = index_to_ticks(second_highest_peak.0);
     }
     // Use the mean of the two peaks as the threshold.
-    return (high_ticks + low_ticks) / 2;
+    (low_ticks, high_ticks, (high_ticks + low_ticks) / 2)
 }
 
 fn parse_byte(bit_ticks: &[u32], threshold: u32) -> u8 {
@@ -627,6 +2106,123 @@ fn parse_byte(bit_ticks: &[u32], threshold: u32) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use core::convert::Infallible;
+
+    /// A pin that tracks its mode and output level.
+    struct ModePin {
+        is_output: bool,
+        is_high: bool,
+        mode_changes: u8,
+    }
+
+    impl InputPin for ModePin {
+        type Error = Infallible;
+
+        fn is_high(&self) -> Result<bool, Infallible> {
+            Ok(self.is_high)
+        }
+
+        fn is_low(&self) -> Result<bool, Infallible> {
+            Ok(!self.is_high)
+        }
+    }
+
+    impl OutputPin for ModePin {
+        type Error = Infallible;
+
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.is_high = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.is_high = true;
+            Ok(())
+        }
+    }
+
+    impl IoPin<ModePin, ModePin> for ModePin {
+        type Error = Infallible;
+
+        fn into_input_pin(mut self) -> Result<ModePin, Infallible> {
+            self.is_output = false;
+            self.mode_changes += 1;
+            Ok(self)
+        }
+
+        fn into_output_pin(mut self, state: PinState) -> Result<ModePin, Infallible> {
+            self.is_output = true;
+            self.is_high = state == PinState::High;
+            self.mode_changes += 1;
+            Ok(self)
+        }
+    }
+
+    fn create_base(
+        pin: ModePin,
+    ) -> DhtBase<ModePin, ModePin, impl Fn() -> u32, impl Fn(u32) -> Duration, u32> {
+        let (input_pin, output_pin) = if pin.is_output {
+            (None, Some(pin))
+        } else {
+            (Some(pin), None)
+        };
+        DhtBase {
+            input_pin,
+            output_pin,
+            last_read_time: 0,
+            power_on_delay: Duration::from_millis(0),
+            clock_checked: false,
+            time_fn: || 0,
+            elapsed_since_fn: |_| Duration::from_millis(0),
+            bit_ticks: [0; 40],
+        }
+    }
+
+    #[test]
+    fn ensure_idle_from_input_mode_drives_high() {
+        let mut base = create_base(ModePin {
+            is_output: false,
+            is_high: false,
+            mode_changes: 0,
+        });
+
+        base.ensure_idle().unwrap();
+
+        assert!(base.input_pin.is_none());
+        let pin = base.output_pin.as_ref().unwrap();
+        assert!(pin.is_output);
+        assert!(pin.is_high);
+        assert_eq!(pin.mode_changes, 1);
+    }
+
+    #[test]
+    fn ensure_idle_from_output_mode_does_nothing() {
+        let mut base = create_base(ModePin {
+            is_output: true,
+            is_high: false,
+            mode_changes: 0,
+        });
+
+        base.ensure_idle().unwrap();
+        base.ensure_idle().unwrap();
+
+        let pin = base.output_pin.as_ref().unwrap();
+        assert!(pin.is_output);
+        assert!(!pin.is_high);
+        assert_eq!(pin.mode_changes, 0);
+    }
+
+    #[test]
+    fn ensure_idle_without_pin_fails() {
+        let mut base = create_base(ModePin {
+            is_output: true,
+            is_high: true,
+            mode_changes: 0,
+        });
+        base.output_pin = None;
+
+        assert_eq!(base.ensure_idle(), Err(Error::InvalidState));
+    }
 
     macro_rules! test_is_valid {
         ($name:ident, $type:ty, $bytes:expr, $is_valid:expr) => {
@@ -763,18 +2359,38 @@ mod tests {
         false
     );
 
+    #[test]
+    fn analyze_bit_ticks_bimodal() {
+        let mut bit_ticks = [10u32; 40];
+        for ticks in bit_ticks.iter_mut().skip(20) {
+            *ticks = 30;
+        }
+
+        assert_eq!(analyze_bit_ticks(&bit_ticks), (11, 30, 20));
+    }
+
+    #[test]
+    fn analyze_bit_ticks_single_peak() {
+        let bit_ticks = [10u32; 40];
+
+        assert_eq!(analyze_bit_ticks(&bit_ticks), (10, 10, 10));
+    }
+
+    #[cfg(not(feature = "no-float"))]
     #[test]
     fn dht11_get_humidity() {
         let response = Dht11Response::from_raw_bytes([71, 2, 0, 0]);
         assert_eq!(response.get_humidity(), 71.2);
     }
 
+    #[cfg(not(feature = "no-float"))]
     #[test]
     fn dht11_get_temperature() {
         let response = Dht11Response::from_raw_bytes([0, 0, 60, 3]);
         assert_eq!(response.get_temperature(), 60.3);
     }
 
+    #[cfg(not(feature = "no-float"))]
     #[test]
     fn dht22_get_humidity() {
         let response =
@@ -782,6 +2398,7 @@ mod tests {
         assert_eq!(response.get_humidity(), 51.3);
     }
 
+    #[cfg(not(feature = "no-float"))]
     #[test]
     fn dht22_get_temperature() {
         let response =
@@ -789,6 +2406,223 @@ mod tests {
         assert_eq!(response.get_temperature(), 41.3);
     }
 
+    #[test]
+    fn dht11_tenths() {
+        let response = Dht11Response::from_raw_bytes([71, 2, 60, 3]);
+        assert_eq!(response.humidity_tenths(), 712);
+        assert_eq!(response.temperature_tenths(), 603);
+    }
+
+    #[test]
+    fn dht22_tenths_negative() {
+        let response = Dht22Response::from_raw_bytes([0x02, 0x8C, 0x80, 0x65]);
+        assert_eq!(response.humidity_tenths(), 652);
+        assert_eq!(response.temperature_tenths(), -101);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    macro_rules! assert_close {
+        ($left:expr, $right:expr, $delta:expr) => {
+            assert!(
+                ($left - $right).abs() <= $delta,
+                "left = {}, right = {}, not within delta = {}",
+                $left,
+                $right,
+                $delta
+            );
+        };
+    }
+
+    macro_rules! test_from_values_round_trip {
+        ($name:ident, $type:ty, $temperature:expr, $humidity:expr) => {
+            #[cfg(not(feature = "no-float"))]
+            #[test]
+            fn $name() {
+                let response = <$type>::from_values($temperature, $humidity);
+
+                assert!(response.is_valid());
+                assert_close!(response.get_temperature(), $temperature, 0.05);
+                assert_close!(response.get_humidity(), $humidity, 0.05);
+            }
+        };
+    }
+
+    test_from_values_round_trip!(dht11_from_values, Dht11Response, 23.4, 56.7);
+    test_from_values_round_trip!(dht11_from_values_rounds, Dht11Response, 23.46, 56.74);
+    test_from_values_round_trip!(dht22_from_values, Dht22Response, 23.4, 56.7);
+    test_from_values_round_trip!(dht22_from_values_rounds, Dht22Response, 23.46, 56.74);
+    test_from_values_round_trip!(dht22_from_values_negative, Dht22Response, -12.3, 4.5);
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dht22_from_values_sets_sign_bit() {
+        let response = Dht22Response::from_values(-41.3, 0.0);
+        assert_eq!(response.temperature_x10, 413 | 0x8000);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dht22_from_values_negative_zero_has_no_sign_bit() {
+        assert_eq!(Dht22Response::from_values(-0.0, 0.0).temperature_x10, 0);
+        assert_eq!(Dht22Response::from_values(-0.04, 0.0).temperature_x10, 0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dht22_from_values_clamps_out_of_range() {
+        let response = Dht22Response::from_values(-5000.0, 7000.0);
+        assert_eq!(response.temperature_x10, 0xFFFF);
+        assert_eq!(response.humidity_x10, u16::MAX);
+
+        let response = Dht22Response::from_values(5000.0, -1.0);
+        assert_eq!(response.temperature_x10, 0x7FFF);
+        assert_eq!(response.humidity_x10, 0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dht11_from_values_clamps_out_of_range() {
+        let response = Dht11Response::from_values(300.0, -1.0);
+        assert_eq!(response.temperature, 255);
+        assert_eq!(response.temperature_decimal, 9);
+        assert_eq!(response.humidity, 0);
+        assert_eq!(response.humidity_decimal, 0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn from_values_nan_is_zero() {
+        let response = Dht22Response::from_values(f32::NAN, f32::NAN);
+        assert_eq!(response.temperature_x10, 0);
+        assert_eq!(response.humidity_x10, 0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dedup_consecutive_collapses_identical_responses() {
+        let mut dedup = DedupConsecutive::new();
+        let first = Dht11Response::from_raw_bytes([40, 0, 21, 0]);
+        let changed = Dht11Response::from_raw_bytes([40, 0, 22, 0]);
+
+        assert_eq!(dedup.filter(first), Deduped::Changed(first));
+        assert_eq!(dedup.filter(first), Deduped::Unchanged);
+        assert_eq!(dedup.filter(changed), Deduped::Changed(changed));
+        assert_eq!(dedup.filter(changed), Deduped::Unchanged);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dedup_consecutive_reports_drift_past_tolerance() {
+        let mut dedup = DedupConsecutive::with_tolerances(0.5, 2.0);
+
+        let first = Dht22Response::from_values(21.0, 40.0);
+        assert_eq!(dedup.filter(first), Deduped::Changed(first));
+        assert_eq!(
+            dedup.filter(Dht22Response::from_values(21.3, 40.0)),
+            Deduped::Unchanged
+        );
+        let drifted = Dht22Response::from_values(21.6, 40.0);
+        assert_eq!(dedup.filter(drifted), Deduped::Changed(drifted));
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn approx_eq_within_tolerances() {
+        let response = Dht22Response::from_values(21.0, 40.0);
+        assert!(response.approx_eq(&Dht22Response::from_values(21.3, 41.5), 0.5, 2.0));
+        assert!(response.approx_eq(&Dht22Response::from_values(20.7, 38.5), 0.5, 2.0));
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn approx_eq_outside_tolerances() {
+        let response = Dht22Response::from_values(21.0, 40.0);
+        assert!(!response.approx_eq(&Dht22Response::from_values(21.6, 40.0), 0.5, 2.0));
+        assert!(!response.approx_eq(&Dht22Response::from_values(21.0, 42.5), 0.5, 2.0));
+        assert!(!response.approx_eq(&Dht22Response::from_values(-21.0, 40.0), 0.5, 2.0));
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    const ALARM_CONFIG: AlarmConfig = AlarmConfig {
+        min_temperature: 10.0,
+        max_temperature: 30.0,
+        min_humidity: 20.0,
+        max_humidity: 60.0,
+    };
+
+    macro_rules! test_check_alarms {
+        ($name:ident, $temperature:expr, $humidity:expr, $expected:expr) => {
+            #[cfg(not(feature = "no-float"))]
+            #[test]
+            fn $name() {
+                let status =
+                    Dht22Response::from_values($temperature, $humidity).check_alarms(&ALARM_CONFIG);
+                assert_eq!(status, $expected);
+                assert_eq!(status.is_triggered(), $expected != AlarmStatus::default());
+            }
+        };
+    }
+
+    test_check_alarms!(check_alarms_clear, 20.0, 40.0, AlarmStatus::default());
+    test_check_alarms!(check_alarms_at_bounds, 30.0, 20.0, AlarmStatus::default());
+    test_check_alarms!(
+        check_alarms_temperature_low,
+        9.9,
+        40.0,
+        AlarmStatus {
+            temperature_low: true,
+            ..AlarmStatus::default()
+        }
+    );
+    test_check_alarms!(
+        check_alarms_temperature_high,
+        30.1,
+        40.0,
+        AlarmStatus {
+            temperature_high: true,
+            ..AlarmStatus::default()
+        }
+    );
+    test_check_alarms!(
+        check_alarms_humidity_low,
+        20.0,
+        19.9,
+        AlarmStatus {
+            humidity_low: true,
+            ..AlarmStatus::default()
+        }
+    );
+    test_check_alarms!(
+        check_alarms_humidity_high,
+        20.0,
+        60.1,
+        AlarmStatus {
+            humidity_high: true,
+            ..AlarmStatus::default()
+        }
+    );
+
+    #[test]
+    fn dht11_normalized_clamps_decimals() {
+        let response = Dht11Response::from_bytes([40, 0x0C, 21, 0x0C]).normalized();
+        assert_eq!(response, Dht11Response::from_bytes([40, 9, 21, 9]));
+        assert!(response.is_valid());
+    }
+
+    #[test]
+    fn dht11_normalized_keeps_valid_decimals() {
+        let response = Dht11Response::from_bytes([40, 3, 21, 7]);
+        assert_eq!(response.normalized(), response);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dht11_from_values_clamps_negative() {
+        let response = Dht11Response::from_values(-5.0, 10.0);
+        assert_eq!(response, Dht11Response::from_raw_bytes([10, 0, 0, 0]));
+    }
+
+    #[cfg(not(feature = "no-float"))]
     #[test]
     fn dht22_get_temperature_negative() {
         let response = Dht22Response::from_raw_bytes([
@@ -799,4 +2633,43 @@ mod tests {
         ]);
         assert_eq!(response.get_temperature(), -41.3);
     }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dht11_checked_get_humidity() {
+        let response = Dht11Response::from_raw_bytes([50, 5, 20, 0]);
+        assert_eq!(response.checked_get_humidity(), Some(50.5));
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn dht11_checked_get_humidity_decimal_beyond_9() {
+        let response = Dht11Response::from_raw_bytes([50, 12, 20, 0]);
+        assert_eq!(response.checked_get_humidity(), None);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn si_reading_from_dht11() {
+        let reading = SiReading::from_response(&Dht11Response::from_raw_bytes([45, 0, 25, 0]));
+        assert_close!(reading.temperature_kelvin, 298.15, 0.001);
+        assert_close!(reading.relative_humidity, 0.45, 0.0001);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn si_reading_from_dht22_negative() {
+        // -10.5°C, 100.0%
+        let reading =
+            SiReading::from_response(&Dht22Response::from_raw_bytes([0x03, 0xE8, 0x80, 0x69]));
+        assert_close!(reading.temperature_kelvin, 262.65, 0.001);
+        assert_close!(reading.relative_humidity, 1.0, 0.0001);
+    }
+
+    #[test]
+    fn dht22_bytes_match_raw_bytes() {
+        let response = Dht22Response::from_raw_bytes([0x02, 0x8C, 0x80, 0x65]);
+        assert_eq!(response.humidity_bytes(), (0x02, 0x8C));
+        assert_eq!(response.temperature_bytes(), (0x80, 0x65));
+    }
 }
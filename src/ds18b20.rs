@@ -1,23 +1,40 @@
+use crate::shared_pin::{BorrowedPin, SharedIoPin};
+#[cfg(not(feature = "no-float"))]
+use crate::temperature::TemperatureSource;
+use crate::timestamp::Timestamped;
+use crate::util::retry_with;
 use core::time::Duration;
 use embedded_hal::delay::blocking::DelayUs;
 use embedded_hal::digital::PinState;
 use embedded_hal::digital::blocking::{InputPin, IoPin, OutputPin};
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// The maximum resolution of the sensor when in 12-bit mode.
+#[cfg(not(feature = "no-float"))]
 pub const MAX_RESOLUTION_F32: f32 = 0.0625;
+/// The most samples that [`read_temperature_median`] can take.
+pub const MAX_MEDIAN_SAMPLES: u8 = 15;
+
+/// The family code of a DS18B20.
+pub const FAMILY_DS18B20: u8 = 0x28;
+/// The family code of a DS1822, which uses the same temperature format as the DS18B20.
+pub const FAMILY_DS1822: u8 = 0x22;
+/// The family code of a DS18S20, which reads the temperature with 9-bit resolution.
+pub const FAMILY_DS18S20: u8 = 0x10;
 
 const RESET_TIME_US: u16 = 480;
 // Sensors send a 60-240us starting 15-60us after the reset.
-const FIRST_PRESENCE_PULSE_DELAY_US: u8 = 30;
-const SECOND_PRESENCE_PULSE_DELAY_US: u8 = 30;
-const POST_PRESENCE_PULSE_DELAY_US: u16 =
-    RESET_TIME_US - FIRST_PRESENCE_PULSE_DELAY_US as u16 - SECOND_PRESENCE_PULSE_DELAY_US as u16;
+const PRESENCE_SAMPLES: u8 = 2;
+const PRESENCE_SAMPLE_INTERVAL_US: u8 = 30;
 
 const READ_WRITE_RECOVERY_TIME_US: u8 = 1;
 const MIN_READ_WRITE_DURATION_US: u8 = 60;
 const WRITE_1_DURATION_US: u8 = 1;
-const WRITE_1_POST_BIT_DELAY_US: u8 = MIN_READ_WRITE_DURATION_US - WRITE_1_DURATION_US;
+const MAX_WRITE_1_DURATION_US: u8 = 15;
 const WRITE_0_DURATION_US: u8 = 60;
+const MAX_WRITE_0_DURATION_US: u8 = 120;
 const READ_REQUEST_DURATION_US: u8 = 1;
 const READ_SAMPLE_DELAY_US: u8 = 15 - READ_REQUEST_DURATION_US;
 const READ_POST_SAMPLE_DELAY_US: u8 = MIN_READ_WRITE_DURATION_US - READ_SAMPLE_DELAY_US;
@@ -25,23 +42,143 @@ const READ_POST_SAMPLE_DELAY_US: u8 = MIN_READ_WRITE_DURATION_US - READ_SAMPLE_D
 const REPOWER_WINDOW: Duration = Duration::from_micros(10);
 
 const EEPROM_COPY_TIME: Duration = Duration::from_millis(10);
+/// How long to wait before retrying a read that returned bad data.
+const RETRY_BACKOFF: Duration = Duration::from_millis(10);
 
 const CONVERSION_TIME_9BIT: Duration = Duration::from_micros(93_750);
 const CONVERSION_TIME_10BIT: Duration = Duration::from_micros(187_500);
 const CONVERSION_TIME_11BIT: Duration = Duration::from_millis(375);
 const CONVERSION_TIME_12BIT: Duration = Duration::from_millis(750);
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Error<TDelayError, TIoError, TInError, TOutError> {
     WrappedDelay(TDelayError),
     WrappedIo(TIoError),
     WrappedInput(TInError),
     WrappedOutput(TOutError),
     NoSensorsFound,
+    /// The line was still low at the end of the reset's recovery window, after any presence
+    /// pulses should have ended.
+    ///
+    /// Unlike [`Error::NoSensorsFound`], this means something is holding the line low, e.g. a
+    /// sensor with VDD and GND swapped, a short to ground, or a missing pull-up resistor.
+    LineHeldLow,
+    /// The data failed its CRC check, or every byte of the scratchpad was 0x00 or 0xFF.
+    ///
+    /// A line that's stuck or disconnected mid-read produces uniform bytes, which can pass the CRC
+    /// check but aren't a plausible reading from a live sensor, so they're rejected by default.
+    /// See [`ScratchpadCheck`].
     BadData,
+    InvalidArgument,
+    TooManyDevices,
+    /// The sensor didn't finish an operation within the maximum time it should take.
+    Timeout,
+    /// The pin was lost after a previous error. The [`Ds18b20`] must be reconstructed with a new
+    /// pin.
+    InvalidState,
+    /// The caller asked for the operation to stop before it completed.
+    Cancelled,
+    /// The device's family code isn't one this driver knows how to decode (see [`read_auto`]).
+    UnsupportedFamily(u8),
+}
+
+/// A problem found in the results of a device search by [`validate_devices`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchError {
+    /// The device ID's CRC byte doesn't match the rest of its ROM code.
+    BadCrc(DeviceId),
+    /// The device ID was found more than once, which suggests a glitch during the search.
+    Duplicate(DeviceId),
+}
+
+/// Which HAL operation produced a wrapped [`Error`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WrappedKind {
+    /// A delay failed.
+    Delay,
+    /// The pin failed to switch between input and output mode.
+    Io,
+    /// Reading the pin failed.
+    Input,
+    /// Setting the pin's output state failed.
+    Output,
+}
+
+impl<TDelayError, TIoError, TInError, TOutError> Error<TDelayError, TIoError, TInError, TOutError> {
+    /// Which HAL operation produced this error, or `None` if it didn't come from the HAL.
+    ///
+    /// This allows HAL errors to be logged uniformly, without matching each wrapped variant.
+    pub fn wrapped_source(&self) -> Option<WrappedKind> {
+        match self {
+            Error::WrappedDelay(_) => Some(WrappedKind::Delay),
+            Error::WrappedIo(_) => Some(WrappedKind::Io),
+            Error::WrappedInput(_) => Some(WrappedKind::Input),
+            Error::WrappedOutput(_) => Some(WrappedKind::Output),
+            _ => None,
+        }
+    }
+}
+
+/// Timings for each read and write slot on the line, for tuning long or capacitive buses.
+///
+/// The defaults in [`DEFAULT_BUS_TIMING`] are the datasheet minimums, which work well on short
+/// buses. If reads are unreliable, a longer recovery time gives the line more time to return high
+/// between slots.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BusTiming {
+    /// How long to let the line recover between slots, in microseconds. Must be at least 1.
+    pub recovery_us: u32,
+    /// How long to hold the line low to write a 0, in microseconds. Must be in the range
+    /// \[60, 120\].
+    pub write_0_us: u32,
+    /// How long to hold the line low to write a 1, in microseconds. Must be in the range \[1, 15\].
+    pub write_1_us: u32,
+    /// How many times to sample the line for a presence pulse after each reset. A device is
+    /// detected if any sample sees the line low. Must be at least 1.
+    pub presence_samples: u8,
+    /// How long to wait before each presence sample, in microseconds. All of the samples must be
+    /// taken within the 480us reset window.
+    ///
+    /// Sensors hold the line low for 60-240us, starting 15-60us after the reset. With imprecise
+    /// delays, more samples spread across this window make detection more reliable.
+    pub presence_interval_us: u32,
+    /// How long to wait after each reset before sending the first command, in microseconds.
+    ///
+    /// Large multi-drop buses can take longer to settle after the presence pulses, so a short
+    /// wait here can make the first command more reliable. This is 0 by default.
+    pub post_reset_settle_us: u32,
+}
+
+impl BusTiming {
+    /// Whether these timings are within the limits given by the datasheet.
+    pub fn is_valid(&self) -> bool {
+        self.recovery_us >= READ_WRITE_RECOVERY_TIME_US as u32
+            && (WRITE_0_DURATION_US as u32..=MAX_WRITE_0_DURATION_US as u32)
+                .contains(&self.write_0_us)
+            && (WRITE_1_DURATION_US as u32..=MAX_WRITE_1_DURATION_US as u32)
+                .contains(&self.write_1_us)
+            && self.presence_samples >= 1
+            && self.presence_window_us() <= RESET_TIME_US as u32
+    }
+
+    /// How long it takes to take all of the presence samples, or `u32::MAX` if this overflows.
+    fn presence_window_us(&self) -> u32 {
+        self.presence_interval_us
+            .saturating_mul(self.presence_samples as u32)
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+/// The datasheet minimum timings, which are used by all functions that don't take a [`BusTiming`].
+pub const DEFAULT_BUS_TIMING: BusTiming = BusTiming {
+    recovery_us: READ_WRITE_RECOVERY_TIME_US as u32,
+    write_0_us: WRITE_0_DURATION_US as u32,
+    write_1_us: WRITE_1_DURATION_US as u32,
+    presence_samples: PRESENCE_SAMPLES,
+    presence_interval_us: PRESENCE_SAMPLE_INTERVAL_US as u32,
+    post_reset_settle_us: 0,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ResolutionMode {
     /// Nine-bit resolution reads the temperature in 0.5 degree increments.
     NineBit = 0b00,
@@ -54,7 +191,11 @@ pub enum ResolutionMode {
 }
 
 impl ResolutionMode {
-    fn get_conversion_time(self) -> Duration {
+    /// Returns the maximum time the sensor needs to convert a temperature at this resolution.
+    ///
+    /// This is useful when issuing the convert command manually, e.g. to do other work while the
+    /// sensor converts, then waiting the remainder of this time before reading the result.
+    pub fn get_conversion_time(self) -> Duration {
         match self {
             ResolutionMode::NineBit => CONVERSION_TIME_9BIT,
             ResolutionMode::TenBit => CONVERSION_TIME_10BIT,
@@ -63,8 +204,106 @@ impl ResolutionMode {
         }
     }
 
+    /// Returns the highest resolution whose conversion time fits within `max_latency`, or `None`
+    /// if even [`ResolutionMode::NineBit`] takes too long.
+    pub fn highest_within(max_latency: Duration) -> Option<ResolutionMode> {
+        [
+            ResolutionMode::TwelveBit,
+            ResolutionMode::ElevenBit,
+            ResolutionMode::TenBit,
+            ResolutionMode::NineBit,
+        ]
+        .iter()
+        .copied()
+        .find(|resolution| resolution.get_conversion_time() <= max_latency)
+    }
+
     fn get_configuration_byte(self) -> u8 {
-        return ((self as u8) << 5) | 0b1111;
+        // The low 5 bits are reserved, and always read as 1s.
+        return ((self as u8) << 5) | 0b1_1111;
+    }
+
+    fn from_configuration_byte(byte: u8) -> Self {
+        match (byte >> 5) & 0b11 {
+            0b00 => ResolutionMode::NineBit,
+            0b01 => ResolutionMode::TenBit,
+            0b10 => ResolutionMode::ElevenBit,
+            _ => ResolutionMode::TwelveBit,
+        }
+    }
+}
+
+/// How the devices on the line are powered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PowerMode {
+    /// All devices have an external power supply.
+    External,
+    /// At least one device relies on parasitic power from the data line, so the line must be held
+    /// high with a strong pull-up during conversions and EEPROM copies.
+    Parasitic,
+}
+
+/// Caches the [`PowerMode`] of the line, so that it only needs to be detected once.
+///
+/// The power mode is a property of the whole line, which rarely changes, so re-detecting it before
+/// every read wastes time. Create a new cache (or call [`PowerModeCache::clear`]) if devices are
+/// added to or removed from the line.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PowerModeCache {
+    power_mode: Option<PowerMode>,
+}
+
+impl PowerModeCache {
+    pub fn new() -> Self {
+        PowerModeCache { power_mode: None }
+    }
+
+    /// The cached power mode, or `None` if it hasn't been detected yet.
+    pub fn power_mode(&self) -> Option<PowerMode> {
+        self.power_mode
+    }
+
+    /// Forgets the cached power mode, so that it is detected again on next use.
+    pub fn clear(&mut self) {
+        self.power_mode = None;
+    }
+
+    /// Returns the cached power mode, detecting it with [`read_power_mode`] if necessary.
+    ///
+    /// If the power mode is already cached, nothing is sent on the line.
+    pub fn get_or_detect<
+        TIoPin,
+        TInPin,
+        TOutPin,
+        TIoError,
+        TInError,
+        TOutError,
+        TDelay,
+        TDelayError,
+    >(
+        &mut self,
+        pin: TIoPin,
+        delay: &mut TDelay,
+    ) -> PinResult<PowerMode, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+    where
+        TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+        TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+        TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+        TDelay: DelayUs<Error = TDelayError>,
+    {
+        if let Some(power_mode) = self.power_mode {
+            return Ok(ReadResult {
+                data: power_mode,
+                pin: pin
+                    .into_output_pin(PinState::High)
+                    .map_err(Error::WrappedIo)?,
+                phantom_io_pin: core::marker::PhantomData,
+                phantom_in_pin: core::marker::PhantomData,
+            });
+        }
+        let result = read_power_mode(pin, delay)?;
+        self.power_mode = Some(result.data);
+        Ok(result)
     }
 }
 
@@ -77,45 +316,163 @@ impl ResolutionMode {
 /// The ID of a given device.
 ///
 /// Used to speak with that device directly when there are mutliple devices on the line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct DeviceId([u8; 8]);
 
 impl DeviceId {
+    /// Creates an ID from its ROM code, in the order it is sent on the line (i.e. family code
+    /// first, and CRC last).
+    pub fn from_bytes(bytes: [u8; 8]) -> Self {
+        DeviceId(bytes)
+    }
+
+    /// The ROM code, in the order expected by [`DeviceId::from_bytes`].
+    pub fn to_bytes(&self) -> [u8; 8] {
+        self.0
+    }
+
     fn crc(&self) -> u8 {
         self.0[7]
     }
 
-    fn serial(&self) -> u64 {
+    /// The device's 48-bit serial number.
+    ///
+    /// The serial is sent on the line least-significant byte first, as bytes 1-6 of the ROM code,
+    /// so this decodes them as a little-endian integer. The upper 16 bits are always zero.
+    pub fn serial(&self) -> u64 {
         let mut copy = [0u8; 8];
-        for i in 1..7 {
-            copy[i - 1] = self.0[i];
-        }
+        copy[..6].copy_from_slice(&self.serial_le());
         u64::from_le_bytes(copy)
     }
 
-    fn family_code(&self) -> u8 {
+    /// The serial number's bytes, least-significant first (i.e. in the order sent on the line).
+    pub fn serial_le(&self) -> [u8; 6] {
+        let mut serial = [0u8; 6];
+        serial.copy_from_slice(&self.0[1..7]);
+        serial
+    }
+
+    /// The serial number's bytes, most-significant first (i.e. in the order they're usually
+    /// written).
+    pub fn serial_be(&self) -> [u8; 6] {
+        let mut serial = self.serial_le();
+        serial.reverse();
+        serial
+    }
+
+    /// The name that Linux's w1 subsystem gives this device, e.g. `28-0316a2795bff`.
+    ///
+    /// This is the family code and the serial number in lowercase hex, separated by a dash, which
+    /// matches the device's directory in `/sys/bus/w1/devices`. The CRC is not included.
+    pub fn canonical_string(&self) -> CanonicalName {
+        const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut name = [b'-'; 15];
+        let mut bytes = [0u8; 7];
+        bytes[0] = self.family_code();
+        bytes[1..].copy_from_slice(&self.serial_be());
+        for (i, byte) in bytes.iter().enumerate() {
+            // Skip over the dash after the family code.
+            let start = if i == 0 { 0 } else { 2 * i + 1 };
+            name[start] = HEX_DIGITS[(byte >> 4) as usize];
+            name[start + 1] = HEX_DIGITS[(byte & 0xF) as usize];
+        }
+        CanonicalName(name)
+    }
+
+    /// The device's family code, which identifies the type of device (e.g. 0x28 for a DS18B20).
+    pub fn family_code(&self) -> u8 {
         self.0[0]
     }
 
+    /// Whether the ID's CRC byte matches the rest of its ROM code.
+    ///
+    /// IDs read from the line are only trustworthy if this is true, but it's also useful for IDs
+    /// from any other source, e.g. a configuration file.
+    pub fn is_valid(&self) -> bool {
+        self.crc() == self.calculated_crc()
+    }
+
     fn calculated_crc(&self) -> u8 {
-        let mut copy = self.0;
-        copy[7] = 0;
+        compute_crc(&self.0[..7])
+    }
+}
+
+/// A device's name in Linux's w1 subsystem, as returned by [`DeviceId::canonical_string`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CanonicalName([u8; 15]);
+
+impl CanonicalName {
+    pub fn as_str(&self) -> &str {
+        // Only ASCII hex digits and a dash are ever written.
+        core::str::from_utf8(&self.0).unwrap()
+    }
+}
+
+impl core::fmt::Display for CanonicalName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-        let x = u64::from_le_bytes(copy);
+/// IDs are ordered by family code, then by serial number.
+///
+/// This gives a stable order for devices found by [`search_devices`], regardless of the order the
+/// search finds them in. The CRC byte is only compared if both of these are equal, which keeps the
+/// ordering consistent with equality.
+impl Ord for DeviceId {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.family_code(), self.serial(), self.crc()).cmp(&(
+            other.family_code(),
+            other.serial(),
+            other.crc(),
+        ))
+    }
+}
 
-        compute_crc(x)
+impl PartialOrd for DeviceId {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-fn compute_crc(x: u64) -> u8 {
-    let x4 = x.wrapping_mul(x).wrapping_mul(x).wrapping_mul(x);
-    let x5 = x4.wrapping_mul(x);
-    let x8 = x5.wrapping_mul(x).wrapping_mul(x).wrapping_mul(x);
+/// The Dallas/Maxim CRC-8 polynomial (x<sup>8</sup> + x<sup>5</sup> + x<sup>4</sup> + 1), in
+/// reversed bit order since data is sent least-significant bit first.
+const CRC8_POLYNOMIAL: u8 = 0x8C;
+
+/// The CRC of each possible byte, generated at compile time.
+const CRC8_TABLE: [u8; 256] = generate_crc8_table();
+
+const fn generate_crc8_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x1 == 0x1 {
+                (crc >> 1) ^ CRC8_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
 
-    (x8.wrapping_add(x5).wrapping_add(x4).wrapping_add(1) & 0xFF) as u8
+/// Computes the CRC of the given bytes, in the order they are sent on the line.
+///
+/// Computing this over the data and its CRC byte results in 0.
+fn compute_crc(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0, |crc, byte| CRC8_TABLE[(crc ^ byte) as usize])
 }
 
 /// Represents a temperature reading from the sensor.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Temperature {
     decimal: i8,
     integer: i16,
@@ -156,37 +513,2038 @@ impl Temperature {
 
     /// The nearest integer of the temperature measurement.
     ///
+    /// Ties (i.e. exactly x.5) are rounded away from zero, so 24.5 becomes 25 and -24.5 becomes -25.
+    /// This is symmetric around zero. See [`Temperature::round_half_up`] for conventional rounding.
+    ///
     /// Calculated without performing floating-point operations.
     pub fn nearest_integer(&self) -> i16 {
-        self.integer + (self.decimal.signum() * (self.decimal.abs() >> 3)) as i16
+        let raw = self.raw() as i32;
+        // Dividing truncates towards zero, so offsetting away from zero rounds ties away from it.
+        let rounded = if raw < 0 {
+            (raw - 8) / 16
+        } else {
+            (raw + 8) / 16
+        };
+        rounded as i16
+    }
+
+    /// The nearest integer of the temperature measurement, rounding ties upwards.
+    ///
+    /// Ties (i.e. exactly x.5) are rounded towards positive infinity, so 24.5 becomes 25 and -24.5
+    /// becomes -24. This matches rounding with `floor(x + 0.5)`.
+    ///
+    /// Calculated without performing floating-point operations.
+    pub fn round_half_up(&self) -> i16 {
+        // Shifting right rounds towards negative infinity.
+        ((self.raw() as i32 + 8) >> 4) as i16
+    }
+
+    /// The raw value of the sensor's temperature register.
+    ///
+    /// This is a two's-complement count of 1/16 degree increments, i.e. exactly the 16-bit value
+    /// that was passed to [`Temperature::from_bytes`].
+    pub fn raw(&self) -> i16 {
+        (self.integer << 4) + self.decimal as i16
+    }
+
+    /// Whether the temperature is below zero.
+    ///
+    /// Temperatures between 0 and -1 (e.g. -0.5) have an integer part of 0, and only their decimal
+    /// part is negative. These are still negative, so check this rather than the sign of
+    /// [`Temperature::integer_part`].
+    pub fn is_negative(&self) -> bool {
+        self.integer < 0 || self.decimal < 0
+    }
+
+    /// The temperature in thousandths of a degree Celsius, rounded toward zero.
+    ///
+    /// This is exact for all but the finest 1/16 degree increment, and avoids floating point.
+    pub fn millidegrees(&self) -> i32 {
+        self.raw() as i32 * 125 / 2
+    }
+
+    /// The magnitude of the temperature, e.g. 0.5 for a temperature of -0.5.
+    pub fn abs(&self) -> Temperature {
+        let [low_sig, high_sig] = self.raw().saturating_abs().to_le_bytes();
+        Temperature::from_bytes(low_sig, high_sig)
+    }
+
+    /// The temperature register's bytes, in the order expected by [`Temperature::from_bytes`]
+    /// (i.e. least-significant byte first).
+    pub fn to_bytes(&self) -> [u8; 2] {
+        self.raw().to_le_bytes()
+    }
+
+    /// Writes the temperature with a fixed number of decimal places, e.g. `-005.1250`.
+    ///
+    /// The integer part is zero-padded so that the whole output is at least `width` characters,
+    /// including the sign and decimal point. Decimals beyond the requested count are truncated,
+    /// not rounded. The sensor's resolution is 1/16 degree, which needs 4 decimal places, so any
+    /// further places are always 0.
+    ///
+    /// Calculated without performing floating-point operations.
+    pub fn write_fixed<W: core::fmt::Write>(
+        &self,
+        out: &mut W,
+        width: usize,
+        decimals: usize,
+    ) -> core::fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let magnitude = (self.raw() as i32).unsigned_abs();
+        let fraction_width = if decimals > 0 { decimals + 1 } else { 0 };
+        let integer_width = width.saturating_sub(sign.len() + fraction_width);
+        write!(
+            out,
+            "{}{:0width$}",
+            sign,
+            magnitude >> 4,
+            width = integer_width
+        )?;
+        if decimals == 0 {
+            return Ok(());
+        }
+
+        // Each 1/16 increment is exactly 0.0625, i.e. 625 ten-thousandths.
+        let ten_thousandths = (magnitude & 0xF) * 625;
+        let exact_decimals = decimals.min(4);
+        write!(
+            out,
+            ".{:0width$}",
+            ten_thousandths / 10u32.pow(4 - exact_decimals as u32),
+            width = exact_decimals
+        )?;
+        for _ in exact_decimals..decimals {
+            out.write_char('0')?;
+        }
+        Ok(())
+    }
+}
+
+/// Orders temperatures from coldest to warmest.
+impl Ord for Temperature {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.raw().cmp(&other.raw())
+    }
+}
+
+impl PartialOrd for Temperature {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
+#[cfg(not(feature = "no-float"))]
 impl From<Temperature> for f32 {
     fn from(temp: Temperature) -> Self {
         temp.integer_part() as f32 + temp.decimal_part() as f32 * MAX_RESOLUTION_F32
     }
 }
 
-impl From<Temperature> for f64 {
-    fn from(temp: Temperature) -> Self {
-        temp.integer_part() as f64 + temp.decimal_part() as f64 * MAX_RESOLUTION_F32 as f64
-    }
+#[cfg(not(feature = "no-float"))]
+impl From<Temperature> for f64 {
+    fn from(temp: Temperature) -> Self {
+        temp.integer_part() as f64 + temp.decimal_part() as f64 * MAX_RESOLUTION_F32 as f64
+    }
+}
+
+/// Tracks how quickly the temperature is changing, over the last `N` readings.
+///
+/// Timestamps are given as the time since any fixed instant, e.g. since boot. All calculations use
+/// millidegrees (see [`Temperature::millidegrees`]) so that no error accumulates.
+#[derive(Clone, Copy, Debug)]
+pub struct TrendTracker<const N: usize> {
+    readings: [(i32, Duration); N],
+    len: usize,
+    next: usize,
+}
+
+impl<const N: usize> TrendTracker<N> {
+    pub fn new() -> Self {
+        TrendTracker {
+            readings: [(0, Duration::from_millis(0)); N],
+            len: 0,
+            next: 0,
+        }
+    }
+
+    /// Adds a reading, replacing the oldest reading once the window is full.
+    pub fn push(&mut self, temperature: Temperature, at: Duration) {
+        if N == 0 {
+            return;
+        }
+        self.readings[self.next] = (temperature.millidegrees(), at);
+        self.next = (self.next + 1) % N;
+        self.len = core::cmp::min(self.len + 1, N);
+    }
+
+    /// The rate of change between the oldest and newest readings in the window, in millidegrees
+    /// per second.
+    ///
+    /// Returns `None` until there are at least two readings, or if the readings were all taken at
+    /// the same time.
+    pub fn millidegrees_per_second(&self) -> Option<i32> {
+        if self.len < 2 {
+            return None;
+        }
+        let oldest = self.readings[(self.next + N - self.len) % N];
+        let newest = self.readings[(self.next + N - 1) % N];
+        let elapsed_ms = newest.1.checked_sub(oldest.1)?.as_millis() as i64;
+        if elapsed_ms == 0 {
+            return None;
+        }
+        let change = (newest.0 - oldest.0) as i64;
+        Some((change * 1000 / elapsed_ms) as i32)
+    }
+
+    /// Removes all readings.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+}
+
+impl<const N: usize> Default for TrendTracker<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The parsed contents of a sensor's scratchpad memory.
+#[derive(Debug)]
+pub struct Scratchpad {
+    /// The temperature from the most recent conversion.
+    pub temperature: Temperature,
+    /// The high temperature threshold for the alarm (T<sub>H</sub>), in degrees Celsius.
+    pub alarm_high: i8,
+    /// The low temperature threshold for the alarm (T<sub>L</sub>), in degrees Celsius.
+    pub alarm_low: i8,
+    /// The resolution that the sensor is configured to use.
+    pub resolution: ResolutionMode,
+    /// Whether the sensor's alarm flag is set for this temperature, i.e. whether it would respond
+    /// to an alarm search. See [`Scratchpad::alarm`] for which threshold was reached.
+    pub alarm_triggered: bool,
+}
+
+impl Scratchpad {
+    fn from_bytes(data: &[u8; 9]) -> Self {
+        let temperature = Temperature::from_bytes(data[0], data[1]);
+        let alarm_high = data[2] as i8;
+        let alarm_low = data[3] as i8;
+        Scratchpad {
+            temperature,
+            alarm_high,
+            alarm_low,
+            resolution: ResolutionMode::from_configuration_byte(data[4]),
+            alarm_triggered: is_in_alarm(&temperature, alarm_high, alarm_low).is_some(),
+        }
+    }
+
+    /// Whether the temperature is outside the alarm thresholds that were read with it. See
+    /// [`is_in_alarm`].
+    pub fn alarm(&self) -> Option<AlarmKind> {
+        is_in_alarm(&self.temperature, self.alarm_high, self.alarm_low)
+    }
+}
+
+/// The configurable state of a sensor: its resolution and alarm thresholds.
+///
+/// See [`read_config`] and [`write_config`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeviceConfig {
+    /// The resolution that the sensor uses for conversions.
+    pub resolution: ResolutionMode,
+    /// The high temperature threshold for the alarm (T<sub>H</sub>), in degrees Celsius.
+    pub alarm_high: i8,
+    /// The low temperature threshold for the alarm (T<sub>L</sub>), in degrees Celsius.
+    pub alarm_low: i8,
+}
+
+impl DeviceConfig {
+    fn from_bytes(data: &[u8; 9]) -> Self {
+        DeviceConfig {
+            resolution: ResolutionMode::from_configuration_byte(data[4]),
+            alarm_high: data[2] as i8,
+            alarm_low: data[3] as i8,
+        }
+    }
+}
+
+/// Which alarm threshold a temperature has reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlarmKind {
+    /// The temperature is at or above T<sub>H</sub>.
+    High,
+    /// The temperature is at or below T<sub>L</sub>.
+    Low,
+}
+
+/// Whether the temperature has reached either of the alarm thresholds, in the same way as the
+/// sensor's own alarm flag.
+///
+/// As on the sensor, only the integer part of the temperature is compared, rounded towards
+/// negative infinity, and each threshold is inclusive. For example, with T<sub>H</sub> = 25, a
+/// temperature of 25.0 or 25.5 is in alarm, but 24.9375 is not.
+pub fn is_in_alarm(temperature: &Temperature, alarm_high: i8, alarm_low: i8) -> Option<AlarmKind> {
+    // Only bits 11 through 4 of the temperature register are compared.
+    let compared = temperature.raw() >> 4;
+    if compared >= alarm_high as i16 {
+        Some(AlarmKind::High)
+    } else if compared <= alarm_low as i16 {
+        Some(AlarmKind::Low)
+    } else {
+        None
+    }
+}
+
+/// A temperature, along with the raw scratchpad that it was decoded from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemperatureWithRaw {
+    /// The decoded temperature.
+    pub temperature: Temperature,
+    /// All nine bytes of the scratchpad, including the CRC, exactly as they were read.
+    pub scratchpad: [u8; 9],
+}
+
+/// A temperature that was read by polling for the end of the conversion.
+#[derive(Debug)]
+pub struct PolledTemperature {
+    /// The temperature that was read.
+    pub temperature: Temperature,
+    /// How long the sensor took to complete the conversion, as measured by the caller's time
+    /// source.
+    ///
+    /// If this is consistently well below the 12-bit conversion time, a lower resolution may not
+    /// make reads any faster.
+    pub conversion_time: Duration,
+}
+
+pub struct ReadResult<TData, TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+{
+    pub data: TData,
+    pub pin: TOutPin,
+    phantom_io_pin: core::marker::PhantomData<TIoPin>,
+    phantom_in_pin: core::marker::PhantomData<TInPin>,
+}
+
+/// The result of an operation that returns the pin with its data, so that the pin can be reused.
+pub type PinResult<TData, TIoPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError> =
+    Result<
+        ReadResult<TData, TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError>,
+        Error<TDelayError, TIoError, TInError, TOutError>,
+    >;
+
+/// Owns the pin and delay for a line with a single DS18B20, so that it can be used as a
+/// [`TemperatureSource`].
+///
+/// The free functions in this module pass the pin through each call instead. If a read returns
+/// [`Error::BadData`], the pin is kept, so the next read can simply try again. Any other error
+/// loses the pin, and every later read returns [`Error::InvalidState`] until a fresh pin is given
+/// to [`Ds18b20::recover`].
+///
+/// The line's [`PowerMode`] is detected before the first read, and reads are then timed to suit
+/// it. If the sensor is parasitically powered, the strong pull-up is enabled for each conversion
+/// (see [`read_temperature_with_strong_pullup`]), so that it doesn't return its 85°C power-on
+/// value.
+#[derive(Debug)]
+pub struct Ds18b20<TInPin, TOutPin, TDelay, TPullup = fn(bool)> {
+    pin: Option<TOutPin>,
+    delay: TDelay,
+    strong_pullup: TPullup,
+    power_mode: PowerModeCache,
+    phantom_in_pin: core::marker::PhantomData<TInPin>,
+}
+
+impl<TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>
+    Ds18b20<TInPin, TOutPin, TDelay>
+where
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    /// Constructs a driver without a strong pull-up.
+    ///
+    /// Parasitically-powered sensors then rely on the pin itself driving the line high during
+    /// conversions, which may not supply enough current. Prefer `with_strong_pullup` for these.
+    pub fn new(pin: TOutPin, delay: TDelay) -> Self {
+        Ds18b20::with_strong_pullup(pin, delay, |_| {})
+    }
+}
+
+impl<TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError, TPullup>
+    Ds18b20<TInPin, TOutPin, TDelay, TPullup>
+where
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TPullup: FnMut(bool),
+{
+    /// Constructs a driver that calls `strong_pullup` around each conversion if the sensor is
+    /// parasitically powered. See [`read_temperature_with_strong_pullup`] for its requirements.
+    pub fn with_strong_pullup(pin: TOutPin, delay: TDelay, strong_pullup: TPullup) -> Self {
+        Ds18b20 {
+            pin: Some(pin),
+            delay,
+            strong_pullup,
+            power_mode: PowerModeCache::new(),
+            phantom_in_pin: core::marker::PhantomData,
+        }
+    }
+
+    /// The line's power mode, or `None` if it hasn't been detected yet.
+    pub fn power_mode(&self) -> Option<PowerMode> {
+        self.power_mode.power_mode()
+    }
+
+    /// Replaces the pin after an error lost it, so that reads can continue.
+    ///
+    /// The detected power mode is kept, since it's a property of the wiring.
+    pub fn recover(&mut self, pin: TOutPin) {
+        self.pin = Some(pin);
+    }
+
+    /// Reads the temperature in the same way as [`read_temperature`], using the strong pull-up
+    /// if the sensor is parasitically powered.
+    ///
+    /// The power mode is detected first if it isn't known yet.
+    pub fn read_temperature(
+        &mut self,
+    ) -> Result<Temperature, Error<TDelayError, TIoError, TInError, TOutError>> {
+        let pin = self.pin.take().ok_or(Error::InvalidState)?;
+        let power_mode = self.power_mode.get_or_detect(pin, &mut self.delay)?;
+        let result = match power_mode.data {
+            PowerMode::External => read_scratchpad(
+                power_mode.pin,
+                &mut self.delay,
+                &mut |_| {},
+                &DEFAULT_BUS_TIMING,
+            )?,
+            PowerMode::Parasitic => read_scratchpad(
+                power_mode.pin,
+                &mut self.delay,
+                &mut self.strong_pullup,
+                &DEFAULT_BUS_TIMING,
+            )?,
+        };
+        // The pin is still usable if the data is corrupt, so keep it for the next read.
+        self.pin = Some(result.pin);
+        if !is_scratchpad_valid(&result.data) {
+            return Err(Error::BadData);
+        }
+        Ok(Temperature::from_bytes(result.data[0], result.data[1]))
+    }
+}
+
+#[cfg(not(feature = "no-float"))]
+impl<TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError, TPullup> TemperatureSource
+    for Ds18b20<TInPin, TOutPin, TDelay, TPullup>
+where
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TPullup: FnMut(bool),
+{
+    type Error = Error<TDelayError, TIoError, TInError, TOutError>;
+
+    async fn read_temperature_celsius(&mut self) -> Result<f32, Self::Error> {
+        self.read_temperature().map(f32::from)
+    }
+}
+
+/// A temperature conversion that was started by [`start_conversion`].
+#[derive(Clone, Copy, Debug)]
+pub struct Conversion<TTime>
+where
+    TTime: Copy,
+{
+    started_at: TTime,
+    resolution: ResolutionMode,
+}
+
+impl<TTime> Conversion<TTime>
+where
+    TTime: Copy,
+{
+    /// The resolution that the conversion is timed for.
+    pub fn resolution(&self) -> ResolutionMode {
+        self.resolution
+    }
+
+    /// How much longer the conversion needs before its result can be read.
+    ///
+    /// The provided `elapsed_since_fn` should determine how much time has passed since the given
+    /// instant, as returned by the `time_fn` given to [`start_conversion`].
+    pub fn remaining<ElapsedFn>(&self, elapsed_since_fn: ElapsedFn) -> Duration
+    where
+        ElapsedFn: Fn(TTime) -> Duration,
+    {
+        conversion_remaining(self.started_at, self.resolution, elapsed_since_fn)
+    }
+
+    /// Whether the conversion is complete, so that its result can be read without waiting.
+    pub fn is_ready<ElapsedFn>(&self, elapsed_since_fn: ElapsedFn) -> bool
+    where
+        ElapsedFn: Fn(TTime) -> Duration,
+    {
+        self.remaining(elapsed_since_fn) == Duration::from_millis(0)
+    }
+}
+
+/// How much longer a conversion started at `started_at` needs before its result can be read.
+///
+/// This is for conversions that weren't started by [`start_conversion`], such as one conversion
+/// command shared by several sensors. The provided `elapsed_since_fn` should determine how much
+/// time has passed since the given instant.
+pub fn conversion_remaining<ElapsedFn, TTime>(
+    started_at: TTime,
+    resolution: ResolutionMode,
+    elapsed_since_fn: ElapsedFn,
+) -> Duration
+where
+    ElapsedFn: Fn(TTime) -> Duration,
+{
+    resolution
+        .get_conversion_time()
+        .saturating_sub(elapsed_since_fn(started_at))
+}
+
+/// Whether a conversion started at `started_at` is complete. See [`conversion_remaining`].
+pub fn conversion_ready<ElapsedFn, TTime>(
+    started_at: TTime,
+    resolution: ResolutionMode,
+    elapsed_since_fn: ElapsedFn,
+) -> bool
+where
+    ElapsedFn: Fn(TTime) -> Duration,
+{
+    conversion_remaining(started_at, resolution, elapsed_since_fn) == Duration::from_millis(0)
+}
+
+pub fn read_temperature<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    to_temperature_result(result)
+}
+
+/// Reads the temperature in the same way as [`read_temperature`], but borrows the pin instead of
+/// consuming it.
+///
+/// This is for HALs whose pin is the same type as both an input and an output, i.e. an open-drain
+/// pin that can be read while it's set high. Instead of switching modes, the line is released by
+/// setting the pin high, and the sensor or pull-up resistor determines its level. The pin must be
+/// configured as open-drain, or the sensor won't be able to pull the line low.
+pub fn read_temperature_ref<TPin, TInError, TOutError, TDelay, TDelayError>(
+    pin: &mut TPin,
+    delay: &mut TDelay,
+) -> Result<Temperature, Error<TDelayError, TOutError, TInError, TOutError>>
+where
+    TPin: InputPin<Error = TInError> + OutputPin<Error = TOutError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    read_temperature(SharedIoPin::new(BorrowedPin(pin)), delay).map(|result| result.data)
+}
+
+/// Reads the raw value of the sensor's temperature register.
+///
+/// This performs the same transaction as [`read_temperature`], including validating the CRC, but
+/// returns the register without decoding it (see [`Temperature::raw`]). This is the most compact
+/// form to transmit or log.
+pub fn read_temperature_raw<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<i16, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    parse_scratchpad_result(result, |data| i16::from_le_bytes([data[0], data[1]]))
+}
+
+/// Reads the temperature, along with the raw scratchpad that it was decoded from.
+///
+/// This performs the same transaction as [`read_temperature`], including validating the CRC. It's
+/// useful for archiving the raw bytes while also acting on the decoded temperature.
+pub fn read_temperature_with_raw<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<
+    TemperatureWithRaw,
+    TOutPin,
+    TInPin,
+    TOutPin,
+    TDelayError,
+    TIoError,
+    TInError,
+    TOutError,
+>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    parse_scratchpad_result(result, |data| TemperatureWithRaw {
+        temperature: Temperature::from_bytes(data[0], data[1]),
+        scratchpad: *data,
+    })
+}
+
+/// How strictly a scratchpad is checked before it's decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ScratchpadCheck {
+    /// Only check the CRC.
+    Crc,
+    /// Check the CRC, and also reject a scratchpad whose bytes are all 0x00 or all 0xFF. This is
+    /// what every read uses, unless it takes a `ScratchpadCheck`.
+    #[default]
+    CrcAndUniform,
+}
+
+impl ScratchpadCheck {
+    /// Whether the scratchpad passes this check.
+    fn accepts(self, data: &[u8; 9]) -> bool {
+        let is_uniform = self == ScratchpadCheck::CrcAndUniform
+            && (data.iter().all(|byte| *byte == 0x00) || data.iter().all(|byte| *byte == 0xFF));
+        !is_uniform && compute_crc(&data[..8]) == data[8]
+    }
+}
+
+/// Reads the temperature in the same way as [`read_temperature`], but validates the scratchpad with
+/// the given `check`.
+///
+/// Use [`ScratchpadCheck::Crc`] to accept a uniform scratchpad that passes its CRC check, e.g. to
+/// log it for diagnosis rather than treating it as [`Error::BadData`].
+pub fn read_temperature_with_check<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    check: ScratchpadCheck,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    if !check.accepts(&result.data) {
+        return Err(Error::BadData);
+    }
+    Ok(ReadResult {
+        data: Temperature::from_bytes(result.data[0], result.data[1]),
+        pin: result.pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Reads the temperature, using the given timings for each read and write slot.
+///
+/// This performs the same transaction as [`read_temperature`]. Returns [`Error::InvalidArgument`]
+/// if the `timing` is not valid (see [`BusTiming::is_valid`]).
+pub fn read_temperature_with_timing<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    timing: &BusTiming,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    if !timing.is_valid() {
+        return Err(Error::InvalidArgument);
+    }
+    let result = read_scratchpad(pin, delay, &mut |_| {}, timing)?;
+    to_temperature_result(result)
+}
+
+/// Reads the temperature from sensors that are already configured with the given resolution.
+///
+/// This performs the same transaction as [`read_temperature`], but only waits for the conversion
+/// time of the given resolution (see [`ResolutionMode::get_conversion_time`]). The configuration
+/// is not written, so this suits sensors whose resolution was previously saved to their EEPROM.
+/// If the sensors actually use a higher resolution, the result will be from the previous
+/// conversion.
+pub fn read_temperature_with_resolution<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    resolution: ResolutionMode,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let pin = convert_temperature(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING, resolution)?;
+    let result = read_converted_scratchpad(pin, delay, &DEFAULT_BUS_TIMING)?;
+    to_temperature_result(result)
+}
+
+/// Reads the temperature at the highest resolution whose conversion fits within `max_latency`.
+///
+/// The resolution is chosen with [`ResolutionMode::highest_within`], and returns
+/// [`Error::InvalidArgument`] if no resolution is fast enough. The sensor's scratchpad is read
+/// first so that its alarm thresholds are kept when the new resolution is written. The resolution
+/// isn't copied to EEPROM, so the sensor reverts to its saved resolution after losing power.
+///
+/// Note that `max_latency` only bounds the conversion time: the reads and writes on the line add
+/// several more milliseconds.
+pub fn read_within<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    max_latency: Duration,
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let resolution = ResolutionMode::highest_within(max_latency).ok_or(Error::InvalidArgument)?;
+    let current = read_converted_scratchpad(pin, delay, &DEFAULT_BUS_TIMING)?;
+    if !is_scratchpad_valid(&current.data) {
+        return Err(Error::BadData);
+    }
+    let alarm_high = current.data[2] as i8;
+    let alarm_low = current.data[3] as i8;
+    let configured = configure(current.pin, delay, resolution, alarm_high, alarm_low, false)?;
+    read_temperature_with_resolution(configured.pin, delay, resolution)
+}
+
+/// Reads the temperature in the same way as [`read_temperature`], and tags it with the time at
+/// which the read completed, as given by `time_fn`.
+pub fn read_temperature_timestamped<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    TimeFn,
+    TTime,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    time_fn: TimeFn,
+) -> PinResult<
+    Timestamped<Temperature, TTime>,
+    TOutPin,
+    TInPin,
+    TOutPin,
+    TDelayError,
+    TIoError,
+    TInError,
+    TOutError,
+>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TimeFn: Fn() -> TTime,
+{
+    let result = read_temperature(pin, delay)?;
+    Ok(ReadResult {
+        data: Timestamped::new(result.data, time_fn()),
+        pin: result.pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Reads the temperature in degrees Celsius as an `f32`.
+///
+/// This performs the same transaction as [`read_temperature`], for callers that don't need the
+/// separate integer and decimal parts of the [`Temperature`].
+#[cfg(not(feature = "no-float"))]
+pub fn read_temperature_f32<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<f32, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    parse_scratchpad_result(result, |data| {
+        f32::from(Temperature::from_bytes(data[0], data[1]))
+    })
+}
+
+/// Reads the temperature without validating it, for maximum throughput.
+///
+/// Only the two temperature bytes of the scratchpad are read, after which the line is reset to
+/// end the read early. Since the CRC byte is never read, corrupted data can't be detected, so this
+/// should only be used on short, clean, externally-powered buses. Otherwise, prefer
+/// [`read_temperature`].
+pub fn read_temperature_unchecked<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let pin = convert_temperature(
+        pin,
+        delay,
+        &mut |_| {},
+        &DEFAULT_BUS_TIMING,
+        ResolutionMode::TwelveBit,
+    )?;
+    let pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    pin = write_byte(
+        FunctionCommand::ReadScratchpad as u8,
+        pin,
+        delay,
+        &DEFAULT_BUS_TIMING,
+    )?;
+    let low_sig = read_byte(pin, delay, &DEFAULT_BUS_TIMING)?;
+    let high_sig = read_byte(low_sig.pin, delay, &DEFAULT_BUS_TIMING)?;
+    // Resetting the line tells the sensor to stop sending the rest of the scratchpad.
+    let pin = reset(high_sig.pin, delay, &DEFAULT_BUS_TIMING)?;
+
+    Ok(ReadResult {
+        data: Temperature::from_bytes(low_sig.data, high_sig.data),
+        pin: pin
+            .into_output_pin(PinState::High)
+            .map_err(Error::WrappedIo)?,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Reads the temperature, also validating the scratchpad's reserved bytes.
+///
+/// On a genuine DS18B20, scratchpad byte 5 is always 0xFF and byte 7 is always 0x10. If either of
+/// these is wrong, the data is likely corrupt even if it passed its CRC check, so
+/// [`Error::BadData`] is returned. Some clones use different values for these bytes, so they can
+/// only be read with [`read_temperature`].
+pub fn read_temperature_strict<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    if !has_expected_reserved_bytes(&result.data) {
+        return Err(Error::BadData);
+    }
+    to_temperature_result(result)
+}
+
+/// Reads the temperature, along with the rest of the sensor's scratchpad.
+///
+/// This performs the same transaction as [`read_temperature`], but also returns the alarm
+/// thresholds and resolution that were read with the temperature.
+pub fn read_full<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<Scratchpad, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    parse_scratchpad_result(result, Scratchpad::from_bytes)
+}
+
+/// Reads the temperature, polling the sensor to find out when the conversion is complete.
+///
+/// Rather than waiting for the maximum conversion time, this sends read slots during the
+/// conversion, which the sensor responds to with a 1 once the conversion is complete. This is
+/// usually faster than [`read_temperature`], and reports how long the conversion took. If the
+/// sensor hasn't signaled completion after the 12-bit conversion time, [`Error::Timeout`] is
+/// returned.
+///
+/// This only works with externally-powered sensors. Parasitically-powered sensors need the line
+/// held high during the conversion, so can't be polled.
+///
+/// The provided `time_fn` closure should provide some representation of a given instant that can
+/// be used with `elapsed_since_fn` to determine how much time has passed since then. It does not
+/// need to reflect real dates and times, but must have millisecond precision or better.
+pub fn read_temperature_polled<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    TimeFn,
+    ElapsedFn,
+    TTime,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    time_fn: TimeFn,
+    elapsed_since_fn: ElapsedFn,
+) -> PinResult<
+    PolledTemperature,
+    TOutPin,
+    TInPin,
+    TOutPin,
+    TDelayError,
+    TIoError,
+    TInError,
+    TOutError,
+>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TimeFn: Fn() -> TTime,
+    ElapsedFn: Fn(TTime) -> Duration,
+    TTime: Copy,
+{
+    read_temperature_polled_cancellable(pin, delay, time_fn, elapsed_since_fn, || false)
+}
+
+/// Reads the temperature like [`read_temperature_polled`], but stops early if asked to.
+///
+/// `should_cancel` is called between each read slot while waiting for the conversion. If it
+/// returns true, [`Error::Cancelled`] is returned, e.g. so that the app can shut down or respond
+/// to a higher-priority event without waiting for the conversion to complete. The sensor will
+/// still finish its conversion in the background.
+pub fn read_temperature_polled_cancellable<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    TimeFn,
+    ElapsedFn,
+    CancelFn,
+    TTime,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    time_fn: TimeFn,
+    elapsed_since_fn: ElapsedFn,
+    mut should_cancel: CancelFn,
+) -> PinResult<
+    PolledTemperature,
+    TOutPin,
+    TInPin,
+    TOutPin,
+    TDelayError,
+    TIoError,
+    TInError,
+    TOutError,
+>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TimeFn: Fn() -> TTime,
+    ElapsedFn: Fn(TTime) -> Duration,
+    CancelFn: FnMut() -> bool,
+    TTime: Copy,
+{
+    let pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    pin = write_byte(
+        FunctionCommand::ConvertTemperature as u8,
+        pin,
+        delay,
+        &DEFAULT_BUS_TIMING,
+    )?;
+    let start = time_fn();
+    loop {
+        let result = read_bit(pin, delay, &DEFAULT_BUS_TIMING)?;
+        pin = result.pin;
+        if result.data {
+            break;
+        }
+        if elapsed_since_fn(start) >= CONVERSION_TIME_12BIT {
+            return Err(Error::Timeout);
+        }
+        if should_cancel() {
+            return Err(Error::Cancelled);
+        }
+    }
+    let conversion_time = elapsed_since_fn(start);
+
+    let result = read_converted_scratchpad(pin, delay, &DEFAULT_BUS_TIMING)?;
+    parse_scratchpad_result(result, |data| PolledTemperature {
+        temperature: Temperature::from_bytes(data[0], data[1]),
+        conversion_time,
+    })
+}
+
+/// Reads the temperature from a parasitically-powered sensor.
+///
+/// Parasitic sensors draw their power from the data line, which the pull-up resistor alone cannot
+/// supply during a temperature conversion. `strong_pullup` is called with `true` immediately after
+/// the conversion command is sent, and should actively drive the line high (e.g. by switching on a
+/// MOSFET between the line and the supply). It is called with `false` once the conversion time has
+/// passed, and should release the line. Since the line must be driven high within 10us of the
+/// command, this callback must return quickly.
+pub fn read_temperature_with_strong_pullup<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    TPullup,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    strong_pullup: &mut TPullup,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TPullup: FnMut(bool),
+{
+    let result = read_scratchpad(pin, delay, strong_pullup, &DEFAULT_BUS_TIMING)?;
+    to_temperature_result(result)
+}
+
+/// Reads the temperature, retrying the full conversion and read sequence if the data is corrupt.
+///
+/// Up to `max_attempts` reads are made, and the first one to pass its CRC check is returned. If
+/// all attempts fail, [`Error::BadData`] is returned. Like the DHT drivers, only bad data is
+/// retried: [`Error::NoSensorsFound`], [`Error::LineHeldLow`], and any wrapped HAL errors are
+/// returned immediately. Each retry waits 10ms first, to let any interference on the line pass.
+///
+/// Returns [`Error::InvalidArgument`] if `max_attempts` is 0.
+pub fn read_temperature_retry<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    max_attempts: u8,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    if max_attempts < 1 {
+        return Err(Error::InvalidArgument);
+    }
+    // Each attempt hands back an output pin, so start from one too.
+    let pin: TOutPin = pin
+        .into_output_pin(PinState::High)
+        .map_err(Error::WrappedIo)?;
+    let result = retry_with(
+        max_attempts,
+        pin,
+        |result: &ReadResult<[u8; 9], TOutPin, TInPin, TOutPin, TIoError, TInError, TOutError>| {
+            !is_scratchpad_valid(&result.data)
+        },
+        |result| result.pin,
+        |pin, attempt| {
+            if attempt > 0 {
+                delay
+                    .delay_us(RETRY_BACKOFF.as_micros() as u32)
+                    .map_err(Error::WrappedDelay)?;
+            }
+            read_scratchpad(pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)
+        },
+    )?;
+    to_temperature_result(result)
+}
+
+/// Reads the temperature `samples` times, and returns the median reading.
+///
+/// This rejects occasional outliers, e.g. from a bad conversion on an electrically noisy line,
+/// better than averaging the readings. Each sample is a full conversion and read sequence, so this
+/// takes at least `samples` * 750ms. If any sample fails its CRC check, [`Error::BadData`] is
+/// returned.
+///
+/// Returns [`Error::InvalidArgument`] if `samples` is even, or is not in the range
+/// \[1, [`MAX_MEDIAN_SAMPLES`]\].
+pub fn read_temperature_median<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    samples: u8,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    if samples % 2 == 0 || samples > MAX_MEDIAN_SAMPLES {
+        return Err(Error::InvalidArgument);
+    }
+    let mut result = read_temperature(pin, delay)?;
+    let mut readings = [result.data; MAX_MEDIAN_SAMPLES as usize];
+    for reading in readings.iter_mut().take(samples as usize).skip(1) {
+        result = read_temperature(result.pin, delay)?;
+        *reading = result.data;
+    }
+    let readings = &mut readings[..samples as usize];
+    readings.sort_unstable();
+    Ok(ReadResult {
+        data: readings[readings.len() / 2],
+        pin: result.pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Asks all devices on the line to start converting the temperature, without waiting for the
+/// result.
+///
+/// This allows other work to be done during the conversion, which can take up to 750ms. The result
+/// can then be read with [`read_conversion_result`].
+///
+/// The `resolution` must match the resolution that the sensors are configured with, as this
+/// determines how long the conversion takes. The provided `time_fn` closure should provide some
+/// representation of the current instant, which is used to determine when the conversion is
+/// complete. It does not need to reflect real dates and times, but must have millisecond
+/// precision or better.
+///
+/// The line is left driven high during the conversion. This is not suitable for parasitically
+/// powered sensors, which must use [`read_temperature_with_strong_pullup`].
+pub fn start_conversion<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    TimeFn,
+    TTime,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    resolution: ResolutionMode,
+    time_fn: TimeFn,
+) -> PinResult<
+    Conversion<TTime>,
+    TOutPin,
+    TInPin,
+    TOutPin,
+    TDelayError,
+    TIoError,
+    TInError,
+    TOutError,
+>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TimeFn: Fn() -> TTime,
+    TTime: Copy,
+{
+    let pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    pin = write_byte(
+        FunctionCommand::ConvertTemperature as u8,
+        pin,
+        delay,
+        &DEFAULT_BUS_TIMING,
+    )?;
+    Ok(ReadResult {
+        data: Conversion {
+            started_at: time_fn(),
+            resolution,
+        },
+        pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Reads the temperature from a conversion that was started by [`start_conversion`].
+///
+/// If the conversion is not yet complete (see [`Conversion::is_ready`]), this blocks for the
+/// remaining time before reading the result.
+pub fn read_conversion_result<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    ElapsedFn,
+    TTime,
+>(
+    conversion: Conversion<TTime>,
+    pin: TIoPin,
+    delay: &mut TDelay,
+    elapsed_since_fn: ElapsedFn,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    ElapsedFn: Fn(TTime) -> Duration,
+    TTime: Copy,
+{
+    let remaining = conversion.remaining(elapsed_since_fn);
+    if remaining > Duration::from_millis(0) {
+        delay
+            .delay_us(remaining.as_micros() as u32)
+            .map_err(Error::WrappedDelay)?;
+    }
+    let result = read_converted_scratchpad(pin, delay, &DEFAULT_BUS_TIMING)?;
+    to_temperature_result(result)
+}
+
+/// Configures the alarm thresholds and resolution of all devices on the line.
+///
+/// These are written to each sensor's scratchpad, so they're lost when the sensor loses power.
+/// If `persist` is true, they're also copied to the sensor's EEPROM, which restores them at
+/// power-up. This waits for the copy to complete, which takes up to 10ms.
+///
+/// The line is left driven high during the copy. This is not suitable for parasitically powered
+/// sensors, which need a strong pull-up during the copy.
+pub fn configure<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    resolution: ResolutionMode,
+    alarm_high: i8,
+    alarm_low: i8,
+    persist: bool,
+) -> PinResult<(), TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    pin = write_byte(
+        FunctionCommand::WriteScratchpad as u8,
+        pin,
+        delay,
+        &DEFAULT_BUS_TIMING,
+    )?;
+    pin = write_byte(alarm_high as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    pin = write_byte(alarm_low as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    pin = write_byte(
+        resolution.get_configuration_byte(),
+        pin,
+        delay,
+        &DEFAULT_BUS_TIMING,
+    )?;
+    if persist {
+        let reset_pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+        pin = write_byte(
+            RomCommand::Skip as u8,
+            reset_pin,
+            delay,
+            &DEFAULT_BUS_TIMING,
+        )?;
+        pin = write_byte(
+            FunctionCommand::CopyScratchpad as u8,
+            pin,
+            delay,
+            &DEFAULT_BUS_TIMING,
+        )?;
+        delay
+            .delay_us(EEPROM_COPY_TIME.as_micros() as u32)
+            .map_err(Error::WrappedDelay)?;
+    }
+    Ok(ReadResult {
+        data: (),
+        pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// The high alarm threshold that DS18B20s ship with, in degrees Celsius (0x4B).
+pub const FACTORY_ALARM_HIGH: i8 = 75;
+/// The low alarm threshold that DS18B20s ship with, in degrees Celsius (0x46).
+pub const FACTORY_ALARM_LOW: i8 = 70;
+
+/// Restores the factory defaults of every device on the line: 12-bit resolution (configuration
+/// byte 0x7F), and alarm thresholds of [`FACTORY_ALARM_HIGH`] and [`FACTORY_ALARM_LOW`].
+///
+/// This is the same as calling [`configure`] with these values and `persist` set, so the defaults
+/// are also copied to each sensor's EEPROM. Since the write is broadcast to all devices at once, use
+/// `configure` for each device individually if they shouldn't all share the same configuration.
+pub fn reset_all_to_default<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<(), TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    configure(
+        pin,
+        delay,
+        ResolutionMode::TwelveBit,
+        FACTORY_ALARM_HIGH,
+        FACTORY_ALARM_LOW,
+        true,
+    )
+}
+
+/// Reads the resolution and alarm thresholds from the sensor's scratchpad.
+///
+/// This doesn't start a conversion. If the scratchpad fails its CRC check, [`Error::BadData`] is
+/// returned.
+pub fn read_config<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<DeviceConfig, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_converted_scratchpad(pin, delay, &DEFAULT_BUS_TIMING)?;
+    parse_scratchpad_result(result, DeviceConfig::from_bytes)
+}
+
+/// Writes the resolution and alarm thresholds to the scratchpad of all devices on the line.
+///
+/// This is the same as calling [`configure`] without persisting the values, so they're lost when
+/// the sensor loses power.
+pub fn write_config<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    config: &DeviceConfig,
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<(), TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    configure(
+        pin,
+        delay,
+        config.resolution,
+        config.alarm_high,
+        config.alarm_low,
+        false,
+    )
+}
+
+/// Detects whether any devices on the line rely on parasitic power.
+///
+/// Parasitically-powered devices hold the line low when asked for their power supply, so this
+/// returns [`PowerMode::Parasitic`] if any device does so. Use a [`PowerModeCache`] to avoid
+/// repeating this before every read.
+pub fn read_power_mode<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<PowerMode, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    pin = write_byte(
+        FunctionCommand::ReadPowerSupply as u8,
+        pin,
+        delay,
+        &DEFAULT_BUS_TIMING,
+    )?;
+    let result = read_bit(pin, delay, &DEFAULT_BUS_TIMING)?;
+    Ok(ReadResult {
+        data: if result.data {
+            PowerMode::External
+        } else {
+            PowerMode::Parasitic
+        },
+        pin: result.pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Resets all devices on the line, without sending any further commands.
+///
+/// This can be used to return all devices to a known idle state, e.g. after an error interrupted a
+/// previous operation. The result's `data` is whether any device responded with a presence pulse.
+pub fn bus_reset<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<bool, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let (pin, is_present) = reset_and_detect_presence(pin, delay, &DEFAULT_BUS_TIMING)?;
+    Ok(ReadResult {
+        data: is_present,
+        pin: pin
+            .into_output_pin(PinState::High)
+            .map_err(Error::WrappedIo)?,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Reads the ID of the only device on the line.
+///
+/// This only works if there is a single device on the line. If there are multiple devices, they
+/// will all respond at once, and the result will be corrupted.
+pub fn read_rom<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<DeviceId, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+    let mut pin = write_byte(RomCommand::Read as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+    let mut rom = [0u8; 8];
+    for byte in rom.iter_mut() {
+        let byte_and_pin = read_byte(pin, delay, &DEFAULT_BUS_TIMING)?;
+        pin = byte_and_pin.pin;
+        *byte = byte_and_pin.data;
+    }
+
+    Ok(ReadResult {
+        data: DeviceId(rom),
+        pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Reads the family code of the only device on the line.
+///
+/// This can be used to identify the type of device before communicating with it further. Like
+/// [`read_rom`], this only works if there is a single device on the line.
+pub fn read_family_code<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<u8, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let result = read_rom(pin, delay)?;
+    Ok(ReadResult {
+        data: result.data.family_code(),
+        pin: result.pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Reads the temperature of the only device on the line, decoding it according to the device's
+/// family code.
+///
+/// This supports the DS18B20 ([`FAMILY_DS18B20`]), the DS1822 ([`FAMILY_DS1822`]), and the
+/// DS18S20 ([`FAMILY_DS18S20`]), whose 9-bit reading is extended to 1/16 degree resolution using
+/// the count registers in its scratchpad. Returns [`Error::UnsupportedFamily`] for any other
+/// device. Like [`read_rom`], this only works if there is a single device on the line.
+pub fn read_auto<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<Temperature, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let family_code = read_family_code(pin, delay)?;
+    let decode_fn = match family_code.data {
+        FAMILY_DS18B20 | FAMILY_DS1822 => {
+            |data: &[u8; 9]| Temperature::from_bytes(data[0], data[1])
+        }
+        FAMILY_DS18S20 => ds18s20_temperature,
+        other => return Err(Error::UnsupportedFamily(other)),
+    };
+    let result = read_scratchpad(family_code.pin, delay, &mut |_| {}, &DEFAULT_BUS_TIMING)?;
+    if family_code.data == FAMILY_DS18S20 && result.data[7] == 0 {
+        // The DS18S20's count per degree is fixed at 16, so 0 can't be a genuine reading.
+        return Err(Error::BadData);
+    }
+    parse_scratchpad_result(result, decode_fn)
+}
+
+/// Decodes a DS18S20 scratchpad's temperature, extended to 1/16 degree resolution.
+///
+/// The DS18S20 reads in 1/2 degree increments, but its scratchpad also holds the remaining count
+/// (byte 6) and the count per degree (byte 7) of the conversion, which give the fraction of the
+/// degree. See page 6 of https://datasheets.maximintegrated.com/en/ds/DS18S20.pdf.
+fn ds18s20_temperature(data: &[u8; 9]) -> Temperature {
+    let half_degrees = i16::from_le_bytes([data[0], data[1]]);
+    let count_remain = data[6] as i16;
+    let count_per_c = data[7] as i16;
+    // TEMPERATURE = TEMP_READ - 0.25 + (COUNT_PER_C - COUNT_REMAIN) / COUNT_PER_C, where TEMP_READ
+    // is the reading with its half-degree bit truncated.
+    let raw = (half_degrees >> 1) * 16 - 4 + (count_per_c - count_remain) * 16 / count_per_c;
+    let [low_sig, high_sig] = raw.to_le_bytes();
+    Temperature::from_bytes(low_sig, high_sig)
+}
+
+/// Searches for all devices on the line, and writes their IDs into `devices`.
+///
+/// This does not allocate, so the caller must provide enough space for every device on the line.
+/// The result's `data` is the number of IDs that were written. If more devices are found than fit
+/// in `devices`, then [`Error::TooManyDevices`] is returned.
+pub fn search_devices<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    devices: &mut [DeviceId],
+) -> PinResult<usize, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let mut count = 0;
+    let pin = search(pin, delay, |id| {
+        if count >= devices.len() {
+            return Err(Error::TooManyDevices);
+        }
+        devices[count] = id;
+        count += 1;
+        Ok(())
+    })?;
+    Ok(ReadResult {
+        data: count,
+        pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Searches for all devices on the line, and returns their IDs.
+#[cfg(feature = "alloc")]
+pub fn search_devices_vec<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<Vec<DeviceId>, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let mut devices = Vec::new();
+    let pin = search(pin, delay, |id| {
+        devices.push(id);
+        Ok(())
+    })?;
+    Ok(ReadResult {
+        data: devices,
+        pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// The temperature of each device on a line, as read by [`discover_and_read`].
+#[cfg(feature = "alloc")]
+type DeviceTemperatures = Vec<(DeviceId, Temperature)>;
+
+/// Searches for all devices on the line, converts the temperature on all of them at once, then
+/// reads each device's temperature.
+///
+/// Returns [`Error::NoSensorsFound`] if the line is empty, or [`Error::BadData`] if any device's
+/// data fails its CRC check. Use [`search_devices_vec`] and [`read_all_into`] instead to keep the
+/// readings of the other devices when one of them fails.
+#[cfg(feature = "alloc")]
+pub fn discover_and_read<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+) -> PinResult<
+    DeviceTemperatures,
+    TOutPin,
+    TInPin,
+    TOutPin,
+    TDelayError,
+    TIoError,
+    TInError,
+    TOutError,
+>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let search_result = search_devices_vec(pin, delay)?;
+    let ids = search_result.data;
+    let mut entries = Vec::with_capacity(ids.len());
+    entries.resize(ids.len(), (DeviceId::default(), None));
+    let read_result = read_all_into(&ids, search_result.pin, delay, &mut entries)?;
+
+    let mut readings = Vec::with_capacity(entries.len());
+    for (id, temperature) in entries {
+        readings.push((id, temperature.ok_or(Error::BadData)?));
+    }
+    Ok(ReadResult {
+        data: readings,
+        pin: read_result.pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Checks the device IDs found by a search, e.g. by [`search_devices`].
+///
+/// Returns [`SearchError::BadCrc`] for the first ID whose CRC doesn't match, or
+/// [`SearchError::Duplicate`] for the first ID that appears more than once. A noisy line can make a
+/// search return such phantom devices, so checking for them avoids addressing devices that don't
+/// exist.
+pub fn validate_devices(devices: &[DeviceId]) -> Result<(), SearchError> {
+    for (i, device) in devices.iter().enumerate() {
+        if !device.is_valid() {
+            return Err(SearchError::BadCrc(*device));
+        }
+        if devices[..i].contains(device) {
+            return Err(SearchError::Duplicate(*device));
+        }
+    }
+    Ok(())
+}
+
+/// Converts the temperature on all devices at once, then reads the temperature of each of the
+/// given devices into `out`, without allocating.
+///
+/// Each entry of `out` is set to the device ID at the same index of `ids`, and its temperature. If
+/// a device doesn't respond, or its data fails its CRC check, its temperature is `None` and the
+/// remaining devices are still read. Wrapped HAL errors are returned immediately.
+///
+/// Returns [`Error::InvalidArgument`] if `ids` and `out` have different lengths.
+pub fn read_all_into<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
+    ids: &[DeviceId],
+    pin: TIoPin,
+    delay: &mut TDelay,
+    out: &mut [(DeviceId, Option<Temperature>)],
+) -> PinResult<(), TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    if ids.len() != out.len() {
+        return Err(Error::InvalidArgument);
+    }
+    let mut pin = convert_temperature(
+        pin,
+        delay,
+        &mut |_| {},
+        &DEFAULT_BUS_TIMING,
+        ResolutionMode::TwelveBit,
+    )?;
+    for (id, entry) in ids.iter().zip(out.iter_mut()) {
+        let (in_pin, is_present) = reset_and_detect_presence(pin, delay, &DEFAULT_BUS_TIMING)?;
+        if !is_present {
+            *entry = (*id, None);
+            pin = in_pin
+                .into_output_pin(PinState::High)
+                .map_err(Error::WrappedIo)?;
+            continue;
+        }
+        let mut out_pin = write_byte(RomCommand::Match as u8, in_pin, delay, &DEFAULT_BUS_TIMING)?;
+        for byte in id.to_bytes().iter() {
+            out_pin = write_byte(*byte, out_pin, delay, &DEFAULT_BUS_TIMING)?;
+        }
+        out_pin = write_byte(
+            FunctionCommand::ReadScratchpad as u8,
+            out_pin,
+            delay,
+            &DEFAULT_BUS_TIMING,
+        )?;
+        let mut data = [0u8; 9];
+        for byte in data.iter_mut() {
+            let byte_and_pin = read_byte(out_pin, delay, &DEFAULT_BUS_TIMING)?;
+            out_pin = byte_and_pin.pin;
+            *byte = byte_and_pin.data;
+        }
+        let temperature = if is_scratchpad_valid(&data) {
+            Some(Temperature::from_bytes(data[0], data[1]))
+        } else {
+            None
+        };
+        *entry = (*id, temperature);
+        pin = out_pin;
+    }
+    Ok(ReadResult {
+        data: (),
+        pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
+/// Polls a fixed set of devices, keeping the last known temperature of each one.
+///
+/// Each [`Monitor::poll`] reads every device in the same way as [`read_all_into`]. A device whose
+/// read fails keeps its previous temperature and update time, so a transient error on one device
+/// doesn't lose its reading, or stop the other devices from being read.
+#[derive(Clone, Copy, Debug)]
+pub struct Monitor<TTime, const N: usize>
+where
+    TTime: Copy,
+{
+    entries: [(DeviceId, Option<Temperature>, TTime); N],
+}
+
+impl<TTime, const N: usize> Monitor<TTime, N>
+where
+    TTime: Copy,
+{
+    /// Creates a monitor for the given devices, none of which have a temperature yet.
+    ///
+    /// `now` is used as every device's update time until it's first read successfully.
+    pub fn new(ids: [DeviceId; N], now: TTime) -> Self {
+        let mut entries = [(DeviceId::default(), None, now); N];
+        for (id, entry) in ids.iter().zip(entries.iter_mut()) {
+            entry.0 = *id;
+        }
+        Monitor { entries }
+    }
+
+    /// Reads every device, and updates the temperature and update time of each device that was
+    /// read successfully, using the time given by `time_fn` once all the devices were read.
+    ///
+    /// The result's `data` is the number of devices that were updated. If the reset before the
+    /// conversion fails, e.g. with [`Error::NoSensorsFound`] or [`Error::LineHeldLow`], or a wrapped
+    /// HAL error occurs, the error is returned immediately, without updating any device.
+    pub fn poll<
+        TIoPin,
+        TInPin,
+        TOutPin,
+        TIoError,
+        TInError,
+        TOutError,
+        TDelay,
+        TDelayError,
+        TimeFn,
+    >(
+        &mut self,
+        pin: TIoPin,
+        delay: &mut TDelay,
+        time_fn: TimeFn,
+    ) -> PinResult<usize, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+    where
+        TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+        TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+        TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+        TDelay: DelayUs<Error = TDelayError>,
+        TimeFn: Fn() -> TTime,
+    {
+        let mut ids = [DeviceId::default(); N];
+        for (id, entry) in ids.iter_mut().zip(self.entries.iter()) {
+            *id = entry.0;
+        }
+        let mut readings = [(DeviceId::default(), None); N];
+        let result = read_all_into(&ids, pin, delay, &mut readings)?;
+
+        let now = time_fn();
+        let mut updated = 0;
+        for ((_, reading), entry) in readings.iter().zip(self.entries.iter_mut()) {
+            if let Some(temperature) = reading {
+                entry.1 = Some(*temperature);
+                entry.2 = now;
+                updated += 1;
+            }
+        }
+        Ok(ReadResult {
+            data: updated,
+            pin: result.pin,
+            phantom_io_pin: core::marker::PhantomData,
+            phantom_in_pin: core::marker::PhantomData,
+        })
+    }
+
+    /// Each device's ID, its last known temperature (or `None` if it was never read
+    /// successfully), and the time it was last updated.
+    pub fn latest(&self) -> &[(DeviceId, Option<Temperature>, TTime)] {
+        &self.entries
+    }
+}
+
+/// Converts the temperature, then reads the full scratchpad without validating it.
+///
+/// `strong_pullup` is enabled for the duration of the conversion.
+fn read_scratchpad<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    TPullup,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    strong_pullup: &mut TPullup,
+    timing: &BusTiming,
+) -> PinResult<[u8; 9], TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TPullup: FnMut(bool),
+{
+    let pin = convert_temperature(pin, delay, strong_pullup, timing, ResolutionMode::TwelveBit)?;
+    read_converted_scratchpad(pin, delay, timing)
 }
 
-pub struct ReadResult<TData, TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError>
+/// Converts the temperature, waiting for the maximum conversion time at the given resolution.
+///
+/// `strong_pullup` is enabled for the duration of the conversion.
+fn convert_temperature<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+    TPullup,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    strong_pullup: &mut TPullup,
+    timing: &BusTiming,
+    resolution: ResolutionMode,
+) -> Result<TOutPin, Error<TDelayError, TIoError, TInError, TOutError>>
 where
     TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
     TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
     TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TPullup: FnMut(bool),
 {
-    pub data: TData,
-    pub pin: TOutPin,
-    phantom_io_pin: core::marker::PhantomData<TIoPin>,
-    phantom_in_pin: core::marker::PhantomData<TInPin>,
+    let pin = reset(pin, delay, timing)?;
+    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay, timing)?;
+    pin = write_byte(
+        FunctionCommand::ConvertTemperature as u8,
+        pin,
+        delay,
+        timing,
+    )?;
+    strong_pullup(true);
+    let conversion_result = delay.delay_us(resolution.get_conversion_time().as_micros() as u32);
+    // Always release the line, even if the delay failed.
+    strong_pullup(false);
+    conversion_result.map_err(Error::WrappedDelay)?;
+    Ok(pin)
 }
 
-pub fn read_temperature<
+/// Reads the full scratchpad without validating it, assuming a conversion has already completed.
+fn read_converted_scratchpad<
     TIoPin,
     TInPin,
     TOutPin,
@@ -198,52 +2556,26 @@ pub fn read_temperature<
 >(
     pin: TIoPin,
     delay: &mut TDelay,
-) -> Result<
-    ReadResult<Temperature, TOutPin, TInPin, TOutPin, TIoError, TInError, TOutError>,
-    Error<TDelayError, TIoError, TInError, TOutError>,
->
+    timing: &BusTiming,
+) -> PinResult<[u8; 9], TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
 where
     TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
     TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
     TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
     TDelay: DelayUs<Error = TDelayError>,
 {
-    let pin = reset(pin, delay)?;
-    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay)?;
-    pin = write_byte(FunctionCommand::ConvertTemperature as u8, pin, delay)?;
-    delay
-        .delay_us(CONVERSION_TIME_12BIT.as_micros() as u32)
-        .map_err(Error::WrappedDelay)?;
-    let pin = reset(pin, delay)?;
-    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay)?;
-    pin = write_byte(FunctionCommand::ReadScratchpad as u8, pin, delay)?;
+    let pin = reset(pin, delay, timing)?;
+    let mut pin = write_byte(RomCommand::Skip as u8, pin, delay, timing)?;
+    pin = write_byte(FunctionCommand::ReadScratchpad as u8, pin, delay, timing)?;
     let mut data = [0u8; 9];
     for i in 0..9 {
-        let byte_and_pin = read_byte(pin, delay)?;
+        let byte_and_pin = read_byte(pin, delay, timing)?;
         pin = byte_and_pin.pin;
         data[i] = byte_and_pin.data;
     }
 
-    let mut x = 0u64;
-    let mut shift = 0u8;
-    // let mut x_7 = 0u64;
-    for i in 0..8 {
-        x += (data[i] as u64) << shift;
-        // if i == 6 {
-        //     x_7 = x;
-        // }
-        shift += 8;
-    }
-    // TODO: Confirm correct way to compute CRC.
-    // let crc_7 = compute_crc(x_7);
-    let crc = compute_crc(x);
-    if crc != data[8] {
-        return Err(Error::BadData);
-    }
-
-    let temperature = Temperature::from_bytes(data[0], data[1]);
     Ok(ReadResult {
-        data: temperature,
+        data,
         pin: pin
             .into_output_pin(PinState::High)
             .map_err(Error::WrappedIo)?,
@@ -252,6 +2584,61 @@ where
     })
 }
 
+/// Whether the scratchpad's CRC byte matches its data, and the data isn't all 0x00 or all 0xFF.
+fn is_scratchpad_valid(data: &[u8; 9]) -> bool {
+    ScratchpadCheck::default().accepts(data)
+}
+
+/// Whether the scratchpad's reserved bytes match those of a genuine DS18B20.
+fn has_expected_reserved_bytes(data: &[u8; 9]) -> bool {
+    data[5] == 0xFF && data[7] == 0x10
+}
+
+/// Validates the scratchpad and parses its temperature.
+fn to_temperature_result<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelayError>(
+    result: ReadResult<[u8; 9], TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError>,
+) -> PinResult<Temperature, TIoPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+{
+    parse_scratchpad_result(result, |data| Temperature::from_bytes(data[0], data[1]))
+}
+
+/// Validates the scratchpad and parses it with the given function.
+fn parse_scratchpad_result<
+    TData,
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelayError,
+    TParseFn,
+>(
+    result: ReadResult<[u8; 9], TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError>,
+    parse_fn: TParseFn,
+) -> PinResult<TData, TIoPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TParseFn: FnOnce(&[u8; 9]) -> TData,
+{
+    if !is_scratchpad_valid(&result.data) {
+        return Err(Error::BadData);
+    }
+
+    Ok(ReadResult {
+        data: parse_fn(&result.data),
+        pin: result.pin,
+        phantom_io_pin: core::marker::PhantomData,
+        phantom_in_pin: core::marker::PhantomData,
+    })
+}
+
 /// Administrative commands for operating the 1-bit data line.
 ///
 /// These are used to retrieve information about devices on the line, or to request those devices
@@ -344,23 +2731,107 @@ pub enum FunctionCommand {
     ReadPowerSupply = 0xB4,
 }
 
-/// Shift the pending CRC byte by a single bit.
+/// Runs the ROM search algorithm, passing each device's ID to `on_found` as it is discovered.
 ///
-/// If performed for the first 56 bits that are read, starting with a CRC of 0, this should result
-/// in the expected CRC byte.
-///
-/// Continuing to operate this for the CRC byte should then result in 0.
-fn shift_crc_bit(bit: u8, crc: u8) -> u8 {
-    let xored_bit = (crc & 0x1) ^ bit;
-    let to_xor = xored_bit << 2 + xored_bit << 3;
-    ((crc >> 1) ^ to_xor) + bit << 7
+/// Each pass resets the line and walks the 64-bit ROM codes one bit at a time. At each bit, all
+/// remaining devices send the bit and its complement. If both are 0, there is a discrepancy: the 0
+/// branch is taken first, and the 1 branch is taken on a later pass.
+fn search<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError, TOnFound>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    mut on_found: TOnFound,
+) -> Result<TOutPin, Error<TDelayError, TIoError, TInError, TOutError>>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+    TOnFound: FnMut(DeviceId) -> Result<(), Error<TDelayError, TIoError, TInError, TOutError>>,
+{
+    let mut rom = [0u8; 8];
+    // 1-indexed position of the discrepancy to branch at on the next pass, or 0 if there is none.
+    let mut last_discrepancy = 0u8;
+    let mut pin: TOutPin = pin
+        .into_output_pin(PinState::High)
+        .map_err(Error::WrappedIo)?;
+    loop {
+        let in_pin = reset(pin, delay, &DEFAULT_BUS_TIMING)?;
+        pin = write_byte(RomCommand::Search as u8, in_pin, delay, &DEFAULT_BUS_TIMING)?;
+        let mut last_zero = 0u8;
+        for position in 1..=64u8 {
+            let id_bit = read_bit(pin, delay, &DEFAULT_BUS_TIMING)?;
+            let complement_bit = read_bit(id_bit.pin, delay, &DEFAULT_BUS_TIMING)?;
+            pin = complement_bit.pin;
+
+            let byte_index = ((position - 1) / 8) as usize;
+            let mask = 1u8 << ((position - 1) % 8);
+            let direction = if id_bit.data != complement_bit.data {
+                id_bit.data
+            } else if id_bit.data {
+                // No devices responded, so they must have left the line mid-search.
+                return Err(Error::BadData);
+            } else {
+                let direction = if position < last_discrepancy {
+                    rom[byte_index] & mask != 0
+                } else {
+                    position == last_discrepancy
+                };
+                if !direction {
+                    last_zero = position;
+                }
+                direction
+            };
+
+            if direction {
+                rom[byte_index] |= mask;
+            } else {
+                rom[byte_index] &= !mask;
+            }
+            pin = write_bit(direction as u8, pin, delay, &DEFAULT_BUS_TIMING)?;
+        }
+
+        on_found(DeviceId(rom))?;
+        last_discrepancy = last_zero;
+        if last_discrepancy == 0 {
+            return Ok(pin);
+        }
+    }
 }
 
 /// Resets the line to prepare for the next rom command.
 fn reset<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
     pin: TIoPin,
     delay: &mut TDelay,
+    timing: &BusTiming,
 ) -> Result<TInPin, Error<TDelayError, TIoError, TInError, TOutError>>
+where
+    TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
+    TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TOutPin: OutputPin<Error = TOutError> + IoPin<TInPin, TOutPin, Error = TIoError>,
+    TDelay: DelayUs<Error = TDelayError>,
+{
+    let (pin, is_present) = reset_and_detect_presence(pin, delay, timing)?;
+    if !is_present {
+        return Err(Error::NoSensorsFound);
+    }
+    Ok(pin)
+}
+
+/// Resets the line, and returns whether any device sent a presence pulse.
+fn reset_and_detect_presence<
+    TIoPin,
+    TInPin,
+    TOutPin,
+    TIoError,
+    TInError,
+    TOutError,
+    TDelay,
+    TDelayError,
+>(
+    pin: TIoPin,
+    delay: &mut TDelay,
+    timing: &BusTiming,
+) -> Result<(TInPin, bool), Error<TDelayError, TIoError, TInError, TOutError>>
 where
     TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
     TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
@@ -388,29 +2859,35 @@ where
     // for signal in results.iter() {
     //     println!("{}", signal);
     // }
-    delay
-        .delay_us(FIRST_PRESENCE_PULSE_DELAY_US as u32)
-        .map_err(Error::WrappedDelay)?;
-    let mut is_present = pin.is_low().map_err(Error::WrappedInput)?;
-    delay
-        .delay_us(FIRST_PRESENCE_PULSE_DELAY_US as u32)
-        .map_err(Error::WrappedDelay)?;
-    is_present |= pin.is_low().map_err(Error::WrappedInput)?;
-    if !is_present {
-        return Err(Error::NoSensorsFound);
+    let mut is_present = false;
+    for _ in 0..timing.presence_samples {
+        delay
+            .delay_us(timing.presence_interval_us)
+            .map_err(Error::WrappedDelay)?;
+        is_present |= pin.is_low().map_err(Error::WrappedInput)?;
     }
 
     // Wait the remaining time.
     delay
-        .delay_us(POST_PRESENCE_PULSE_DELAY_US as u32)
+        .delay_us((RESET_TIME_US as u32).saturating_sub(timing.presence_window_us()))
         .map_err(Error::WrappedDelay)?;
-    Ok(pin)
+    // Presence pulses last at most 240us, so the line should be released by now.
+    if pin.is_low().map_err(Error::WrappedInput)? {
+        return Err(Error::LineHeldLow);
+    }
+    if timing.post_reset_settle_us > 0 {
+        delay
+            .delay_us(timing.post_reset_settle_us)
+            .map_err(Error::WrappedDelay)?;
+    }
+    Ok((pin, is_present))
 }
 
 fn write_byte<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
     byte: u8,
     pin: TIoPin,
     delay: &mut TDelay,
+    timing: &BusTiming,
 ) -> Result<TOutPin, Error<TDelayError, TIoError, TInError, TOutError>>
 where
     TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
@@ -423,7 +2900,7 @@ where
         .into_output_pin(PinState::High)
         .map_err(Error::WrappedIo)?;
     for _ in 0..8 {
-        out_pin = write_bit(byte & 1, out_pin, delay)?;
+        out_pin = write_bit(byte & 1, out_pin, delay, timing)?;
         byte >>= 1;
     }
     Ok(out_pin)
@@ -432,10 +2909,8 @@ where
 fn read_byte<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
     pin: TIoPin,
     delay: &mut TDelay,
-) -> Result<
-    ReadResult<u8, TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError>,
-    Error<TDelayError, TIoError, TInError, TOutError>,
->
+    timing: &BusTiming,
+) -> PinResult<u8, TIoPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
 where
     TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
     TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
@@ -447,7 +2922,7 @@ where
         .into_output_pin(PinState::High)
         .map_err(Error::WrappedIo)?;
     for bit in 0..8 {
-        let result = read_bit(out_pin, delay)?;
+        let result = read_bit(out_pin, delay, timing)?;
         out_pin = result.pin;
         byte += (result.data as u8) << bit;
     }
@@ -464,6 +2939,7 @@ fn write_bit<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDe
     bit: u8,
     pin: TIoPin,
     delay: &mut TDelay,
+    timing: &BusTiming,
 ) -> Result<TOutPin, Error<TDelayError, TIoError, TInError, TOutError>>
 where
     TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
@@ -476,24 +2952,22 @@ where
         .into_output_pin(PinState::High)
         .map_err(Error::WrappedIo)?;
     delay
-        .delay_us(READ_WRITE_RECOVERY_TIME_US as u32)
+        .delay_us(timing.recovery_us)
         .map_err(Error::WrappedDelay)?;
 
     // Output bit.
     pin.set_low().map_err(Error::WrappedOutput)?;
-    let op_delay_us: u8 = match bit {
-        0 => WRITE_0_DURATION_US,
-        _ => WRITE_1_DURATION_US,
+    let op_delay_us = match bit {
+        0 => timing.write_0_us,
+        _ => timing.write_1_us,
     };
-    delay
-        .delay_us(op_delay_us as u32)
-        .map_err(Error::WrappedDelay)?;
+    delay.delay_us(op_delay_us).map_err(Error::WrappedDelay)?;
 
     // Return high and wait.
     pin.set_high().map_err(Error::WrappedOutput)?;
     if bit != 0 {
         delay
-            .delay_us(WRITE_1_POST_BIT_DELAY_US as u32)
+            .delay_us(MIN_READ_WRITE_DURATION_US as u32 - timing.write_1_us)
             .map_err(Error::WrappedDelay)?;
     }
     Ok(pin)
@@ -503,10 +2977,8 @@ where
 fn read_bit<TIoPin, TInPin, TOutPin, TIoError, TInError, TOutError, TDelay, TDelayError>(
     pin: TIoPin,
     delay: &mut TDelay,
-) -> Result<
-    ReadResult<bool, TOutPin, TInPin, TOutPin, TIoError, TInError, TOutError>,
-    Error<TDelayError, TIoError, TInError, TOutError>,
->
+    timing: &BusTiming,
+) -> PinResult<bool, TOutPin, TInPin, TOutPin, TDelayError, TIoError, TInError, TOutError>
 where
     TIoPin: IoPin<TInPin, TOutPin, Error = TIoError>,
     TInPin: InputPin<Error = TInError> + IoPin<TInPin, TOutPin, Error = TIoError>,
@@ -518,7 +2990,7 @@ where
         .into_output_pin(PinState::High)
         .map_err(Error::WrappedIo)?;
     delay
-        .delay_us(READ_WRITE_RECOVERY_TIME_US as u32)
+        .delay_us(timing.recovery_us)
         .map_err(Error::WrappedDelay)?;
 
     // Request bit.
@@ -555,6 +3027,40 @@ where
 mod tests {
     use super::*;
 
+    type TestError = Error<(), (), (), ()>;
+
+    macro_rules! test_wrapped_source {
+        ($name:ident, $error:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                let error: TestError = $error;
+                assert_eq!(error.wrapped_source(), $expected);
+            }
+        };
+    }
+
+    test_wrapped_source!(
+        wrapped_source_delay,
+        Error::WrappedDelay(()),
+        Some(WrappedKind::Delay)
+    );
+    test_wrapped_source!(
+        wrapped_source_io,
+        Error::WrappedIo(()),
+        Some(WrappedKind::Io)
+    );
+    test_wrapped_source!(
+        wrapped_source_input,
+        Error::WrappedInput(()),
+        Some(WrappedKind::Input)
+    );
+    test_wrapped_source!(
+        wrapped_source_output,
+        Error::WrappedOutput(()),
+        Some(WrappedKind::Output)
+    );
+    test_wrapped_source!(wrapped_source_bad_data, Error::BadData, None);
+
     #[test]
     fn temp_integer_part() {
         let temperature = Temperature::from_bytes(0xF9, 0x12);
@@ -625,6 +3131,318 @@ mod tests {
         assert_eq!(temperature.nearest_integer(), -26);
     }
 
+    #[test]
+    fn temp_positive_is_not_negative() {
+        let temperature = Temperature::from_bytes(0xF9, 0x12);
+
+        assert!(!temperature.is_negative());
+        assert_eq!(temperature.abs().raw(), temperature.raw());
+    }
+
+    #[test]
+    fn temp_zero_is_not_negative() {
+        let temperature = Temperature::from_bytes(0x00, 0x00);
+
+        assert!(!temperature.is_negative());
+        assert_eq!(temperature.abs().raw(), 0);
+    }
+
+    #[test]
+    fn temp_negative_is_negative() {
+        let temperature = Temperature::from_bytes(0x6E, 0xFE);
+
+        assert!(temperature.is_negative());
+        let abs = temperature.abs();
+        assert_eq!(abs.integer_part(), 25);
+        assert_eq!(abs.decimal_part(), 2);
+    }
+
+    #[test]
+    fn temp_negative_zero_is_negative() {
+        // -0.5
+        let temperature = Temperature::from_bytes(0xF8, 0xFF);
+
+        assert!(temperature.is_negative());
+        let abs = temperature.abs();
+        assert!(!abs.is_negative());
+        assert_eq!(abs.integer_part(), 0);
+        assert_eq!(abs.decimal_part(), 8);
+    }
+
+    /// A fixed-size buffer for checking formatted output.
+    struct FixedBuffer {
+        data: [u8; 16],
+        len: usize,
+    }
+
+    impl FixedBuffer {
+        fn new() -> Self {
+            FixedBuffer {
+                data: [0; 16],
+                len: 0,
+            }
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.data[..self.len]).unwrap()
+        }
+    }
+
+    impl core::fmt::Write for FixedBuffer {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            if end > self.data.len() {
+                return Err(core::fmt::Error);
+            }
+            self.data[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    macro_rules! test_temp_write_fixed {
+        ($name:ident, $low_sig:expr, $high_sig:expr, $width:expr, $decimals:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                let mut out = FixedBuffer::new();
+                Temperature::from_bytes($low_sig, $high_sig)
+                    .write_fixed(&mut out, $width, $decimals)
+                    .unwrap();
+                assert_eq!(out.as_str(), $expected);
+            }
+        };
+    }
+
+    // -5.125
+    test_temp_write_fixed!(
+        temp_write_fixed_full_precision,
+        0xAE,
+        0xFF,
+        9,
+        4,
+        "-005.1250"
+    );
+    // 25.0625
+    test_temp_write_fixed!(temp_write_fixed_truncates, 0x91, 0x01, 0, 2, "25.06");
+    // -0.5
+    test_temp_write_fixed!(temp_write_fixed_negative_zero, 0xF8, 0xFF, 6, 1, "-000.5");
+    test_temp_write_fixed!(temp_write_fixed_zero, 0x00, 0x00, 4, 0, "0000");
+    // 25.0625
+    test_temp_write_fixed!(
+        temp_write_fixed_extra_decimals,
+        0x91,
+        0x01,
+        0,
+        6,
+        "25.062500"
+    );
+
+    macro_rules! test_is_in_alarm {
+        ($name:ident, $low_sig:expr, $high_sig:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                let temperature = Temperature::from_bytes($low_sig, $high_sig);
+                assert_eq!(is_in_alarm(&temperature, 25, -10), $expected);
+            }
+        };
+    }
+
+    // 25.0625
+    test_is_in_alarm!(alarm_above_high, 0x91, 0x01, Some(AlarmKind::High));
+    // 24.9375
+    test_is_in_alarm!(alarm_in_range, 0x8F, 0x01, None);
+    // -10.125
+    test_is_in_alarm!(alarm_below_low, 0x5E, 0xFF, Some(AlarmKind::Low));
+    // -9.5 is compared as -10, as on the sensor.
+    test_is_in_alarm!(alarm_at_low_rounds_down, 0x68, 0xFF, Some(AlarmKind::Low));
+    // -8.9375
+    test_is_in_alarm!(alarm_negative_in_range, 0x71, 0xFF, None);
+
+    #[test]
+    fn temp_millidegrees() {
+        assert_eq!(Temperature::from_bytes(0x91, 0x01).millidegrees(), 25_062);
+        assert_eq!(Temperature::from_bytes(0x6E, 0xFE).millidegrees(), -25_125);
+    }
+
+    #[test]
+    fn trend_tracker_rising() {
+        let mut tracker = TrendTracker::<4>::new();
+        // 25, 25.5, 26, 26.5, then 27 degrees, one second apart.
+        for (i, raw) in [0x190i16, 0x198, 0x1A0, 0x1A8, 0x1B0].iter().enumerate() {
+            let [low_sig, high_sig] = raw.to_le_bytes();
+            tracker.push(
+                Temperature::from_bytes(low_sig, high_sig),
+                Duration::from_secs(i as u64),
+            );
+        }
+
+        assert_eq!(tracker.millidegrees_per_second(), Some(500));
+    }
+
+    #[test]
+    fn trend_tracker_needs_two_readings() {
+        let mut tracker = TrendTracker::<4>::new();
+        assert_eq!(tracker.millidegrees_per_second(), None);
+
+        tracker.push(Temperature::from_bytes(0x90, 0x01), Duration::from_secs(1));
+
+        assert_eq!(tracker.millidegrees_per_second(), None);
+    }
+
+    macro_rules! test_temp_rounding {
+        ($name:ident, $low_sig:expr, $high_sig:expr, $nearest:expr, $half_up:expr) => {
+            #[test]
+            fn $name() {
+                let temperature = Temperature::from_bytes($low_sig, $high_sig);
+
+                assert_eq!(temperature.nearest_integer(), $nearest);
+                assert_eq!(temperature.round_half_up(), $half_up);
+            }
+        };
+    }
+
+    // 24.5
+    test_temp_rounding!(temp_rounding_positive_tie, 0x88, 0x01, 25, 25);
+    // 24.4375
+    test_temp_rounding!(temp_rounding_positive_below_tie, 0x87, 0x01, 24, 24);
+    // 0.5
+    test_temp_rounding!(temp_rounding_positive_zero_tie, 0x08, 0x00, 1, 1);
+    // -0.5
+    test_temp_rounding!(temp_rounding_negative_zero_tie, 0xF8, 0xFF, -1, 0);
+    // -0.5625
+    test_temp_rounding!(temp_rounding_negative_zero_beyond_tie, 0xF7, 0xFF, -1, -1);
+    // -24.5
+    test_temp_rounding!(temp_rounding_negative_tie, 0x78, 0xFE, -25, -24);
+    // -24.4375
+    test_temp_rounding!(temp_rounding_negative_below_tie, 0x79, 0xFE, -24, -24);
+    // -24.5625
+    test_temp_rounding!(temp_rounding_negative_beyond_tie, 0x77, 0xFE, -25, -25);
+    // -1.0
+    test_temp_rounding!(temp_rounding_negative_one, 0xF0, 0xFF, -1, -1);
+    // -25.0
+    test_temp_rounding!(temp_rounding_negative_integer, 0x70, 0xFE, -25, -25);
+
+    macro_rules! test_temp_raw_round_trip {
+        ($name:ident, $low_sig:expr, $high_sig:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                let temperature = Temperature::from_bytes($low_sig, $high_sig);
+
+                assert_eq!(temperature.raw(), $expected);
+                assert_eq!(temperature.to_bytes(), [$low_sig, $high_sig]);
+            }
+        };
+    }
+
+    test_temp_raw_round_trip!(temp_raw_positive, 0xF9, 0x12, 0x12F9);
+    test_temp_raw_round_trip!(temp_raw_negative, 0x6E, 0xFE, -402);
+    test_temp_raw_round_trip!(temp_raw_negative_zero, 0xF8, 0xFF, -8);
+    test_temp_raw_round_trip!(temp_raw_zero, 0x00, 0x00, 0);
+
+    /// Shifts the pending CRC byte by a single bit, as described by the datasheet.
+    fn shift_crc_bit(bit: u8, crc: u8) -> u8 {
+        let xored_bit = (crc & 0x1) ^ bit;
+        let shifted = crc >> 1;
+        if xored_bit == 1 {
+            (shifted ^ 0b0000_1100) | 0b1000_0000
+        } else {
+            shifted
+        }
+    }
+
+    fn compute_crc_bitwise(bytes: &[u8]) -> u8 {
+        let mut crc = 0;
+        for byte in bytes {
+            for i in 0..8 {
+                crc = shift_crc_bit((byte >> i) & 0x1, crc);
+            }
+        }
+        crc
+    }
+
+    #[test]
+    fn crc_table_matches_bitwise() {
+        for byte in 0..=255u8 {
+            assert_eq!(
+                compute_crc(&[byte]),
+                compute_crc_bitwise(&[byte]),
+                "byte {}",
+                byte
+            );
+        }
+    }
+
+    #[test]
+    fn crc_scratchpad() {
+        // Example from the datasheet.
+        let data = [0x50, 0x05, 0x4B, 0x46, 0x7F, 0xFF, 0x0C, 0x10];
+
+        assert_eq!(compute_crc(&data), 0x1C);
+        assert_eq!(compute_crc_bitwise(&data), 0x1C);
+    }
+
+    #[test]
+    fn crc_rom() {
+        let id = DeviceId::from_bytes([0x02, 0x1C, 0xB8, 0x01, 0x00, 0x00, 0x00, 0xA2]);
+
+        assert_eq!(id.calculated_crc(), id.crc());
+        assert_eq!(compute_crc(&id.to_bytes()), 0);
+    }
+
+    macro_rules! test_conversion_time {
+        ($name:ident, $mode:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!($mode.get_conversion_time(), $expected);
+            }
+        };
+    }
+
+    test_conversion_time!(
+        conversion_time_9bit,
+        ResolutionMode::NineBit,
+        Duration::from_micros(93_750)
+    );
+    test_conversion_time!(
+        conversion_time_10bit,
+        ResolutionMode::TenBit,
+        Duration::from_micros(187_500)
+    );
+    test_conversion_time!(
+        conversion_time_11bit,
+        ResolutionMode::ElevenBit,
+        Duration::from_millis(375)
+    );
+    test_conversion_time!(
+        conversion_time_12bit,
+        ResolutionMode::TwelveBit,
+        Duration::from_millis(750)
+    );
+
+    macro_rules! test_bus_timing_valid {
+        ($name:ident, $field:ident, $value:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                let timing = BusTiming {
+                    $field: $value,
+                    ..DEFAULT_BUS_TIMING
+                };
+                assert_eq!(timing.is_valid(), $expected);
+            }
+        };
+    }
+
+    test_bus_timing_valid!(bus_timing_write_1_too_short, write_1_us, 0, false);
+    test_bus_timing_valid!(bus_timing_write_1_max, write_1_us, 15, true);
+    test_bus_timing_valid!(bus_timing_write_1_too_long, write_1_us, 16, false);
+    test_bus_timing_valid!(bus_timing_write_0_too_short, write_0_us, 59, false);
+    test_bus_timing_valid!(bus_timing_write_0_max, write_0_us, 120, true);
+    test_bus_timing_valid!(bus_timing_write_0_too_long, write_0_us, 121, false);
+    test_bus_timing_valid!(bus_timing_no_recovery, recovery_us, 0, false);
+    test_bus_timing_valid!(bus_timing_long_recovery, recovery_us, 100, true);
+    test_bus_timing_valid!(bus_timing_no_presence_samples, presence_samples, 0, false);
+
+    #[cfg(not(feature = "no-float"))]
     macro_rules! assert_close {
         ($left:expr, $right:expr, $delta:expr) => {
             assert!(
@@ -639,6 +3457,7 @@ mod tests {
 
     macro_rules! test_temp_to_float {
         ($name:ident, $type_conv:expr, $low_sig:expr, $high_sig:expr, $expected:expr) => {
+            #[cfg(not(feature = "no-float"))]
             #[test]
             fn $name() {
                 let temperature = Temperature::from_bytes($low_sig, $high_sig);
@@ -0,0 +1,98 @@
+use crate::{dhtxx, ds18b20, mcp300x};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{self, Debug, Display};
+
+/// An error from any of the drivers in this crate, with the HAL's error type erased.
+///
+/// Each driver's `Error` converts into this, so application code that reads several sensor types
+/// can use `?` with a single error type. The driver's error is kept as its `Debug` description.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SensorError {
+    /// An error from a DHT11 or DHT22.
+    Dhtxx(String),
+    /// An error from a DS18B20.
+    Ds18b20(String),
+    /// An error from an MCP300x.
+    Mcp300x(String),
+}
+
+impl Display for SensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SensorError::Dhtxx(description) => write!(f, "DHT error: {}", description),
+            SensorError::Ds18b20(description) => write!(f, "DS18B20 error: {}", description),
+            SensorError::Mcp300x(description) => write!(f, "MCP300x error: {}", description),
+        }
+    }
+}
+
+impl<TIoError: Debug> From<dhtxx::Error<TIoError>> for SensorError {
+    fn from(error: dhtxx::Error<TIoError>) -> SensorError {
+        SensorError::Dhtxx(format!("{:?}", error))
+    }
+}
+
+impl<TDelayError, TIoError, TInError, TOutError>
+    From<ds18b20::Error<TDelayError, TIoError, TInError, TOutError>> for SensorError
+where
+    TDelayError: Debug,
+    TIoError: Debug,
+    TInError: Debug,
+    TOutError: Debug,
+{
+    fn from(error: ds18b20::Error<TDelayError, TIoError, TInError, TOutError>) -> SensorError {
+        SensorError::Ds18b20(format!("{:?}", error))
+    }
+}
+
+impl<TIoError: Debug> From<mcp300x::Error<TIoError>> for SensorError {
+    fn from(error: mcp300x::Error<TIoError>) -> SensorError {
+        SensorError::Mcp300x(format!("{:?}", error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[derive(Debug)]
+    struct HalError;
+
+    #[test]
+    fn from_dhtxx_error() {
+        let error = SensorError::from(dhtxx::Error::<HalError>::Wrapped(HalError));
+        assert_eq!(error, SensorError::Dhtxx("Wrapped(HalError)".to_string()));
+    }
+
+    #[test]
+    fn from_ds18b20_error() {
+        let error = SensorError::from(ds18b20::Error::<HalError, (), (), ()>::NoSensorsFound);
+        assert_eq!(error, SensorError::Ds18b20("NoSensorsFound".to_string()));
+    }
+
+    #[test]
+    fn from_mcp300x_error() {
+        let error = SensorError::from(mcp300x::Error::<HalError>::ChannelOutOfRange(9));
+        assert_eq!(
+            error,
+            SensorError::Mcp300x("ChannelOutOfRange(9)".to_string())
+        );
+    }
+
+    #[test]
+    fn question_mark_converts() {
+        fn read() -> Result<u16, SensorError> {
+            let value = Err(mcp300x::Error::<HalError>::BadData)?;
+            Ok(value)
+        }
+        assert_eq!(read(), Err(SensorError::Mcp300x("BadData".to_string())));
+    }
+
+    #[test]
+    fn display_names_driver() {
+        let error = SensorError::Dhtxx("NoResponse".to_string());
+        assert_eq!(error.to_string(), "DHT error: NoResponse");
+    }
+}
@@ -1,5 +1,8 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 /// Universal drivers for reading from DHT11 or DHT22 sensors.
 ///
 /// Communication with DHT sensors is performed using a single pin that can switch between input
@@ -23,3 +26,113 @@ pub mod ds18b20;
 /// Refer to [this datasheet](https://cdn-shop.adafruit.com/datasheets/MCP3008.pdf) for more
 /// information about these devices.
 pub mod mcp300x;
+
+/// Re-exports the traits and types needed for typical use of the drivers in this crate.
+///
+/// Each driver's `Error` type is not included, since their names overlap. Refer to them through
+/// their modules instead, e.g. `dhtxx::Error`.
+///
+/// ```
+/// use simple_sensors::mcp300x;
+/// use simple_sensors::prelude::*;
+/// # use embedded_hal::spi::blocking::TransferInplace;
+/// #
+/// # #[derive(Debug)]
+/// # struct FakeError;
+/// # impl embedded_hal::spi::Error for FakeError {
+/// #     fn kind(&self) -> embedded_hal::spi::ErrorKind {
+/// #         embedded_hal::spi::ErrorKind::Other
+/// #     }
+/// # }
+/// #
+/// # /// Responds to every request with half of the maximum value.
+/// # struct FakeSpi;
+/// # impl TransferInplace<u8> for FakeSpi {
+/// #     type Error = FakeError;
+/// #     fn transfer_inplace(&mut self, words: &mut [u8]) -> Result<(), FakeError> {
+/// #         words.copy_from_slice(&[0x00, 0x02, 0x00]);
+/// #         Ok(())
+/// #     }
+/// # }
+/// # let mut spi = FakeSpi;
+///
+/// let value = mcp300x::read_mcp3008(Request::SingleEnded(0), &mut spi).unwrap();
+/// assert_eq!(value, 512);
+///
+/// let response = Dht22Response::from_bytes([0x01, 0xF4, 0x00, 0xFA]);
+/// assert_eq!(response.humidity_tenths(), 500);
+/// # #[cfg(not(feature = "no-float"))]
+/// assert_eq!(response.get_humidity(), 50.0);
+/// # #[cfg(not(feature = "no-float"))]
+/// assert_eq!(response.get_temperature(), 25.0);
+///
+/// let temperature = Temperature::from_bytes(0x91, 0x01);
+/// assert_eq!(temperature.millidegrees(), 25_062);
+/// # #[cfg(not(feature = "no-float"))]
+/// assert_eq!(f32::from(temperature), 25.0625);
+/// ```
+pub mod prelude;
+
+/// A common interface for reading temperatures from any of the sensors in this crate.
+///
+/// See [`temperature::TemperatureSource`]. Not available with the `no-float` feature.
+#[cfg(not(feature = "no-float"))]
+pub mod temperature;
+
+/// A common wrapper for tagging readings from any sensor with the time they were taken.
+pub mod timestamp;
+
+/// Formatting for logging timestamped readings as CSV, e.g. to an SD card.
+pub mod csv;
+
+/// A single error type for applications that use several of the drivers in this crate.
+///
+/// Requires the `alloc` feature. See [`error::SensorError`].
+#[cfg(feature = "alloc")]
+pub mod error;
+
+/// Small helpers that are shared by the drivers, and are useful for building custom sequences.
+pub mod util;
+
+/// Tick-counting pulse measurement for bit-banged single-wire protocols, as used by the DHT
+/// drivers.
+pub mod pulse;
+
+/// Adapters for using a single bidirectional pin type with the drivers in this crate.
+///
+/// The single-pin drivers expect separate input and output pin types that convert into each other
+/// via [`IoPin`](embedded_hal::digital::blocking::IoPin). Some HALs instead model a pin that can
+/// read and write in the same mode, which can be wrapped in a [`shared_pin::SharedIoPin`].
+pub mod shared_pin;
+
+/// Simulated hardware for testing code that uses these drivers, without a real sensor.
+///
+/// Requires the `testing` feature. A [`testing::Pin`] returns a predetermined sequence of line
+/// levels, and a [`testing::Clock`] provides the time and delay functions, only moving forward when
+/// asked to. [`testing::dht_frame`] generates the levels for a DHT sensor's response.
+///
+/// ```
+/// use simple_sensors::dhtxx::{self, Response};
+/// use simple_sensors::testing;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), dhtxx::Error<core::convert::Infallible>> {
+/// // 55% humidity and 24°C, followed by the parity byte.
+/// let pin = testing::Pin::new(testing::dht_frame([55, 0, 24, 0, 79]));
+/// let clock = testing::Clock::new();
+/// let mut sensor = dhtxx::Dht11::new(
+///     pin,
+///     || clock.now(),
+///     |instant| clock.elapsed_since(instant),
+///     None,
+/// )?;
+///
+/// let response = sensor.read(|duration| clock.delay(duration)).await?;
+///
+/// assert_eq!(response.humidity_tenths(), 550);
+/// assert_eq!(response.temperature_tenths(), 240);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -1,23 +1,80 @@
+use crate::util::retry;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use embedded_hal::nb;
 use embedded_hal::spi::blocking::TransferInplace;
+use embedded_hal::spi::nb::FullDuplex;
+use embedded_hal::spi::{Mode, MODE_0};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// The maximum SPI clock speed when V<sub>DD</sub> is 5V.
 pub const MAX_CLOCK_AT_5V: u32 = 3_600_000;
 /// The maximum SPI clock speed when V<sub>DD</sub> is 2.7V.
 pub const MAX_CLOCK_AT_2_7V: u32 = 1_350_000;
 
+const VDD_5V_MV: u32 = 5_000;
+const VDD_2_7V_MV: u32 = 2_700;
+
+/// The maximum SPI clock speed, in Hz, for the given V<sub>DD</sub> in millivolts.
+///
+/// The datasheet only gives the maximum clock speed at 2.7V and 5V, so this interpolates linearly
+/// between them, rounding down. Voltages above 5V are clamped to it. Returns `None` below 2.7V,
+/// since the device isn't rated to run there.
+pub fn max_clock_hz(vdd_mv: u32) -> Option<u32> {
+    if vdd_mv < VDD_2_7V_MV {
+        return None;
+    }
+    let vdd_mv = vdd_mv.min(VDD_5V_MV);
+    let clock_range = (MAX_CLOCK_AT_5V - MAX_CLOCK_AT_2_7V) as u64;
+    let vdd_range = (VDD_5V_MV - VDD_2_7V_MV) as u64;
+    let extra_clock = (vdd_mv - VDD_2_7V_MV) as u64 * clock_range / vdd_range;
+    Some(MAX_CLOCK_AT_2_7V + extra_clock as u32)
+}
+
+/// The number of SPI clock cycles used by each read, since every read transfers 3 bytes.
+const CLOCKS_PER_READ: u32 = 24;
+
+/// An estimate of the maximum number of reads per second at the given SPI clock speed.
+///
+/// This ignores the time spent toggling chip select and any gaps between transfers, so the real
+/// rate will be somewhat lower.
+pub fn max_sample_rate_hz(spi_clock_hz: u32) -> u32 {
+    spi_clock_hz / CLOCKS_PER_READ
+}
+
 /// The number of addressable channels on an MCP3004.
 pub const NUM_CHANNELS_MCP3004: u8 = 4;
 /// The number of addressable channels on an MCP3008.
 pub const NUM_CHANNELS_MCP3008: u8 = 8;
 
+/// The maximum value that can be read from these 10-bit devices.
+pub const MAX_VALUE: u16 = 1023;
+
+/// A sensible number of readings for [`read_settled`] to discard after switching channels.
+pub const DEFAULT_DISCARD_READS: u8 = 1;
+
+/// The maximum difference between the two readings taken by [`self_test`].
+pub const SELF_TEST_TOLERANCE: u16 = 16;
+
+/// How far from the expected rail values the readings taken by [`calibration_check`] may be.
+pub const CALIBRATION_TOLERANCE: u16 = 8;
+
 #[derive(Debug, PartialEq)]
 pub enum Error<TIoError> {
     /// Wrapped error from the SPI driver.
     Wrapped(TIoError),
-    /// Invalid argument was provided.
-    InvalidArgument,
+    /// The requested single-ended channel does not exist on this device.
+    ChannelOutOfRange(u8),
+    /// The requested differential mode is not supported by this device.
+    UnsupportedDifferentialMode(DifferentialMode),
     /// Bad data was read. Check the connection and try again.
     BadData,
+    /// An argument is invalid, e.g. an SPI configuration that can't be used with the device (see
+    /// [`check_spi_config`]).
+    InvalidArgument,
 }
 
 impl<TIoError> From<TIoError> for Error<TIoError> {
@@ -26,8 +83,102 @@ impl<TIoError> From<TIoError> for Error<TIoError> {
     }
 }
 
+/// Describes the range of values returned by a device, so that generic code can scale readings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Resolution {
+    /// The number of bits in each reading.
+    pub bits: u8,
+    /// Whether readings are two's complement signed values.
+    pub signed: bool,
+}
+
+impl Resolution {
+    /// The maximum value a reading can have.
+    pub fn max_value(&self) -> i32 {
+        if self.signed {
+            (1 << (self.bits - 1)) - 1
+        } else {
+            (1 << self.bits) - 1
+        }
+    }
+
+    /// The minimum value a reading can have.
+    pub fn min_value(&self) -> i32 {
+        if self.signed {
+            -(1 << (self.bits - 1))
+        } else {
+            0
+        }
+    }
+}
+
+/// The resolution of readings from an MCP3004.
+///
+/// This applies to both single-ended and differential reads, since differential reads are
+/// pseudo-differential: a negative difference is read as 0.
+pub fn mcp3004_resolution() -> Resolution {
+    Resolution {
+        bits: 10,
+        signed: false,
+    }
+}
+
+/// The resolution of readings from an MCP3008.
+///
+/// This applies to both single-ended and differential reads, since differential reads are
+/// pseudo-differential: a negative difference is read as 0.
+pub fn mcp3008_resolution() -> Resolution {
+    Resolution {
+        bits: 10,
+        signed: false,
+    }
+}
+
+/// Describes the inputs of a device, so that generic code can enumerate what it can read.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeviceSpec {
+    /// The number of single-ended channels, which are numbered from 0.
+    pub num_channels: u8,
+    /// The differential modes that the device supports.
+    pub differential_modes: &'static [DifferentialMode],
+    /// The resolution of readings from the device.
+    pub resolution: Resolution,
+}
+
+/// The inputs of an MCP3004.
+pub fn mcp3004_spec() -> DeviceSpec {
+    DeviceSpec {
+        num_channels: NUM_CHANNELS_MCP3004,
+        differential_modes: &[
+            DifferentialMode::ZeroMinusOne,
+            DifferentialMode::OneMinusZero,
+            DifferentialMode::TwoMinusThree,
+            DifferentialMode::ThreeMinusTwo,
+        ],
+        resolution: mcp3004_resolution(),
+    }
+}
+
+/// The inputs of an MCP3008.
+pub fn mcp3008_spec() -> DeviceSpec {
+    DeviceSpec {
+        num_channels: NUM_CHANNELS_MCP3008,
+        differential_modes: &[
+            DifferentialMode::ZeroMinusOne,
+            DifferentialMode::OneMinusZero,
+            DifferentialMode::TwoMinusThree,
+            DifferentialMode::ThreeMinusTwo,
+            DifferentialMode::FourMinusFive,
+            DifferentialMode::FiveMinusFour,
+            DifferentialMode::SixMinusSeven,
+            DifferentialMode::SevenMinusSix,
+        ],
+        resolution: mcp3008_resolution(),
+    }
+}
+
 /// Which channels to read the voltage difference between.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DifferentialMode {
     /// Reads V<sub>channel 0</sub> - V<sub>channel 1</sub>.
     ZeroMinusOne,
@@ -47,6 +198,41 @@ pub enum DifferentialMode {
     SevenMinusSix,
 }
 
+/// Formats the mode as its channel pair, e.g. `CH0-CH1`.
+impl core::fmt::Display for DifferentialMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let positive = *self as u8;
+        write!(f, "CH{}-CH{}", positive, positive ^ 1)
+    }
+}
+
+/// A single-ended input channel.
+///
+/// Unlike [`Request::SingleEnded`], this can't represent a channel that doesn't exist on an MCP3008,
+/// so it's preferable when the channel is known at compile time. Note that only channels 0-3 exist
+/// on an MCP3004.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Channel {
+    Ch0 = 0,
+    Ch1 = 1,
+    Ch2 = 2,
+    Ch3 = 3,
+    /// MCP3008 only.
+    Ch4 = 4,
+    /// MCP3008 only.
+    Ch5 = 5,
+    /// MCP3008 only.
+    Ch6 = 6,
+    /// MCP3008 only.
+    Ch7 = 7,
+}
+
+impl From<Channel> for Request {
+    fn from(channel: Channel) -> Request {
+        Request::SingleEnded(channel as u8)
+    }
+}
+
 const MAX_DIFFERENTIAL_MODE_MCP3004: DifferentialMode = DifferentialMode::ThreeMinusTwo;
 
 /// The type of read to make from the Mcp300x device.
@@ -72,7 +258,52 @@ pub enum Request {
     Differential(DifferentialMode),
 }
 
+/// Formats the request as a human-readable label, e.g. `CH3 (single-ended)` or
+/// `CH0-CH1 (differential)`.
+impl core::fmt::Display for Request {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Request::SingleEnded(channel) => write!(f, "CH{} (single-ended)", channel),
+            Request::Differential(mode) => write!(f, "{} (differential)", mode),
+        }
+    }
+}
+
+/// The channel indices that a [`Request`] reads from, as returned by [`Request::channels`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Channels {
+    channels: [u8; 2],
+    len: u8,
+}
+
+impl Channels {
+    /// The channel indices. For differential requests, IN+ comes before IN-.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.channels[..self.len as usize]
+    }
+}
+
 impl Request {
+    /// The channel indices that this request reads from, e.g. for labelling readings.
+    ///
+    /// A single-ended request reads from one channel, and a differential request reads from a pair
+    /// of channels, listed as IN+ then IN-. The channels are not validated for any device type.
+    pub fn channels(&self) -> Channels {
+        match self {
+            Request::SingleEnded(channel) => Channels {
+                channels: [*channel, 0],
+                len: 1,
+            },
+            Request::Differential(mode) => {
+                let positive = *mode as u8;
+                Channels {
+                    channels: [positive, positive ^ 1],
+                    len: 2,
+                }
+            }
+        }
+    }
+
     /// Converts the request to the appropriate bit sequence for the sensor. See page 19 of
     /// https://cdn-shop.adafruit.com/datasheets/MCP3008.pdf.
     ///
@@ -93,24 +324,88 @@ impl Request {
     }
 }
 
+/// A reading that records which kind of request produced it, as returned by `read_mcp3004_detailed`
+/// or `read_mcp3008_detailed`.
+///
+/// Both kinds of reading are in the range \[0, 1023\], but a differential reading is relative to
+/// another channel rather than to analog ground, so it mustn't be interpreted as an absolute
+/// voltage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Measurement {
+    /// A reading from a [`Request::SingleEnded`] request.
+    SingleEnded(u16),
+    /// A reading from a [`Request::Differential`] request.
+    Differential(u16),
+}
+
+impl Measurement {
+    fn new(request: Request, value: u16) -> Self {
+        match request {
+            Request::SingleEnded(_) => Measurement::SingleEnded(value),
+            Request::Differential(_) => Measurement::Differential(value),
+        }
+    }
+
+    /// The raw reading, regardless of its kind.
+    pub fn value(&self) -> u16 {
+        match self {
+            Measurement::SingleEnded(value) | Measurement::Differential(value) => *value,
+        }
+    }
+}
+
+/// A request that has already been validated for a specific device type.
+///
+/// This is created with [`Request::validate_for_mcp3004`] or [`Request::validate_for_mcp3008`], and
+/// can then be read repeatedly with [`read_validated`] without repeating the validation.
+#[derive(Clone, Copy, Debug)]
+pub struct ValidatedRequest {
+    bits: u8,
+}
+
 macro_rules! mcp_300x_impl {
-    ($name:ident, $is_arg_invalid:expr) => {
+    ($name:ident,
+     $name_u16:ident,
+     $detailed_name:ident,
+     $scan_name:ident,
+     $scan_vec_name:ident,
+     $validate_name:ident,
+     $num_channels:expr,
+     $validate:expr
+    ) => {
+        impl Request {
+            /// Validates the request for this device type, so that it can be read repeatedly with
+            /// [`read_validated`].
+            ///
+            /// This returns [`Error::ChannelOutOfRange`] or [`Error::UnsupportedDifferentialMode`]
+            /// if the request is not possible for this device type.
+            pub fn $validate_name<TIoError>(self) -> Result<ValidatedRequest, Error<TIoError>> {
+                $validate(self)?;
+                Ok(ValidatedRequest {
+                    bits: self.to_bits(),
+                })
+            }
+        }
+
         /// Reads the requested data from the device.
         ///
         /// It's important that the given SPI interface is configured correctly to work with the
         /// device. This means:
         ///
-        /// * Use 8 bits per word.
+        /// * Use 8 bits per word. If the SPI can only use 16-bit words, use the `_u16` variant of
+        ///   this function instead. With any other word size, the request will be misaligned, so
+        ///   the device will either read the wrong channel or return [`Error::BadData`].
         /// * Depending on V<sub>DD</sub>, the maximum possible clock speed is:
         ///   * *2.7V*: 1.35MHz ([`MAX_CLOCK_AT_2_7V`])
         ///   * *5V*: 3.6MHz ([`MAX_CLOCK_AT_5V`])
-        /// * Data is sent most-significant-bit first
+        /// * Data is sent most-significant-bit first. If the SPI can only send data
+        ///   least-significant-bit first, use [`read_validated_lsb_first`] instead.
         /// * SPI mode: 0 (i.e. idle low, capture on first transition)
         /// * Chip select is performed automatically by the SPI driver, or manually around this
         ///   function call.
         ///
-        /// This returns [`Error::InvalidArgument`] if the request is not possible for this device
-        /// type. See [`Request`] for more details.
+        /// This returns [`Error::ChannelOutOfRange`] or [`Error::UnsupportedDifferentialMode`] if
+        /// the request is not possible for this device type. See [`Request`] for more details.
         ///
         /// A valid response is in the range \[0, 1023\]. What this means depends on the request:
         ///
@@ -128,27 +423,650 @@ macro_rules! mcp_300x_impl {
         where
             TSpi: TransferInplace<u8, Error = TIoError>,
         {
-            if $is_arg_invalid(request) {
-                return Err(Error::InvalidArgument);
-            }
+            $validate(request)?;
             read(request, spi)
         }
+
+        /// Reads the requested data from the device using an SPI interface configured for 16 bits
+        /// per word.
+        ///
+        /// This is identical to the 8-bit read, except that the request is padded so that it can
+        /// be sent as two 16-bit words. All other SPI requirements are the same.
+        pub fn $name_u16<TSpi, TIoError>(
+            request: Request,
+            spi: &mut TSpi,
+        ) -> Result<u16, Error<TIoError>>
+        where
+            TSpi: TransferInplace<u16, Error = TIoError>,
+        {
+            $validate(request)?;
+            read_u16(request, spi)
+        }
+
+        /// Reads the requested data in the same way as the plain read, and tags the reading with
+        /// the kind of request that produced it. See [`Measurement`].
+        pub fn $detailed_name<TSpi, TIoError>(
+            request: Request,
+            spi: &mut TSpi,
+        ) -> Result<Measurement, Error<TIoError>>
+        where
+            TSpi: TransferInplace<u8, Error = TIoError>,
+        {
+            let value = $name(request, spi)?;
+            Ok(Measurement::new(request, value))
+        }
+
+        /// Reads every single-ended channel, and calls `on_active` with the channel and reading of
+        /// each channel whose reading is above `threshold`.
+        ///
+        /// This is intended as a diagnostic tool, e.g. to find which channels are connected while
+        /// prototyping. The SPI requirements are the same as for a single read.
+        pub fn $scan_name<TSpi, TIoError, TOnActive>(
+            spi: &mut TSpi,
+            threshold: u16,
+            mut on_active: TOnActive,
+        ) -> Result<(), Error<TIoError>>
+        where
+            TSpi: TransferInplace<u8, Error = TIoError>,
+            TOnActive: FnMut(u8, u16),
+        {
+            for channel in 0..$num_channels {
+                let value = read(Request::SingleEnded(channel), spi)?;
+                if value > threshold {
+                    on_active(channel, value);
+                }
+            }
+            Ok(())
+        }
+
+        /// Reads every single-ended channel, and returns the channel and reading of each channel
+        /// whose reading is above `threshold`.
+        #[cfg(feature = "alloc")]
+        pub fn $scan_vec_name<TSpi, TIoError>(
+            spi: &mut TSpi,
+            threshold: u16,
+        ) -> Result<Vec<(u8, u16)>, Error<TIoError>>
+        where
+            TSpi: TransferInplace<u8, Error = TIoError>,
+        {
+            let mut active = Vec::new();
+            $scan_name(spi, threshold, |channel, value| {
+                active.push((channel, value))
+            })?;
+            Ok(active)
+        }
     };
 }
 
-mcp_300x_impl!(read_mcp3004, |request| match request {
-    Request::SingleEnded(channel) => channel >= NUM_CHANNELS_MCP3004,
-    Request::Differential(mode) => (mode as u8) > (MAX_DIFFERENTIAL_MODE_MCP3004 as u8),
-});
-mcp_300x_impl!(read_mcp3008, |request| match request {
-    Request::SingleEnded(channel) => channel >= NUM_CHANNELS_MCP3008,
-    _ => false,
-});
+mcp_300x_impl!(
+    read_mcp3004,
+    read_mcp3004_u16,
+    read_mcp3004_detailed,
+    scan_mcp3004,
+    scan_mcp3004_vec,
+    validate_for_mcp3004,
+    NUM_CHANNELS_MCP3004,
+    |request| match request {
+        Request::SingleEnded(channel) if channel >= NUM_CHANNELS_MCP3004 => {
+            Err(Error::ChannelOutOfRange(channel))
+        }
+        Request::Differential(mode) if (mode as u8) > (MAX_DIFFERENTIAL_MODE_MCP3004 as u8) => {
+            Err(Error::UnsupportedDifferentialMode(mode))
+        }
+        _ => Ok(()),
+    }
+);
+mcp_300x_impl!(
+    read_mcp3008,
+    read_mcp3008_u16,
+    read_mcp3008_detailed,
+    scan_mcp3008,
+    scan_mcp3008_vec,
+    validate_for_mcp3008,
+    NUM_CHANNELS_MCP3008,
+    |request| match request {
+        Request::SingleEnded(channel) if channel >= NUM_CHANNELS_MCP3008 => {
+            Err(Error::ChannelOutOfRange(channel))
+        }
+        _ => Ok(()),
+    }
+);
+
+/// Performs a quick check that the device is responding correctly.
+///
+/// This reads channel 0 twice in quick succession. Both reads must include the null bit that
+/// precedes the data, and their values must be within [`SELF_TEST_TOLERANCE`] of each other. If
+/// not, [`Error::BadData`] is returned. This works with both the MCP3004 and the MCP3008.
+///
+/// Note that a floating (i.e. unconnected) channel 0 may fail this check, even if the device is
+/// working correctly.
+pub fn self_test<TSpi, TIoError>(spi: &mut TSpi) -> Result<(), Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    let first = read(Request::SingleEnded(0), spi)?;
+    let second = read(Request::SingleEnded(0), spi)?;
+    if first.abs_diff(second) > SELF_TEST_TOLERANCE {
+        return Err(Error::BadData);
+    }
+    Ok(())
+}
+
+/// The order in which the bits of each SPI word are sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
+
+/// How an SPI interface is configured, as declared by the user.
+///
+/// This crate can't read the configuration from the HAL, so this should be filled in from the
+/// same values used to configure it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpiConfig {
+    pub mode: Mode,
+    pub bit_order: BitOrder,
+    pub bits_per_word: u8,
+    pub clock_hz: u32,
+}
+
+/// Checks that an SPI interface with the given configuration can be used with [`read_mcp3004`] or
+/// [`read_mcp3008`], and returns [`Error::InvalidArgument`] if not.
+///
+/// This requires SPI mode 0, 8 bits per word, most-significant-bit first, and a clock no faster
+/// than [`MAX_CLOCK_AT_5V`]. Use this during setup to catch a misconfigured interface before it
+/// produces incorrect readings. It can only check the configuration that is declared, so it can't
+/// detect a HAL that was configured differently.
+pub fn check_spi_config<TIoError>(config: &SpiConfig) -> Result<(), Error<TIoError>> {
+    if config.mode != MODE_0
+        || config.bit_order != BitOrder::MsbFirst
+        || config.bits_per_word != 8
+        || config.clock_hz > MAX_CLOCK_AT_5V
+    {
+        return Err(Error::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Checks that the reference voltage and analog ground are wired correctly, e.g. during bring-up.
+///
+/// This reads `vref_channel`, which should be tied to V<sub>ref</sub>, and `agnd_channel`, which
+/// should be tied to analog ground. Returns `true` if the first reads within
+/// [`CALIBRATION_TOLERANCE`] of full scale and the second reads within it of zero. If not, the
+/// references are likely swapped or disconnected. Both channels must exist on the device, which is
+/// only validated against the MCP3008's eight channels.
+pub fn calibration_check<TSpi, TIoError>(
+    spi: &mut TSpi,
+    vref_channel: u8,
+    agnd_channel: u8,
+) -> Result<bool, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    check_encodable(Request::SingleEnded(vref_channel))?;
+    check_encodable(Request::SingleEnded(agnd_channel))?;
+    let vref = read(Request::SingleEnded(vref_channel), spi)?;
+    let agnd = read(Request::SingleEnded(agnd_channel), spi)?;
+    Ok(vref >= MAX_VALUE - CALIBRATION_TOLERANCE && agnd <= CALIBRATION_TOLERANCE)
+}
+
+/// Converts a reading to a percentage of the full scale, e.g. for display.
+///
+/// Note that this divides by the maximum reading, 1023 ([`MAX_VALUE`]), so that a full-scale
+/// reading is exactly 100%. This is different to converting a reading to a voltage, which divides
+/// by 1024 as shown in the datasheet: a reading of 1023 actually means the input voltage is between
+/// 1023/1024 and 1024/1024 of V<sub>ref</sub>.
+#[cfg(not(feature = "no-float"))]
+pub fn to_percent(raw: u16) -> f32 {
+    raw as f32 / MAX_VALUE as f32 * 100.0
+}
+
+/// Converts a reading to millivolts, given the reference voltage in millivolts.
+///
+/// As shown in the datasheet, this divides by 1024, so a reading of 1023 is slightly below
+/// V<sub>ref</sub>. The result is rounded down to the nearest millivolt. For differential reads,
+/// this is the voltage difference between the two channels.
+pub fn to_millivolts(raw: u16, vref_mv: u32) -> u32 {
+    raw as u32 * vref_mv / (MAX_VALUE as u32 + 1)
+}
+
+/// The code that sits halfway through the full scale.
+pub const MIDSCALE: u16 = 512;
+
+/// Reinterprets a reading as a signed offset from mid-scale, e.g. -512 for 0 and 511 for 1023.
+///
+/// This suits bidirectional sensors that are biased so that zero signal sits at
+/// V<sub>ref</sub>/2, such as ratiometric current sensors or joysticks. Differential reads need
+/// the same bias, since the MCP300x reports any negative difference as 0 rather than wrapping.
+pub fn as_signed_midscale(raw: u16) -> i16 {
+    raw as i16 - MIDSCALE as i16
+}
+
+/// A linear relationship between the input voltage and some engineering unit, e.g. for an analog
+/// temperature or pressure transducer.
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearScale {
+    /// Units per volt.
+    pub slope: f32,
+    /// The value in units at 0 volts.
+    pub offset: f32,
+}
+
+#[cfg(not(feature = "no-float"))]
+impl LinearScale {
+    /// Converts a reading to engineering units, given the reference voltage in volts.
+    ///
+    /// As in [`to_millivolts`], the voltage is calculated by dividing by 1024.
+    pub fn scale(&self, raw: u16, vref: f32) -> f32 {
+        raw as f32 / (MAX_VALUE as f32 + 1.0) * vref * self.slope + self.offset
+    }
+}
+
+/// The scale of an LM35 temperature sensor, which outputs 10mV/°C from 0V at 0°C.
+#[cfg(not(feature = "no-float"))]
+pub const LM35: LinearScale = LinearScale {
+    slope: 100.0,
+    offset: 0.0,
+};
+
+/// The scale of a TMP36 temperature sensor, which outputs 10mV/°C from 0.5V at 0°C.
+#[cfg(not(feature = "no-float"))]
+pub const TMP36: LinearScale = LinearScale {
+    slope: 100.0,
+    offset: -50.0,
+};
+
+/// Converts a reading from an LM35 to degrees Celsius, given the reference voltage in volts.
+///
+/// This is [`LM35`] applied with [`LinearScale::scale`]. Since the LM35 can't output negative
+/// voltages when powered from a single supply, readings start at 0°C.
+#[cfg(not(feature = "no-float"))]
+pub fn lm35_celsius(raw: u16, vref: f32) -> f32 {
+    LM35.scale(raw, vref)
+}
+
+/// Converts a reading from a TMP36 to degrees Celsius, given the reference voltage in volts.
+///
+/// This is [`TMP36`] applied with [`LinearScale::scale`]. The 0.5V offset allows temperatures
+/// down to -50°C.
+#[cfg(not(feature = "no-float"))]
+pub fn tmp36_celsius(raw: u16, vref: f32) -> f32 {
+    TMP36.scale(raw, vref)
+}
+
+/// A separate [`LinearScale`] for each single-ended channel, for boards where each input has its
+/// own signal conditioning.
+///
+/// Scales are indexed by channel number. On an MCP3004, only the first 4 are used.
+#[cfg(not(feature = "no-float"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Calibration {
+    pub per_channel: [LinearScale; NUM_CHANNELS_MCP3008 as usize],
+}
+
+#[cfg(not(feature = "no-float"))]
+impl Calibration {
+    /// The scale for the given channel.
+    pub fn scale_for(&self, channel: Channel) -> LinearScale {
+        self.per_channel[channel as usize]
+    }
+}
+
+/// Reads a single-ended channel and converts it to engineering units with that channel's scale,
+/// given the reference voltage in volts.
+///
+/// Note that channels 4-7 only exist on an MCP3008. The SPI requirements are the same as for
+/// [`read_mcp3004`] or [`read_mcp3008`].
+#[cfg(not(feature = "no-float"))]
+pub fn read_calibrated<TSpi, TIoError>(
+    channel: Channel,
+    spi: &mut TSpi,
+    calibration: &Calibration,
+    vref: f32,
+) -> Result<f32, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    let raw = read(channel.into(), spi)?;
+    Ok(calibration.scale_for(channel).scale(raw, vref))
+}
+
+/// Reads a single-ended channel `samples` times in quick succession, and returns the difference
+/// between the highest and lowest readings.
+///
+/// This peak-to-peak spread estimates the noise on the input, which helps decide how many readings
+/// to average. A spread of 0 or 1 codes is within the device's own quantization noise. Returns
+/// [`Error::InvalidArgument`] if `samples` is 0. The SPI requirements are the same as for
+/// [`read_mcp3004`] or [`read_mcp3008`].
+pub fn estimate_noise<TSpi, TIoError>(
+    channel: Channel,
+    spi: &mut TSpi,
+    samples: u8,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    if samples == 0 {
+        return Err(Error::InvalidArgument);
+    }
+    let request = Request::from(channel);
+    let mut min = MAX_VALUE;
+    let mut max = 0;
+    for _ in 0..samples {
+        let value = read(request, spi)?;
+        min = min.min(value);
+        max = max.max(value);
+    }
+    Ok(max - min)
+}
+
+/// Reads each of the given requests in order, and returns their voltages in millivolts.
+///
+/// The requests must have been validated for this device type (see [`ValidatedRequest`]). See
+/// [`to_millivolts`] for how the voltage is calculated, and [`read_validated`] for the SPI
+/// requirements.
+#[cfg(feature = "alloc")]
+pub fn read_channels_voltage<TSpi, TIoError>(
+    requests: &[ValidatedRequest],
+    spi: &mut TSpi,
+    vref_mv: u32,
+) -> Result<Vec<u32>, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    let mut voltages = Vec::with_capacity(requests.len());
+    for request in requests.iter() {
+        voltages.push(to_millivolts(read_validated(*request, spi)?, vref_mv));
+    }
+    Ok(voltages)
+}
+
+/// Reads a request that was already validated for this device type, using a non-blocking SPI
+/// interface.
+///
+/// Whenever the SPI interface would block, this yields to the async runtime before polling it
+/// again, so other tasks can run during the transfer. The SPI requirements are otherwise the same as
+/// for [`read_mcp3004`] or [`read_mcp3008`].
+pub async fn read_validated_async<TSpi, TIoError>(
+    request: ValidatedRequest,
+    spi: &mut TSpi,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: FullDuplex<u8, Error = TIoError>,
+{
+    let mut rx_buf = [0u8; 3];
+    for (tx, rx) in request_buf(request.bits).iter().zip(rx_buf.iter_mut()) {
+        poll_spi(|| spi.write(*tx)).await?;
+        *rx = poll_spi(|| spi.read()).await?;
+    }
+    parse_response(&rx_buf)
+}
+
+/// Reads each of the given requests in order using a non-blocking SPI interface, and returns their
+/// values in the same order.
+///
+/// The requests must have been validated for this device type (see [`ValidatedRequest`]). This
+/// yields to the async runtime between transfers, as described in [`read_validated_async`].
+pub async fn read_channels_async<TSpi, TIoError, const N: usize>(
+    requests: [ValidatedRequest; N],
+    spi: &mut TSpi,
+) -> Result<[u16; N], Error<TIoError>>
+where
+    TSpi: FullDuplex<u8, Error = TIoError>,
+{
+    let mut values = [0u16; N];
+    for (request, value) in requests.iter().zip(values.iter_mut()) {
+        *value = read_validated_async(*request, spi).await?;
+    }
+    Ok(values)
+}
+
+/// Reads a request that was already validated for this device type.
+///
+/// This skips validating the request, which can be useful when reading the same request at a high
+/// rate. The SPI requirements are the same as for [`read_mcp3004`] or [`read_mcp3008`].
+pub fn read_validated<TSpi, TIoError>(
+    request: ValidatedRequest,
+    spi: &mut TSpi,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    read_bits(request.bits, spi)
+}
+
+/// A fixed set of requests that can be read back-to-back repeatedly.
+///
+/// The requests are validated once when building the plan, and the transmit buffer for each
+/// request is precomputed, so [`ScanPlan::execute`] does no per-call validation or encoding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScanPlan<const N: usize> {
+    tx_bufs: [[u8; 3]; N],
+}
+
+impl<const N: usize> ScanPlan<N> {
+    /// Builds a plan from requests that were already validated for this device type.
+    pub fn new(requests: [ValidatedRequest; N]) -> Self {
+        let mut tx_bufs = [[0u8; 3]; N];
+        for (request, tx_buf) in requests.iter().zip(tx_bufs.iter_mut()) {
+            *tx_buf = request_buf(request.bits);
+        }
+        ScanPlan { tx_bufs }
+    }
+
+    /// Reads every request in the plan, in order, writing the results into `out`.
+    ///
+    /// The SPI requirements are the same as for [`read_mcp3004`] or [`read_mcp3008`]. If any read
+    /// fails, the error is returned immediately and the remaining entries of `out` are left
+    /// unchanged.
+    pub fn execute<TSpi, TIoError>(
+        &self,
+        spi: &mut TSpi,
+        out: &mut [u16; N],
+    ) -> Result<(), Error<TIoError>>
+    where
+        TSpi: TransferInplace<u8, Error = TIoError>,
+    {
+        for (tx_buf, value) in self.tx_bufs.iter().zip(out.iter_mut()) {
+            let mut buf = *tx_buf;
+            spi.transfer_inplace(&mut buf)?;
+            *value = parse_response(&buf)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads a request that was already validated for this device type, retrying up to
+/// `max_attempts` times if [`Error::BadData`] is returned.
+///
+/// Any other error is returned immediately. The SPI requirements are the same as for
+/// [`read_mcp3004`] or [`read_mcp3008`].
+pub fn read_validated_retry<TSpi, TIoError>(
+    request: ValidatedRequest,
+    spi: &mut TSpi,
+    max_attempts: u8,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    retry(
+        max_attempts,
+        |err| matches!(err, Error::BadData),
+        || read_bits(request.bits, spi),
+    )
+}
+
+/// Reads a request that was already validated for this device type, after first discarding
+/// `discard` readings of the same request.
+///
+/// When switching between channels, the device's sample-and-hold capacitor may not have settled
+/// by the first reading, especially with a high-impedance source. The discarded readings give it
+/// time to settle, so only the final reading is returned. [`DEFAULT_DISCARD_READS`] is a sensible
+/// default. The SPI requirements are the same as for [`read_mcp3004`] or [`read_mcp3008`].
+pub fn read_settled<TSpi, TIoError>(
+    request: ValidatedRequest,
+    spi: &mut TSpi,
+    discard: u8,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    for _ in 0..discard {
+        read_raw_bits(request.bits, spi)?;
+    }
+    read_bits(request.bits, spi)
+}
+
+/// Reads a request that was already validated for this device type until two consecutive readings
+/// are within `tolerance` of each other, and returns the later of the two.
+///
+/// This filters out transient glitches in slowly-changing signals. Returns [`Error::BadData`] if no
+/// two consecutive readings agree within `max_reads` readings, or [`Error::InvalidArgument`] if
+/// `max_reads` is less than 2. The SPI requirements are the same as for [`read_mcp3004`] or
+/// [`read_mcp3008`].
+pub fn read_until_stable<TSpi, TIoError>(
+    request: ValidatedRequest,
+    spi: &mut TSpi,
+    tolerance: u16,
+    max_reads: u8,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    if max_reads < 2 {
+        return Err(Error::InvalidArgument);
+    }
+    let mut previous = read_bits(request.bits, spi)?;
+    for _ in 1..max_reads {
+        let value = read_bits(request.bits, spi)?;
+        if value.abs_diff(previous) <= tolerance {
+            return Ok(value);
+        }
+        previous = value;
+    }
+    Err(Error::BadData)
+}
+
+/// Reads a request that was already validated for this device type, using an SPI interface that
+/// can only send data least-significant-bit first.
+///
+/// The device itself always sends and receives data most-significant-bit first, which is the
+/// default for all other reads. This variant reverses the bits of each byte before sending the
+/// request and after receiving the response, so that the device still sees the correct bit order.
+/// All other SPI requirements are the same as for [`read_mcp3004`] or [`read_mcp3008`].
+pub fn read_validated_lsb_first<TSpi, TIoError>(
+    request: ValidatedRequest,
+    spi: &mut TSpi,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    let mut tx_buf = request_buf(request.bits);
+    for byte in tx_buf.iter_mut() {
+        *byte = byte.reverse_bits();
+    }
+    spi.transfer_inplace(&mut tx_buf)?;
+    for byte in tx_buf.iter_mut() {
+        *byte = byte.reverse_bits();
+    }
+    parse_response(&tx_buf)
+}
+
+/// Reads a request that was already validated for this device type, using an SPI interface
+/// configured for 16 bits per word.
+///
+/// The SPI requirements are the same as for [`read_mcp3004_u16`] or [`read_mcp3008_u16`].
+pub fn read_validated_u16<TSpi, TIoError>(
+    request: ValidatedRequest,
+    spi: &mut TSpi,
+) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u16, Error = TIoError>,
+{
+    read_bits_u16(request.bits, spi)
+}
+
+/// Reads the requested data from the device, and returns the three bytes received over SPI.
+///
+/// This is intended for diagnosing a misbehaving device. The response is returned before the null
+/// bit is checked or the value is extracted, so it never returns [`Error::BadData`]. The request is
+/// only checked to be one that an MCP3008 supports. The SPI requirements are the same as for
+/// [`read_mcp3004`] or [`read_mcp3008`].
+///
+/// For a valid response, the data is in the last 10 bits, and the bit before that is always 0.
+pub fn read_raw<TSpi, TIoError>(
+    request: Request,
+    spi: &mut TSpi,
+) -> Result<[u8; 3], Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    check_encodable(request)?;
+    read_raw_bits(request.to_bits(), spi)
+}
 
 fn read<TSpi, TIoError>(request: Request, spi: &mut TSpi) -> Result<u16, Error<TIoError>>
 where
     TSpi: TransferInplace<u8, Error = TIoError>,
 {
+    read_bits(request.to_bits(), spi)
+}
+
+/// Reads the request encoded by the given bits (see `Request::to_bits`).
+fn read_bits<TSpi, TIoError>(bits: u8, spi: &mut TSpi) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    parse_response(&read_raw_bits(bits, spi)?)
+}
+
+/// Reads the request encoded by the given bits (see `Request::to_bits`), and returns the response
+/// without checking it.
+fn read_raw_bits<TSpi, TIoError>(bits: u8, spi: &mut TSpi) -> Result<[u8; 3], Error<TIoError>>
+where
+    TSpi: TransferInplace<u8, Error = TIoError>,
+{
+    let mut tx_buf = request_buf(bits);
+    spi.transfer_inplace(&mut tx_buf)?;
+    Ok(tx_buf)
+}
+
+/// Polls the non-blocking SPI operation until it completes, yielding whenever it would block.
+async fn poll_spi<T, TIoError, TOp>(mut op: TOp) -> Result<T, Error<TIoError>>
+where
+    TOp: FnMut() -> nb::Result<T, TIoError>,
+{
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(nb::Error::Other(error)) => return Err(Error::Wrapped(error)),
+            Err(nb::Error::WouldBlock) => YieldNow { yielded: false }.await,
+        }
+    }
+}
+
+/// A future that is pending the first time it's polled, so that other tasks get a chance to run.
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Builds the 8-bit word request for the given bits (see `Request::to_bits`).
+fn request_buf(bits: u8) -> [u8; 3] {
     // Send the request aligned such that it is easy to read data using 8-bit words. See page 21 of
     // https://cdn-shop.adafruit.com/datasheets/MCP3008.pdf.
     //
@@ -156,16 +1074,54 @@ where
     //   1 - start bit
     //   1/0 - single-ended/differential read
     //   X X X - channel select bits
-    let mut tx_buf: [u8; 3] = [0x1, request.to_bits() << 4, 0x0];
-    spi.transfer_inplace(&mut tx_buf)?;
+    [0x1, bits << 4, 0x0]
+}
 
-    if (tx_buf[1] & 0b100) != 0 {
+/// Extracts the value from a response to a request built by `request_buf`.
+fn parse_response<TIoError>(rx_buf: &[u8; 3]) -> Result<u16, Error<TIoError>> {
+    if (rx_buf[1] & 0b100) != 0 {
         // MCP300x sensors should send a null-bit right before the data. If this is missing, then
         // this read can't be trusted.
         return Err(Error::BadData);
     }
 
-    Ok((((tx_buf[1] & 0b11) as u16) << 8) + tx_buf[2] as u16)
+    Ok((((rx_buf[1] & 0b11) as u16) << 8) + rx_buf[2] as u16)
+}
+
+fn read_u16<TSpi, TIoError>(request: Request, spi: &mut TSpi) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u16, Error = TIoError>,
+{
+    read_bits_u16(request.to_bits(), spi)
+}
+
+/// Reads the request encoded by the given bits (see `Request::to_bits`) using 16-bit words.
+fn read_bits_u16<TSpi, TIoError>(bits: u8, spi: &mut TSpi) -> Result<u16, Error<TIoError>>
+where
+    TSpi: TransferInplace<u16, Error = TIoError>,
+{
+    // This is the same sequence as the 8-bit read, but with an extra leading zero-byte. This
+    // aligns the response so that the null-bit and data fill the end of the second word.
+    let mut tx_buf: [u16; 2] = [0x1, (bits as u16) << 12];
+    spi.transfer_inplace(&mut tx_buf)?;
+
+    if (tx_buf[1] & 0x400) != 0 {
+        return Err(Error::BadData);
+    }
+
+    Ok(tx_buf[1] & 0x3FF)
+}
+
+/// Checks that the request fits in the channel select bits.
+///
+/// Channels beyond the three channel select bits would silently alias onto other channels.
+fn check_encodable<TIoError>(request: Request) -> Result<(), Error<TIoError>> {
+    match request {
+        Request::SingleEnded(channel) if channel >= NUM_CHANNELS_MCP3008 => {
+            Err(Error::ChannelOutOfRange(channel))
+        }
+        _ => Ok(()),
+    }
 }
 
 #[cfg(test)]
@@ -173,6 +1129,172 @@ mod tests {
     use super::*;
     use Request::{Differential, SingleEnded};
 
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn linear_scale_tmp36() {
+        // A TMP36 outputs 0.5V at 0°C, and 10mV/°C.
+        let scale = LinearScale {
+            slope: 100.0,
+            offset: -50.0,
+        };
+
+        assert_eq!(scale.scale(0, 3.3), -50.0);
+        // 256/1024 * 3.2V = 0.8V, i.e. 30°C.
+        assert!((scale.scale(256, 3.2) - 30.0).abs() < 0.001);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn lm35_celsius_at_known_reading() {
+        // 256/1024 * 3.2V = 0.8V, i.e. 80°C.
+        assert!((lm35_celsius(256, 3.2) - 80.0).abs() < 0.001);
+        assert_eq!(lm35_celsius(0, 3.3), 0.0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn tmp36_celsius_at_known_reading() {
+        // 192/1024 * 4.0V = 0.75V, i.e. 25°C.
+        assert!((tmp36_celsius(192, 4.0) - 25.0).abs() < 0.001);
+        assert_eq!(tmp36_celsius(0, 3.3), -50.0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn linear_scale_identity_is_voltage() {
+        let scale = LinearScale {
+            slope: 1.0,
+            offset: 0.0,
+        };
+
+        assert_eq!(scale.scale(512, 4.0), 2.0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn to_percent_zero() {
+        assert_eq!(to_percent(0), 0.0);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn to_percent_half() {
+        assert!((to_percent(512) - 50.0).abs() < 0.1);
+    }
+
+    #[cfg(not(feature = "no-float"))]
+    #[test]
+    fn to_percent_full_scale() {
+        assert_eq!(to_percent(MAX_VALUE), 100.0);
+    }
+
+    #[test]
+    fn as_signed_midscale_zero() {
+        assert_eq!(as_signed_midscale(0), -512);
+    }
+
+    #[test]
+    fn as_signed_midscale_midpoint() {
+        assert_eq!(as_signed_midscale(MIDSCALE), 0);
+    }
+
+    #[test]
+    fn as_signed_midscale_full_scale() {
+        assert_eq!(as_signed_midscale(MAX_VALUE), 511);
+    }
+
+    #[test]
+    fn max_clock_hz_at_2_7v() {
+        assert_eq!(max_clock_hz(2_700), Some(MAX_CLOCK_AT_2_7V));
+    }
+
+    #[test]
+    fn max_clock_hz_at_5v() {
+        assert_eq!(max_clock_hz(5_000), Some(MAX_CLOCK_AT_5V));
+    }
+
+    #[test]
+    fn max_clock_hz_at_midpoint() {
+        assert_eq!(max_clock_hz(3_850), Some(2_475_000));
+    }
+
+    #[test]
+    fn max_clock_hz_at_3_3v() {
+        let clock = max_clock_hz(3_300).unwrap();
+        assert!(clock > MAX_CLOCK_AT_2_7V && clock < 2_475_000);
+    }
+
+    #[test]
+    fn max_clock_hz_clamps_above_5v() {
+        assert_eq!(max_clock_hz(5_500), Some(MAX_CLOCK_AT_5V));
+    }
+
+    #[test]
+    fn max_clock_hz_below_2_7v_is_none() {
+        assert_eq!(max_clock_hz(2_699), None);
+        assert_eq!(max_clock_hz(1_800), None);
+    }
+
+    const VALID_SPI_CONFIG: SpiConfig = SpiConfig {
+        mode: MODE_0,
+        bit_order: BitOrder::MsbFirst,
+        bits_per_word: 8,
+        clock_hz: MAX_CLOCK_AT_2_7V,
+    };
+
+    #[test]
+    fn check_spi_config_valid() {
+        assert_eq!(check_spi_config::<()>(&VALID_SPI_CONFIG), Ok(()));
+    }
+
+    #[test]
+    fn check_spi_config_wrong_mode() {
+        let config = SpiConfig {
+            mode: embedded_hal::spi::MODE_1,
+            ..VALID_SPI_CONFIG
+        };
+        assert_eq!(check_spi_config::<()>(&config), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn check_spi_config_lsb_first() {
+        let config = SpiConfig {
+            bit_order: BitOrder::LsbFirst,
+            ..VALID_SPI_CONFIG
+        };
+        assert_eq!(check_spi_config::<()>(&config), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn check_spi_config_wrong_word_size() {
+        let config = SpiConfig {
+            bits_per_word: 16,
+            ..VALID_SPI_CONFIG
+        };
+        assert_eq!(check_spi_config::<()>(&config), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn check_spi_config_clock_too_fast() {
+        let config = SpiConfig {
+            clock_hz: MAX_CLOCK_AT_5V + 1,
+            ..VALID_SPI_CONFIG
+        };
+        assert_eq!(check_spi_config::<()>(&config), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn max_sample_rate_hz_at_max_clocks() {
+        assert_eq!(max_sample_rate_hz(MAX_CLOCK_AT_5V), 150_000);
+        assert_eq!(max_sample_rate_hz(MAX_CLOCK_AT_2_7V), 56_250);
+    }
+
+    #[test]
+    fn max_sample_rate_hz_rounds_down() {
+        assert_eq!(max_sample_rate_hz(1_000_000), 41_666);
+        assert_eq!(max_sample_rate_hz(23), 0);
+    }
+
     macro_rules! test_request_to_bits {
         ($name:ident, $req:expr, $expected:expr) => {
             #[test]
@@ -190,6 +1312,10 @@ mod tests {
     test_request_to_bits!(single_edge_5, SingleEnded(5), 0b1101);
     test_request_to_bits!(single_edge_6, SingleEnded(6), 0b1110);
     test_request_to_bits!(single_edge_7, SingleEnded(7), 0b1111);
+    test_request_to_bits!(channel_0, Request::from(Channel::Ch0), 0b1000);
+    test_request_to_bits!(channel_3, Request::from(Channel::Ch3), 0b1011);
+    test_request_to_bits!(channel_4, Request::from(Channel::Ch4), 0b1100);
+    test_request_to_bits!(channel_7, Request::from(Channel::Ch7), 0b1111);
     test_request_to_bits!(
         differential_0_minus_1,
         Differential(DifferentialMode::ZeroMinusOne),
@@ -230,4 +1356,62 @@ mod tests {
         Differential(DifferentialMode::SevenMinusSix),
         0b0111
     );
+
+    macro_rules! test_request_channels {
+        ($name:ident, $req:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!($req.channels().as_slice(), $expected);
+            }
+        };
+    }
+
+    test_request_channels!(single_edge_0_channels, SingleEnded(0), [0]);
+    test_request_channels!(single_edge_1_channels, SingleEnded(1), [1]);
+    test_request_channels!(single_edge_2_channels, SingleEnded(2), [2]);
+    test_request_channels!(single_edge_3_channels, SingleEnded(3), [3]);
+    test_request_channels!(single_edge_4_channels, SingleEnded(4), [4]);
+    test_request_channels!(single_edge_5_channels, SingleEnded(5), [5]);
+    test_request_channels!(single_edge_6_channels, SingleEnded(6), [6]);
+    test_request_channels!(single_edge_7_channels, SingleEnded(7), [7]);
+    test_request_channels!(
+        differential_0_minus_1_channels,
+        Differential(DifferentialMode::ZeroMinusOne),
+        [0, 1]
+    );
+    test_request_channels!(
+        differential_1_minus_0_channels,
+        Differential(DifferentialMode::OneMinusZero),
+        [1, 0]
+    );
+    test_request_channels!(
+        differential_2_minus_3_channels,
+        Differential(DifferentialMode::TwoMinusThree),
+        [2, 3]
+    );
+    test_request_channels!(
+        differential_3_minus_2_channels,
+        Differential(DifferentialMode::ThreeMinusTwo),
+        [3, 2]
+    );
+    test_request_channels!(
+        differential_4_minus_5_channels,
+        Differential(DifferentialMode::FourMinusFive),
+        [4, 5]
+    );
+    test_request_channels!(
+        differential_5_minus_4_channels,
+        Differential(DifferentialMode::FiveMinusFour),
+        [5, 4]
+    );
+    test_request_channels!(
+        differential_6_minus_7_channels,
+        Differential(DifferentialMode::SixMinusSeven),
+        [6, 7]
+    );
+    test_request_channels!(
+        differential_7_minus_6_channels,
+        Differential(DifferentialMode::SevenMinusSix),
+        [7, 6]
+    );
 }
@@ -0,0 +1,6 @@
+pub use crate::dhtxx::{Dht11, Dht11Response, Dht22, Dht22Response, Response};
+pub use crate::ds18b20::{Ds18b20, ResolutionMode, Temperature};
+pub use crate::mcp300x::{DifferentialMode, Request};
+#[cfg(not(feature = "no-float"))]
+pub use crate::temperature::TemperatureSource;
+pub use crate::timestamp::Timestamped;
@@ -0,0 +1,121 @@
+use embedded_hal::digital::blocking::InputPin;
+
+/// An error while measuring a pulse.
+#[derive(Debug, PartialEq)]
+pub enum Error<TIoError> {
+    /// Wrapped error from the HAL.
+    Wrapped(TIoError),
+    /// The pulse lasted for more than the allowed number of samples.
+    Timeout,
+}
+
+/// Samples the pin until it reads low, and returns how many samples read high.
+///
+/// Ticks are counted as fast as the pin can be sampled, so they're only comparable to other ticks
+/// measured on the same platform, e.g. to tell short pulses from long ones. This returns 0 if the
+/// pin is already low, or [`Error::Timeout`] once more than `timeout` samples have read high.
+#[inline]
+pub fn measure_high<TPin, TIoError>(pin: &TPin, timeout: u32) -> Result<u32, Error<TIoError>>
+where
+    TPin: InputPin<Error = TIoError>,
+{
+    let mut ticks = 0u32;
+    while pin.is_high().map_err(Error::Wrapped)? {
+        ticks += 1;
+        if ticks > timeout {
+            return Err(Error::Timeout);
+        }
+    }
+    Ok(ticks)
+}
+
+/// Samples the pin until it reads high, and returns how many samples read low.
+///
+/// See [`measure_high`] for how ticks are counted.
+#[inline]
+pub fn measure_low<TPin, TIoError>(pin: &TPin, timeout: u32) -> Result<u32, Error<TIoError>>
+where
+    TPin: InputPin<Error = TIoError>,
+{
+    let mut ticks = 0u32;
+    while pin.is_low().map_err(Error::Wrapped)? {
+        ticks += 1;
+        if ticks > timeout {
+            return Err(Error::Timeout);
+        }
+    }
+    Ok(ticks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+    use core::convert::Infallible;
+
+    /// A pin that reads the given levels in order, then stays high.
+    struct LevelsPin {
+        levels: &'static [u8],
+        next: Cell<usize>,
+    }
+
+    impl LevelsPin {
+        fn new(levels: &'static [u8]) -> Self {
+            LevelsPin {
+                levels,
+                next: Cell::new(0),
+            }
+        }
+
+        fn next_level(&self) -> bool {
+            let index = self.next.get();
+            self.next.set(index + 1);
+            match self.levels.get(index) {
+                Some(level) => *level == 1,
+                None => true,
+            }
+        }
+    }
+
+    impl InputPin for LevelsPin {
+        type Error = Infallible;
+
+        fn is_high(&self) -> Result<bool, Infallible> {
+            Ok(self.next_level())
+        }
+
+        fn is_low(&self) -> Result<bool, Infallible> {
+            Ok(!self.next_level())
+        }
+    }
+
+    #[test]
+    fn measure_high_counts_high_samples() {
+        let pin = LevelsPin::new(&[1, 1, 1, 0]);
+        assert_eq!(measure_high(&pin, 10), Ok(3));
+    }
+
+    #[test]
+    fn measure_high_when_already_low() {
+        let pin = LevelsPin::new(&[0]);
+        assert_eq!(measure_high(&pin, 10), Ok(0));
+    }
+
+    #[test]
+    fn measure_high_timeout() {
+        let pin = LevelsPin::new(&[1, 1, 1, 0]);
+        assert_eq!(measure_high(&pin, 2), Err(Error::Timeout));
+    }
+
+    #[test]
+    fn measure_low_counts_low_samples() {
+        let pin = LevelsPin::new(&[0, 0, 1]);
+        assert_eq!(measure_low(&pin, 2), Ok(2));
+    }
+
+    #[test]
+    fn measure_low_timeout() {
+        let pin = LevelsPin::new(&[0, 0, 0, 1]);
+        assert_eq!(measure_low(&pin, 2), Err(Error::Timeout));
+    }
+}
@@ -0,0 +1,94 @@
+use embedded_hal::digital::PinState;
+use embedded_hal::digital::blocking::{InputPin, IoPin, OutputPin};
+
+/// Wraps a single bidirectional pin so that it can be used where separate input and output pin
+/// types are expected.
+///
+/// Switching to input mode releases the line by setting the pin high, and switching to output mode
+/// sets the requested state. This means the underlying pin must be able to read the line while
+/// it's not driving it low, as is the case for an open-drain pin with a pull-up resistor.
+#[derive(Debug)]
+pub struct SharedIoPin<TPin> {
+    pin: TPin,
+}
+
+impl<TPin> SharedIoPin<TPin> {
+    pub fn new(pin: TPin) -> Self {
+        SharedIoPin { pin }
+    }
+
+    /// Returns the underlying pin.
+    pub fn into_inner(self) -> TPin {
+        self.pin
+    }
+}
+
+impl<TPin: InputPin> InputPin for SharedIoPin<TPin> {
+    type Error = TPin::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.pin.is_high()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.pin.is_low()
+    }
+}
+
+impl<TPin: OutputPin> OutputPin for SharedIoPin<TPin> {
+    type Error = TPin::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_low()
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_high()
+    }
+}
+
+impl<TPin: InputPin + OutputPin> IoPin<SharedIoPin<TPin>, SharedIoPin<TPin>> for SharedIoPin<TPin> {
+    type Error = <TPin as OutputPin>::Error;
+
+    fn into_input_pin(mut self) -> Result<SharedIoPin<TPin>, Self::Error> {
+        // Release the line.
+        self.pin.set_high()?;
+        Ok(self)
+    }
+
+    fn into_output_pin(mut self, state: PinState) -> Result<SharedIoPin<TPin>, Self::Error> {
+        self.pin.set_state(state)?;
+        Ok(self)
+    }
+}
+
+/// Forwards to a borrowed pin, so that it can be wrapped in a [`SharedIoPin`].
+///
+/// The HAL only implements [`InputPin`] for shared references, so a mutable reference can't be
+/// wrapped directly.
+#[derive(Debug)]
+pub(crate) struct BorrowedPin<'a, TPin>(pub(crate) &'a mut TPin);
+
+impl<'a, TPin: InputPin> InputPin for BorrowedPin<'a, TPin> {
+    type Error = TPin::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.0.is_high()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.0.is_low()
+    }
+}
+
+impl<'a, TPin: OutputPin> OutputPin for BorrowedPin<'a, TPin> {
+    type Error = TPin::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low()
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high()
+    }
+}
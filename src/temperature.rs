@@ -0,0 +1,13 @@
+/// A sensor that can read the current temperature.
+///
+/// This allows applications that only need temperatures to swap between sensors without changing
+/// any other code. Sensor-specific functionality, such as reading humidity, is still only
+/// available from each driver.
+#[allow(async_fn_in_trait)]
+pub trait TemperatureSource {
+    /// The error returned when a read fails.
+    type Error;
+
+    /// Reads the current temperature, in degrees Celsius.
+    async fn read_temperature_celsius(&mut self) -> Result<f32, Self::Error>;
+}
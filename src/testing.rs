@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::convert::Infallible;
+use core::future::{ready, Ready};
+use core::time::Duration;
+use embedded_hal::digital::PinState;
+use embedded_hal::digital::blocking::{InputPin, IoPin, OutputPin};
+
+/// Creates the line levels for a DHT sensor's complete response to a start pulse, sending the
+/// given bytes.
+///
+/// The last byte is the parity byte, which the driver checks against the first four, so use a
+/// mismatched value to simulate a corrupted read. Each level is read once by [`Pin`], as 0 (low)
+/// or 1 (high).
+pub fn dht_frame(bytes: [u8; 5]) -> Vec<u8> {
+    // Start with the acknowledgement.
+    let mut levels = Vec::from([1, 1, 0, 0, 1, 1]);
+    for byte in bytes.iter() {
+        for bit in (0..8).rev() {
+            // The sensor holds the line high for longer to send a 1.
+            if byte >> bit & 1 == 1 {
+                levels.extend_from_slice(&[0, 0, 1, 1, 1]);
+            } else {
+                levels.extend_from_slice(&[0, 0, 1, 1]);
+            }
+        }
+    }
+    // The sensor then pulls the line low to end the transmission.
+    levels.extend_from_slice(&[0, 0, 1, 1]);
+    levels
+}
+
+/// A simulated pin, which returns a predetermined sequence of levels when read.
+///
+/// Each read returns the next level in the sequence. Once they've all been read, every later
+/// read returns the default level, which is high unless set otherwise. Writes and mode changes
+/// always succeed, and have no effect.
+#[derive(Debug)]
+pub struct Pin {
+    levels: Vec<u8>,
+    next_index: Cell<usize>,
+    default_level: bool,
+}
+
+impl Pin {
+    pub fn new(levels: Vec<u8>) -> Pin {
+        Pin {
+            levels,
+            next_index: Cell::new(0),
+            default_level: true,
+        }
+    }
+
+    /// Replaces the remaining levels, so that the next read returns the first of these.
+    pub fn set_levels(&mut self, levels: Vec<u8>) {
+        self.levels = levels;
+        self.next_index.set(0);
+    }
+
+    /// Sets the level to return once all of the levels have been read.
+    pub fn set_default_level(&mut self, high: bool) {
+        self.default_level = high;
+    }
+
+    fn next_level(&self) -> bool {
+        let index = self.next_index.get();
+        match self.levels.get(index) {
+            Some(&level) => {
+                self.next_index.set(index + 1);
+                level > 0
+            }
+            None => self.default_level,
+        }
+    }
+}
+
+impl InputPin for Pin {
+    type Error = Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.next_level())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.next_level())
+    }
+}
+
+impl OutputPin for Pin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl IoPin<Pin, Pin> for Pin {
+    type Error = Infallible;
+
+    fn into_input_pin(self) -> Result<Pin, Self::Error> {
+        Ok(self)
+    }
+
+    fn into_output_pin(self, _state: PinState) -> Result<Pin, Self::Error> {
+        Ok(self)
+    }
+}
+
+/// A manually-advanced time source, for use as a driver's `time_fn`, `elapsed_since_fn` and
+/// `delay_fn`.
+///
+/// Instants are represented as the duration since the clock was created. Time only moves forward
+/// when `advance` or `delay` is called, so tests can check how long a driver waits without
+/// actually sleeping.
+#[derive(Debug, Default)]
+pub struct Clock {
+    now: Cell<Duration>,
+    delays: RefCell<Vec<Duration>>,
+}
+
+impl Clock {
+    pub fn new() -> Clock {
+        Clock::default()
+    }
+
+    /// The current instant.
+    pub fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    /// How much time has passed since the given instant.
+    pub fn elapsed_since(&self, instant: Duration) -> Duration {
+        self.now.get().saturating_sub(instant)
+    }
+
+    /// Moves the clock forward by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    /// Moves the clock forward by the given duration, and records the delay. The returned future
+    /// is always ready.
+    pub fn delay(&self, duration: Duration) -> Ready<()> {
+        self.delays.borrow_mut().push(duration);
+        self.advance(duration);
+        ready(())
+    }
+
+    /// Every duration passed to `delay`, in order.
+    pub fn delays(&self) -> Vec<Duration> {
+        self.delays.borrow().clone()
+    }
+}
@@ -0,0 +1,138 @@
+use core::time::Duration;
+
+/// A reading, tagged with the time at which it was taken.
+///
+/// `TTime` is whatever representation of an instant the driver's time source provides.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timestamped<T, TTime> {
+    pub value: T,
+    pub at: TTime,
+}
+
+impl<T, TTime> Timestamped<T, TTime> {
+    pub fn new(value: T, at: TTime) -> Self {
+        Timestamped { value, at }
+    }
+
+    /// Converts the value, keeping the same timestamp.
+    pub fn map<U, TMapFn>(self, map_fn: TMapFn) -> Timestamped<U, TTime>
+    where
+        TMapFn: FnOnce(T) -> U,
+    {
+        Timestamped {
+            value: map_fn(self.value),
+            at: self.at,
+        }
+    }
+}
+
+/// Decimates timestamped readings, so that kept readings are at least a minimum spacing apart.
+///
+/// This is useful for loggers that read frequently but only store occasional readings. The
+/// `elapsed_since_fn` should be the same one given to the driver that took the readings, so that
+/// their timestamps can be compared.
+#[derive(Debug)]
+pub struct Decimate<ElapsedFn, TTime> {
+    min_spacing: Duration,
+    elapsed_since_fn: ElapsedFn,
+    last_kept: Option<TTime>,
+}
+
+impl<ElapsedFn, TTime> Decimate<ElapsedFn, TTime>
+where
+    ElapsedFn: Fn(TTime) -> Duration,
+    TTime: Copy,
+{
+    pub fn new(min_spacing: Duration, elapsed_since_fn: ElapsedFn) -> Self {
+        Decimate {
+            min_spacing,
+            elapsed_since_fn,
+            last_kept: None,
+        }
+    }
+
+    /// Whether to keep a reading taken at the given time.
+    ///
+    /// The first reading is always kept. After that, a reading is only kept if it was taken at
+    /// least the minimum spacing after the last kept reading. Readings must be given in the order
+    /// they were taken.
+    pub fn keep(&mut self, at: TTime) -> bool {
+        if let Some(last_kept) = self.last_kept {
+            // Both are measured from now, so their difference is the time between them.
+            let spacing =
+                (self.elapsed_since_fn)(last_kept).saturating_sub((self.elapsed_since_fn)(at));
+            if spacing < self.min_spacing {
+                return false;
+            }
+        }
+        self.last_kept = Some(at);
+        true
+    }
+
+    /// Wraps an iterator of readings, so that it only yields the readings that are kept.
+    pub fn filter<T, TIter>(self, readings: TIter) -> Decimated<TIter, ElapsedFn, TTime>
+    where
+        TIter: Iterator<Item = Timestamped<T, TTime>>,
+    {
+        Decimated {
+            readings,
+            decimate: self,
+        }
+    }
+}
+
+/// An iterator that only yields readings that are kept by a [`Decimate`].
+#[derive(Debug)]
+pub struct Decimated<TIter, ElapsedFn, TTime> {
+    readings: TIter,
+    decimate: Decimate<ElapsedFn, TTime>,
+}
+
+impl<T, TIter, ElapsedFn, TTime> Iterator for Decimated<TIter, ElapsedFn, TTime>
+where
+    TIter: Iterator<Item = Timestamped<T, TTime>>,
+    ElapsedFn: Fn(TTime) -> Duration,
+    TTime: Copy,
+{
+    type Item = Timestamped<T, TTime>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let decimate = &mut self.decimate;
+        self.readings.find(|reading| decimate.keep(reading.at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: Duration = Duration::from_secs(10);
+
+    fn elapsed_since(at: Duration) -> Duration {
+        NOW - at
+    }
+
+    #[test]
+    fn decimate_drops_close_readings() {
+        let readings = [0, 500, 1000, 1200, 2500].map(|ms| {
+            let at = Duration::from_millis(ms);
+            Timestamped::new(ms, at)
+        });
+
+        let kept: [Option<u64>; 4] = {
+            let mut iter = Decimate::new(Duration::from_secs(1), elapsed_since)
+                .filter(readings.iter().copied())
+                .map(|reading| reading.value);
+            [iter.next(), iter.next(), iter.next(), iter.next()]
+        };
+
+        assert_eq!(kept, [Some(0), Some(1000), Some(2500), None]);
+    }
+
+    #[test]
+    fn decimate_keeps_first_reading() {
+        let mut decimate = Decimate::new(Duration::from_secs(60), elapsed_since);
+        assert!(decimate.keep(Duration::from_secs(5)));
+        assert!(!decimate.keep(Duration::from_secs(6)));
+    }
+}
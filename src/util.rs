@@ -0,0 +1,474 @@
+use core::time::Duration;
+
+/// Runs `op` until it succeeds, up to `max_attempts` times, and returns the last result.
+///
+/// Only errors for which `should_retry` returns `true` are retried. Any other error is returned
+/// immediately. `op` is always run at least once, even if `max_attempts` is 0.
+pub fn retry<T, TError, ShouldRetryFn, OpFn>(
+    max_attempts: u8,
+    should_retry: ShouldRetryFn,
+    mut op: OpFn,
+) -> Result<T, TError>
+where
+    ShouldRetryFn: Fn(&TError) -> bool,
+    OpFn: FnMut() -> Result<T, TError>,
+{
+    let mut attempts = 1;
+    loop {
+        match op() {
+            Err(err) if attempts < max_attempts && should_retry(&err) => attempts += 1,
+            result => return result,
+        }
+    }
+}
+
+/// Runs `op` in the same way as [`retry`], but threads `state` through each attempt, e.g. for
+/// operations that consume a pin and hand it back with their result.
+///
+/// `op` is passed the state and the zero-based attempt index. Errors don't hand the state back, so
+/// they're always returned immediately. Instead, a successful result is retried if
+/// `should_retry` returns `true` for it, in which case `into_state` recovers the state for the
+/// next attempt. The last result is returned once `max_attempts` attempts have been made.
+pub fn retry_with<TState, T, TError, ShouldRetryFn, IntoStateFn, OpFn>(
+    max_attempts: u8,
+    state: TState,
+    should_retry: ShouldRetryFn,
+    into_state: IntoStateFn,
+    mut op: OpFn,
+) -> Result<T, TError>
+where
+    ShouldRetryFn: Fn(&T) -> bool,
+    IntoStateFn: Fn(T) -> TState,
+    OpFn: FnMut(TState, u8) -> Result<T, TError>,
+{
+    let mut state = state;
+    let mut attempt = 0;
+    loop {
+        let value = op(state, attempt)?;
+        attempt += 1;
+        if attempt >= max_attempts || !should_retry(&value) {
+            return Ok(value);
+        }
+        state = into_state(value);
+    }
+}
+
+/// Runs the asynchronous `op` in the same way as [`retry`].
+///
+/// The future returned by `op` can't borrow from `op` itself, so any state that it needs mutable
+/// access to (e.g. the sensor being read) is threaded through each attempt: `op` is passed the
+/// state and the zero-based attempt index, and its future resolves to the state along with the
+/// attempt's result.
+pub async fn retry_async<TState, T, TError, ShouldRetryFn, OpFn, OpFuture>(
+    max_attempts: u8,
+    state: TState,
+    should_retry: ShouldRetryFn,
+    mut op: OpFn,
+) -> Result<T, TError>
+where
+    ShouldRetryFn: Fn(&TError) -> bool,
+    OpFn: FnMut(TState, u8) -> OpFuture,
+    OpFuture: core::future::Future<Output = (TState, Result<T, TError>)>,
+{
+    let mut state = state;
+    let mut attempt = 0;
+    loop {
+        let (next_state, result) = op(state, attempt).await;
+        attempt += 1;
+        match result {
+            Err(err) if attempt < max_attempts && should_retry(&err) => state = next_state,
+            result => return result,
+        }
+    }
+}
+
+/// Returned by [`RateLimit::call`] when the minimum interval hasn't passed since the last call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotReady {
+    /// How long until the next call will be allowed.
+    pub remaining: Duration,
+}
+
+/// Limits how often an operation runs, e.g. to cap how often sensors are read in power-sensitive
+/// deployments.
+///
+/// This is a policy on top of any sensor's own minimum read interval, and can wrap reads from any
+/// of the drivers in this crate. Share one `RateLimit` between several sensors to cap their
+/// combined rate.
+///
+/// The provided `time_fn` closure should provide some representation of a given instant that can
+/// be used with `elapsed_since_fn` to determine how much time has passed since then, in the same
+/// way as the drivers' time functions.
+#[derive(Debug)]
+pub struct RateLimit<TimeFn, ElapsedFn, TTime> {
+    min_interval: Duration,
+    time_fn: TimeFn,
+    elapsed_since_fn: ElapsedFn,
+    last_call: Option<TTime>,
+}
+
+impl<TimeFn, ElapsedFn, TTime> RateLimit<TimeFn, ElapsedFn, TTime>
+where
+    TimeFn: Fn() -> TTime,
+    ElapsedFn: Fn(TTime) -> Duration,
+    TTime: Copy,
+{
+    /// Constructs a rate limit that allows one call per `min_interval`. The first call is always
+    /// allowed.
+    pub fn new(min_interval: Duration, time_fn: TimeFn, elapsed_since_fn: ElapsedFn) -> Self {
+        RateLimit {
+            min_interval,
+            time_fn,
+            elapsed_since_fn,
+            last_call: None,
+        }
+    }
+
+    /// How long until the next call will be allowed. This is zero if it's allowed now.
+    pub fn time_until_ready(&self) -> Duration {
+        match self.last_call {
+            // Saturate in case a custom clock isn't monotonic.
+            Some(last_call) => self
+                .min_interval
+                .saturating_sub((self.elapsed_since_fn)(last_call)),
+            None => Duration::from_millis(0),
+        }
+    }
+
+    /// Runs `op` if the minimum interval has passed since the last call, or returns [`NotReady`]
+    /// without running it.
+    pub fn call<T, OpFn>(&mut self, op: OpFn) -> Result<T, NotReady>
+    where
+        OpFn: FnOnce() -> T,
+    {
+        let remaining = self.time_until_ready();
+        if remaining > Duration::from_millis(0) {
+            return Err(NotReady { remaining });
+        }
+        self.last_call = Some((self.time_fn)());
+        Ok(op())
+    }
+
+    /// Runs `op` once the minimum interval has passed since the last call, asynchronously
+    /// sleeping with the provided `delay_fn` if necessary.
+    pub async fn call_when_ready<T, DelayFn, EmptyFuture, OpFn, OpFuture>(
+        &mut self,
+        delay_fn: DelayFn,
+        op: OpFn,
+    ) -> T
+    where
+        DelayFn: Fn(Duration) -> EmptyFuture,
+        EmptyFuture: core::future::Future<Output = ()>,
+        OpFn: FnOnce() -> OpFuture,
+        OpFuture: core::future::Future<Output = T>,
+    {
+        let remaining = self.time_until_ready();
+        if remaining > Duration::from_millis(0) {
+            delay_fn(remaining).await;
+        }
+        self.last_call = Some((self.time_fn)());
+        op().await
+    }
+}
+
+/// Spaces the reads of several sensors evenly across a shared interval, rather than reading them
+/// all at once.
+///
+/// Reading several sensors at the same moment causes spikes in supply current and bursts of
+/// activity that can interfere with each other. With `N` sensors, sensor `i` is first due
+/// `i * interval / N` after the schedule is constructed, and then once per `interval`. If a sensor
+/// is read late, its later reads stay on the same grid, so the spacing is kept.
+///
+/// The schedule only decides which sensor to read next: the caller reads it, since each sensor may
+/// be a different type. The time functions are the same as the drivers' `time_fn` and
+/// `elapsed_since_fn`.
+#[derive(Debug)]
+pub struct StaggeredSchedule<ElapsedFn, TTime, const N: usize> {
+    interval: Duration,
+    elapsed_since_fn: ElapsedFn,
+    start: TTime,
+    /// When each sensor is next due, as a duration since `start`.
+    due: [Duration; N],
+}
+
+impl<ElapsedFn, TTime, const N: usize> StaggeredSchedule<ElapsedFn, TTime, N>
+where
+    ElapsedFn: Fn(TTime) -> Duration,
+    TTime: Copy,
+{
+    /// Constructs a schedule for `N` sensors that are each read once per `interval`.
+    ///
+    /// The interval should be a little longer than the minimum read interval of each sensor, since
+    /// drivers measure that from the end of their last read. Otherwise, the driver may wait again
+    /// before reading.
+    pub fn new<TimeFn>(interval: Duration, time_fn: TimeFn, elapsed_since_fn: ElapsedFn) -> Self
+    where
+        TimeFn: FnOnce() -> TTime,
+    {
+        let mut due = [Duration::from_millis(0); N];
+        for (i, due) in due.iter_mut().enumerate() {
+            *due = interval * i as u32 / N as u32;
+        }
+        StaggeredSchedule {
+            interval,
+            elapsed_since_fn,
+            start: time_fn(),
+            due,
+        }
+    }
+
+    /// How long until the given sensor is due to be read. This is zero if it's already due.
+    ///
+    /// Panics if `index` is not less than `N`.
+    pub fn next_due(&self, index: usize) -> Duration {
+        self.due[index].saturating_sub((self.elapsed_since_fn)(self.start))
+    }
+
+    /// Waits until the next sensor is due, asynchronously sleeping with the provided `delay_fn`,
+    /// and returns its index.
+    ///
+    /// The sensor is then scheduled for its next read, so the caller should read it straight
+    /// away. If several sensors are due, the one that has been due the longest is returned.
+    pub async fn poll_next<DelayFn, EmptyFuture>(&mut self, delay_fn: DelayFn) -> usize
+    where
+        DelayFn: Fn(Duration) -> EmptyFuture,
+        EmptyFuture: core::future::Future<Output = ()>,
+    {
+        let index = (0..N)
+            .min_by_key(|&i| self.due[i])
+            .expect("A schedule must have at least one sensor.");
+        let remaining = self.next_due(index);
+        if remaining > Duration::from_millis(0) {
+            delay_fn(remaining).await;
+        }
+
+        let elapsed = (self.elapsed_since_fn)(self.start);
+        while self.due[index] <= elapsed && self.interval > Duration::from_millis(0) {
+            self.due[index] += self.interval;
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Transient,
+        Fatal,
+    }
+
+    #[test]
+    fn retry_returns_first_success() {
+        let mut attempts = 0;
+        let result = retry(
+            3,
+            |_: &TestError| true,
+            || {
+                attempts += 1;
+                if attempts < 2 {
+                    Err(TestError::Transient)
+                } else {
+                    Ok(attempts)
+                }
+            },
+        );
+        assert_eq!(result, Ok(2));
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn retry_stops_after_max_attempts() {
+        let mut attempts = 0;
+        let result: Result<(), TestError> = retry(
+            3,
+            |_| true,
+            || {
+                attempts += 1;
+                Err(TestError::Transient)
+            },
+        );
+        assert_eq!(result, Err(TestError::Transient));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn retry_respects_should_retry() {
+        let mut attempts = 0;
+        let result: Result<(), TestError> = retry(
+            3,
+            |err| *err == TestError::Transient,
+            || {
+                attempts += 1;
+                Err(TestError::Fatal)
+            },
+        );
+        assert_eq!(result, Err(TestError::Fatal));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn retry_zero_attempts_runs_once() {
+        let mut attempts = 0;
+        let result: Result<(), TestError> = retry(
+            0,
+            |_| true,
+            || {
+                attempts += 1;
+                Err(TestError::Transient)
+            },
+        );
+        assert_eq!(result, Err(TestError::Transient));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn retry_with_threads_state() {
+        let result: Result<(u8, u8), TestError> = retry_with(
+            3,
+            10,
+            |&(_, value)| value < 12,
+            |(state, _)| state + 1,
+            |state, attempt| Ok((state, state + attempt)),
+        );
+        assert_eq!(result, Ok((11, 12)));
+    }
+
+    #[test]
+    fn retry_with_stops_after_max_attempts() {
+        let mut attempts = 0;
+        let result: Result<u8, TestError> = retry_with(
+            3,
+            0,
+            |_| true,
+            |state| state,
+            |state, _| {
+                attempts += 1;
+                Ok(state)
+            },
+        );
+        assert_eq!(result, Ok(0));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn retry_with_returns_errors_immediately() {
+        let mut attempts = 0;
+        let result: Result<u8, TestError> = retry_with(
+            3,
+            0,
+            |_| true,
+            |state| state,
+            |_, _| {
+                attempts += 1;
+                Err(TestError::Transient)
+            },
+        );
+        assert_eq!(result, Err(TestError::Transient));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn retry_async_returns_first_success() {
+        let mut attempts = 0;
+        let result = retry_async(
+            3,
+            &mut attempts,
+            |_: &TestError| true,
+            |attempts, attempt| async move {
+                *attempts += 1;
+                let result = if attempt < 1 {
+                    Err(TestError::Transient)
+                } else {
+                    Ok(attempt)
+                };
+                (attempts, result)
+            },
+        )
+        .await;
+        assert_eq!(result, Ok(1));
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn retry_async_respects_should_retry() {
+        let mut attempts = 0;
+        let result: Result<(), TestError> = retry_async(
+            3,
+            &mut attempts,
+            |err| *err == TestError::Transient,
+            |attempts, _| async move {
+                *attempts += 1;
+                (attempts, Err(TestError::Fatal))
+            },
+        )
+        .await;
+        assert_eq!(result, Err(TestError::Fatal));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn retry_async_stops_after_max_attempts() {
+        let mut attempts = 0;
+        let result: Result<(), TestError> = retry_async(
+            3,
+            &mut attempts,
+            |_| true,
+            |attempts, _| async move {
+                *attempts += 1;
+                (attempts, Err(TestError::Transient))
+            },
+        )
+        .await;
+        assert_eq!(result, Err(TestError::Transient));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn rate_limit_rejects_rapid_calls() {
+        let now = Cell::new(Duration::from_secs(0));
+        let mut limit = RateLimit::new(
+            Duration::from_secs(10),
+            || now.get(),
+            |instant| now.get() - instant,
+        );
+
+        assert_eq!(limit.call(|| 1), Ok(1));
+        now.set(Duration::from_secs(4));
+        assert_eq!(
+            limit.call(|| 2),
+            Err(NotReady {
+                remaining: Duration::from_secs(6)
+            })
+        );
+
+        now.set(Duration::from_secs(10));
+        assert_eq!(limit.call(|| 3), Ok(3));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_waits_when_not_ready() {
+        let now = Cell::new(Duration::from_secs(0));
+        let delays = Cell::new(Duration::from_secs(0));
+        let mut limit = RateLimit::new(
+            Duration::from_secs(10),
+            || now.get(),
+            |instant| now.get() - instant,
+        );
+        let delay_fn = |duration| {
+            delays.set(delays.get() + duration);
+            now.set(now.get() + duration);
+            core::future::ready(())
+        };
+
+        assert_eq!(limit.call_when_ready(delay_fn, || async { 1 }).await, 1);
+        now.set(Duration::from_secs(3));
+        assert_eq!(limit.call_when_ready(delay_fn, || async { 2 }).await, 2);
+
+        assert_eq!(delays.get(), Duration::from_secs(7));
+        assert_eq!(now.get(), Duration::from_secs(10));
+    }
+}
@@ -1,7 +1,15 @@
+use embedded_hal::digital::blocking::IoPin;
+use embedded_hal::digital::PinState;
 use simple_sensors::dhtxx::{self, Response};
+use simple_sensors::shared_pin;
+#[cfg(not(feature = "no-float"))]
+use simple_sensors::temperature::TemperatureSource;
+use std::cell::RefCell;
 use std::time::{Duration, Instant};
 
 mod fake_hal;
+use fake_hal::clock as fake_clock;
+use fake_hal::delay as fake_delay;
 use fake_hal::digital as fake_digital;
 
 macro_rules! test_new_with_invalid_options_fails {
@@ -29,9 +37,11 @@ test_new_with_invalid_options_fails!(
     dht11_invalid_min_interval,
     "dht11-invalid-interval",
     dhtxx::Dht11::new,
-    dhtxx::Options {
-        min_read_interval: dhtxx::MIN_DHT11_READ_INTERVAL - Duration::from_millis(1),
-        max_attempts: 1,
+    {
+        let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+        options.min_read_interval = dhtxx::MIN_DHT11_READ_INTERVAL - Duration::from_millis(1);
+        options.max_attempts = 1;
+        options
     }
 );
 
@@ -39,9 +49,44 @@ test_new_with_invalid_options_fails!(
     dht22_invalid_min_interval,
     "dht22-invalid-interval",
     dhtxx::Dht22::new,
-    dhtxx::Options {
-        min_read_interval: dhtxx::MIN_DHT22_READ_INTERVAL - Duration::from_millis(1),
-        max_attempts: 1,
+    {
+        let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+        options.min_read_interval = dhtxx::MIN_DHT22_READ_INTERVAL - Duration::from_millis(1);
+        options.max_attempts = 1;
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht11_invalid_max_bit_ticks,
+    "dht11-invalid-max-bit-ticks",
+    dhtxx::Dht11::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+        options.max_bit_ticks = 0;
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht11_invalid_start_pulse,
+    "dht11-invalid-start-pulse",
+    dhtxx::Dht11::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+        options.start_pulse = Some(dhtxx::DHT11_START_PULSE - Duration::from_millis(1));
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht22_invalid_start_pulse,
+    "dht22-invalid-start-pulse",
+    dhtxx::Dht22::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+        options.start_pulse = Some(dhtxx::DHT22_START_PULSE - Duration::from_micros(1));
+        options
     }
 );
 
@@ -49,9 +94,11 @@ test_new_with_invalid_options_fails!(
     dht11_invalid_max_attempts,
     "dht11-invalid-attempts",
     dhtxx::Dht11::new,
-    dhtxx::Options {
-        min_read_interval: dhtxx::MIN_DHT11_READ_INTERVAL,
-        max_attempts: 0,
+    {
+        let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+        options.min_read_interval = dhtxx::MIN_DHT11_READ_INTERVAL;
+        options.max_attempts = 0;
+        options
     }
 );
 
@@ -59,9 +106,67 @@ test_new_with_invalid_options_fails!(
     dht22_invalid_max_attempts,
     "dht22-invalid-attempts",
     dhtxx::Dht22::new,
-    dhtxx::Options {
-        min_read_interval: dhtxx::MIN_DHT22_READ_INTERVAL,
-        max_attempts: 0,
+    {
+        let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+        options.min_read_interval = dhtxx::MIN_DHT22_READ_INTERVAL;
+        options.max_attempts = 0;
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht11_excessive_max_attempts,
+    "dht11-excessive-attempts",
+    dhtxx::Dht11::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+        options.max_attempts = dhtxx::MAX_ATTEMPTS + 1;
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht22_excessive_max_attempts,
+    "dht22-excessive-attempts",
+    dhtxx::Dht22::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+        options.max_attempts = 25;
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht11_zero_bit_timeout_multiplier,
+    "dht11-zero-bit-timeout-multiplier",
+    dhtxx::Dht11::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+        options.bit_timeout_multiplier = 0;
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht11_worst_case_duration_overflows,
+    "dht11-worst-case-overflow",
+    dhtxx::Dht11::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+        options.min_read_interval = Duration::MAX / 2;
+        options.max_attempts = 3;
+        options
+    }
+);
+
+test_new_with_invalid_options_fails!(
+    dht22_worst_case_duration_overflows,
+    "dht22-worst-case-overflow",
+    dhtxx::Dht22::new,
+    {
+        let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+        options.min_read_interval = Duration::MAX;
+        options
     }
 );
 
@@ -107,6 +212,161 @@ async fn read_all_zeros_succeeds() -> Result<(), dhtxx::Error<fake_digital::Erro
     Ok(())
 }
 
+#[tokio::test]
+async fn read_all_zeros_with_reject_all_zero_fails() -> Result<(), dhtxx::Error<fake_digital::Error>>
+{
+    let mut pin = fake_digital::Pin::new("all-zeros-rejected");
+    pin.set_data(create_data_vec([0; 40]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.reject_all_zero = true;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::BadData::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_after_losing_pin_fails_cleanly() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("lost-pin");
+    pin.set_fail_mode_changes(true);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.max_attempts = 1;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::Wrapped(fake_digital::Error::ModeChangeFailed)
+    );
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::InvalidState::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_pin_error_mid_frame_returns_to_output_mode(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("pin-error-mid-frame");
+    // Fail at the start of the 11th bit, after the ACK and 10 zero bits.
+    let fail_index = 6 + 10 * 4;
+    let mut data = create_data_vec([0; 40]);
+    data.truncate(fail_index + 1);
+    data.extend(create_data_vec([0; 40]));
+    pin.set_data(data);
+    pin.set_fail_read_at(fail_index);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::Wrapped(fake_digital::Error::ReadFailed)
+    );
+
+    // The pin should be back in output mode, so the next read can proceed.
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+    assert_eq!(
+        result,
+        dhtxx::Dht11Response {
+            humidity: 0,
+            humidity_decimal: 0,
+            temperature: 0,
+            temperature_decimal: 0
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_clock_with_matching_time_source_succeeds(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let pin = fake_digital::Pin::new("verify-clock");
+    let clock = fake_clock::Clock::new();
+    let sensor = dhtxx::Dht22::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    sensor
+        .verify_clock(|duration| clock.delay(duration))
+        .await?;
+
+    assert_eq!(clock.get_delays(), [dhtxx::CLOCK_CHECK_DURATION]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_clock_with_half_speed_time_source_fails(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let pin = fake_digital::Pin::new("verify-clock-half-speed");
+    let clock = fake_clock::Clock::new();
+    let sensor = dhtxx::Dht22::new(
+        pin,
+        || clock.now() / 2,
+        |instant| clock.now() / 2 - instant,
+        None,
+    )?;
+
+    let result = sensor.verify_clock(|duration| clock.delay(duration)).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::ClockMismatch::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[test]
+fn reset_timer_restarts_read_interval() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let pin = fake_digital::Pin::new("reset-timer");
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht22::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+    clock.advance(dhtxx::MIN_DHT22_READ_INTERVAL);
+    assert_eq!(sensor.time_until_ready(), Duration::from_millis(0));
+
+    sensor.reset_timer();
+
+    assert_eq!(sensor.time_until_ready(), dhtxx::MIN_DHT22_READ_INTERVAL);
+    Ok(())
+}
+
 #[tokio::test]
 async fn dht11_read_with_valid_data() -> Result<(), dhtxx::Error<fake_digital::Error>> {
     let mut fake_pin = fake_digital::Pin::new("dht11-valid-data");
@@ -139,6 +399,171 @@ async fn dht11_read_with_valid_data() -> Result<(), dhtxx::Error<fake_digital::E
     Ok(())
 }
 
+#[tokio::test]
+async fn dht11_consecutive_reads_with_different_data(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("dht11-consecutive-reads");
+    let mut data = create_data_vec([
+        0, 0, 0, 1, 0, 0, 0, 1, /*0x11*/
+        0, 0, 0, 0, 0, 1, 0, 0, /*0x04*/
+        0, 0, 0, 0, 1, 1, 1, 1, /*0x0F*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 1, 0, 0, 1, 0, 0, /*0x24*/
+    ]);
+    data.extend(create_data_vec([
+        0, 0, 1, 0, 1, 0, 0, 0, /*0x28*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 1, 0, 1, 1, 0, /*0x16*/
+        0, 0, 0, 0, 0, 0, 1, 0, /*0x02*/
+        0, 1, 0, 0, 0, 0, 0, 0, /*0x40*/
+    ]));
+    fake_pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        fake_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let first = sensor.read(|duration| clock.delay(duration)).await?;
+    let second = sensor.read(|duration| clock.delay(duration)).await?;
+    assert_eq!(
+        first,
+        dhtxx::Dht11Response {
+            humidity: 0x11,
+            humidity_decimal: 0x04,
+            temperature: 0x0F,
+            temperature_decimal: 0
+        }
+    );
+    assert_eq!(
+        second,
+        dhtxx::Dht11Response {
+            humidity: 0x28,
+            humidity_decimal: 0,
+            temperature: 0x16,
+            temperature_decimal: 0x02
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn dht11_request_then_receive_matches_read() -> Result<(), dhtxx::Error<fake_digital::Error>>
+{
+    let data = create_data_vec([
+        0, 0, 0, 1, 0, 0, 0, 1, /*0x11*/
+        0, 0, 0, 0, 0, 1, 0, 0, /*0x04*/
+        0, 0, 0, 0, 1, 1, 1, 1, /*0x0F*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 1, 0, 0, 1, 0, 0, /*0x24*/
+    ]);
+    let mut read_pin = fake_digital::Pin::new("dht11-two-phase-read");
+    read_pin.set_data(data.clone());
+    let mut two_phase_pin = fake_digital::Pin::new("dht11-two-phase");
+    two_phase_pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut read_sensor = dhtxx::Dht11::new(
+        read_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+    let mut two_phase_sensor = dhtxx::Dht11::new(
+        two_phase_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let read_result = read_sensor.read(|duration| clock.delay(duration)).await?;
+    let read_delays = clock.get_delays();
+    two_phase_sensor
+        .request(|duration| clock.delay(duration))
+        .await?;
+    let two_phase_result = two_phase_sensor.receive()?;
+
+    assert_eq!(two_phase_result, read_result);
+    assert_eq!(
+        two_phase_sensor
+            .last_reading()
+            .map(|(response, _)| response),
+        Some(read_result)
+    );
+    // The second sensor was already ready, so it only waits for the start pulse.
+    assert_eq!(
+        clock.get_delays()[read_delays.len()..],
+        [dhtxx::DHT11_START_PULSE]
+    );
+    Ok(())
+}
+
+fn create_inverted_data_vec() -> Vec<u8> {
+    create_data_vec([
+        0, 0, 0, 1, 0, 0, 0, 1, /*0x11*/
+        0, 0, 0, 0, 0, 1, 0, 0, /*0x04*/
+        0, 0, 0, 0, 1, 1, 1, 1, /*0x0F*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 1, 0, 0, 1, 0, 0, /*0x24*/
+    ])
+    .iter()
+    .map(|level| 1 - level)
+    .collect()
+}
+
+#[tokio::test]
+async fn dht11_read_inverted_line() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("dht11-inverted-line");
+    pin.set_data(create_inverted_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.inverted_line = true;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(
+        result,
+        dhtxx::Dht11Response {
+            humidity: 0x11,
+            humidity_decimal: 0x04,
+            temperature: 0x0F,
+            temperature_decimal: 0
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn dht11_read_inverted_line_without_option_fails(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("dht11-inverted-line-no-option");
+    pin.set_data(create_inverted_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::NoResponse::<fake_digital::Error>
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn dht22_read_with_valid_data() -> Result<(), dhtxx::Error<fake_digital::Error>> {
     let mut fake_pin = fake_digital::Pin::new("dht22-valid-data");
@@ -169,6 +594,30 @@ async fn dht22_read_with_valid_data() -> Result<(), dhtxx::Error<fake_digital::E
     Ok(())
 }
 
+#[cfg(not(feature = "no-float"))]
+#[tokio::test]
+async fn dht22_temperature_source_reads_celsius() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("dht22-temperature-source");
+    fake_pin.set_data(create_data_vec([
+        0, 0, 0, 0, 0, 0, 1, 0, /*0x02*/
+        1, 0, 0, 0, 0, 0, 0, 0, /*0x80*/
+        0, 0, 0, 0, 0, 0, 0, 1, /*0x01*/
+        0, 0, 0, 0, 0, 1, 0, 0, /*0x04*/
+        1, 0, 0, 0, 0, 1, 1, 1, /*0x87*/
+    ]));
+    let clock = fake_clock::Clock::new();
+    let sensor = dhtxx::Dht22::new(
+        fake_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+    let mut source = dhtxx::TemperatureReader::new(sensor, |duration| clock.delay(duration));
+
+    assert_eq!(source.read_temperature_celsius().await?, 26.0);
+    Ok(())
+}
+
 macro_rules! test_read_bad_data_fails {
     ($name:ident, $pin_name: expr, $new_dht_fn:expr, $data:expr) => {
         #[tokio::test]
@@ -269,35 +718,530 @@ test_read_bad_data_fails!(
 );
 
 #[tokio::test]
-async fn read_with_negative_temperature_dht22_succeeds(
-) -> Result<(), dhtxx::Error<fake_digital::Error>> {
-    let mut fake_pin = fake_digital::Pin::new("negative-temperature-dht22");
-    fake_pin.set_data(create_data_vec([
-        0, 0, 0, 0, 0, 0, 0, 1, /* Byte 0 = 0x01 */
-        0, 0, 0, 0, 0, 0, 0, 0, /* Byte 1 = 0x00 */
-        1, 0, 0, 0, 0, 0, 0, 1, /* Byte 2 = 0x81 */
-        0, 0, 0, 0, 0, 0, 0, 1, /* Byte 3 = 0x01 */
-        1, 0, 0, 0, 0, 0, 1, 1, /* Parity = 0x93 */
+async fn read_with_start_pulse_override() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("start-pulse-override");
+    pin.set_data(create_data_vec([
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
     ]));
+    let clock = fake_clock::Clock::new();
     let mut sensor = dhtxx::Dht22::new(
-        fake_pin,
-        || Instant::now(),
-        |instant| instant.elapsed(),
-        None,
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+            options.start_pulse = Some(Duration::from_millis(5));
+            options
+        }),
     )?;
 
-    let result = sensor
-        .read(|duration| tokio::time::sleep(duration.into()))
-        .await?;
-    assert_eq!(result.get_temperature(), -25.7f32);
+    sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(
+        clock.get_delays(),
+        [dhtxx::MIN_DHT22_READ_INTERVAL, Duration::from_millis(5)]
+    );
     Ok(())
 }
 
 #[tokio::test]
-async fn read_with_imperfect_timing_succeeds() -> Result<(), dhtxx::Error<fake_digital::Error>> {
-    let mut fake_pin = fake_digital::Pin::new("imperfect-timing");
-    fake_pin.set_data(vec![
-        /* ACK */
+async fn first_read_waits_for_power_on_delay() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("power-on-delay");
+    let mut data = create_data_vec([0; 40]);
+    data.append(&mut create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.power_on_delay = Duration::from_secs(5);
+            options
+        }),
+    )?;
+
+    sensor.read(|duration| clock.delay(duration)).await?;
+    sensor.read(|duration| clock.delay(duration)).await?;
+
+    // Only the first read waits for the sensor to settle.
+    assert_eq!(
+        clock.get_delays(),
+        [
+            Duration::from_secs(5),
+            dhtxx::DHT11_START_PULSE,
+            dhtxx::MIN_DHT11_READ_INTERVAL,
+            dhtxx::DHT11_START_PULSE
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn blocking_delay_fn_splits_long_delays() {
+    let delay = RefCell::new(fake_delay::Delay::new());
+    let blocking_delay_fn = dhtxx::blocking_delay_fn(&delay);
+
+    blocking_delay_fn(Duration::from_micros(2 * u32::MAX as u64 + 5)).await;
+
+    assert_eq!(delay.borrow().get_delays_us(), [u32::MAX, u32::MAX, 5]);
+}
+
+#[tokio::test]
+async fn read_with_blocking_delay_fn() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("blocking-delay-fn");
+    pin.set_data(create_data_vec([0; 40]));
+    let clock = fake_clock::Clock::new();
+    let delay = RefCell::new(fake_delay::Delay::new());
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+    let blocking_delay_fn = dhtxx::blocking_delay_fn(&delay);
+
+    // Advance the clock as well, so that it isn't detected as stalled.
+    sensor
+        .read(|duration| {
+            clock.advance(duration);
+            blocking_delay_fn(duration)
+        })
+        .await?;
+
+    assert_eq!(
+        delay.borrow().get_delays_us(),
+        [
+            dhtxx::MIN_DHT11_READ_INTERVAL.as_micros() as u32,
+            dhtxx::DHT11_START_PULSE.as_micros() as u32
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_non_monotonic_clock_succeeds() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("non-monotonic-clock");
+    let mut data = create_data_vec([0; 40]);
+    data.append(&mut create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    // Each call reports much more time has elapsed than the last, regardless of the instant.
+    let num_elapsed_calls = std::cell::Cell::new(0u32);
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |_| {
+            num_elapsed_calls.set(num_elapsed_calls.get() + 1);
+            Duration::from_millis(999) * num_elapsed_calls.get()
+        },
+        None,
+    )?;
+
+    sensor.read(|duration| clock.delay(duration)).await?;
+    sensor.read(|duration| clock.delay(duration)).await?;
+
+    // Only the first read is within the minimum read interval.
+    assert_eq!(
+        clock.get_delays(),
+        [
+            Duration::from_millis(1),
+            dhtxx::DHT11_START_PULSE,
+            dhtxx::DHT11_START_PULSE
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn bus_constructs_sensors_with_shared_time_source(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut dht11_pin = fake_digital::Pin::new("bus-dht11");
+    dht11_pin.set_data(create_data_vec([0; 40]));
+    let mut dht22_pin = fake_digital::Pin::new("bus-dht22");
+    dht22_pin.set_data(create_data_vec([0; 40]));
+    let clock = fake_clock::Clock::new();
+    let bus = dhtxx::DhtBus::new(|| clock.now(), |instant| clock.elapsed_since(instant));
+    let mut dht11 = bus.dht11(dht11_pin, None)?;
+    let mut dht22 = bus.dht22(dht22_pin, None)?;
+
+    dht11.read(|duration| clock.delay(duration)).await?;
+    dht22.read(|duration| clock.delay(duration)).await?;
+
+    // Both sensors measure their read interval from the shared clock.
+    assert_eq!(
+        clock.get_delays(),
+        [
+            dhtxx::MIN_DHT11_READ_INTERVAL,
+            dhtxx::DHT11_START_PULSE,
+            dhtxx::MIN_DHT22_READ_INTERVAL
+                - dhtxx::MIN_DHT11_READ_INTERVAL
+                - dhtxx::DHT11_START_PULSE,
+            dhtxx::DHT22_START_PULSE
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn last_reading_updates_after_successful_read(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("last-reading");
+    pin.set_data(create_data_vec([
+        0, 0, 1, 1, 0, 1, 1, 1, /*0x37*/
+        0, 0, 0, 0, 0, 0, 1, 0, /*0x02*/
+        0, 0, 0, 1, 1, 0, 0, 0, /*0x18*/
+        0, 0, 0, 0, 0, 1, 1, 0, /*0x06*/
+        0, 1, 0, 1, 0, 1, 1, 1, /*0x57*/
+    ]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    assert_eq!(sensor.last_reading(), None);
+
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(sensor.last_reading(), Some((result, clock.now())));
+    Ok(())
+}
+
+#[tokio::test]
+async fn last_reading_not_updated_after_failed_read(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("last-reading-failed");
+    pin.set_default_data(true);
+    let mut sensor = dhtxx::Dht11::new(pin, || Instant::now(), |instant| instant.elapsed(), None)?;
+
+    let result = sensor
+        .read(|duration| tokio::time::sleep(duration.into()))
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(sensor.last_reading(), None);
+    Ok(())
+}
+
+#[test]
+fn worst_case_read_duration_covers_all_attempts() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let sensor = dhtxx::Dht22::new(
+        fake_digital::Pin::new("worst-case-read-duration"),
+        || Instant::now(),
+        |instant| instant.elapsed(),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+            options.min_read_interval = Duration::from_secs(2);
+            options.max_attempts = 3;
+            options
+        }),
+    )?;
+
+    assert_eq!(
+        sensor.worst_case_read_duration(),
+        (Duration::from_secs(2) + dhtxx::DHT22_START_PULSE + dhtxx::MAX_BLOCKING_READ_DURATION) * 3
+    );
+    Ok(())
+}
+
+#[test]
+fn worst_case_read_duration_covers_max_blocking_receive(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let sensor = dhtxx::Dht11::new(
+        fake_digital::Pin::new("worst-case-blocking-receive"),
+        || Instant::now(),
+        |instant| instant.elapsed(),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.max_attempts = 2;
+            options
+        }),
+    )?;
+
+    assert!(dhtxx::TYPICAL_BLOCKING_RECEIVE < dhtxx::MAX_BLOCKING_RECEIVE);
+    assert!(dhtxx::MAX_BLOCKING_RECEIVE <= dhtxx::MAX_BLOCKING_READ_DURATION);
+    let non_blocking = (dhtxx::MIN_DHT11_READ_INTERVAL + dhtxx::DHT11_START_PULSE) * 2;
+    assert!(sensor.worst_case_read_duration() - non_blocking >= dhtxx::MAX_BLOCKING_RECEIVE * 2);
+    Ok(())
+}
+
+#[test]
+fn effective_min_read_interval_defaults_to_sensor_minimum(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let dht11 = dhtxx::Dht11::new(
+        fake_digital::Pin::new("effective-interval-dht11"),
+        || Instant::now(),
+        |instant| instant.elapsed(),
+        None,
+    )?;
+    let dht22 = dhtxx::Dht22::new(
+        fake_digital::Pin::new("effective-interval-dht22"),
+        || Instant::now(),
+        |instant| instant.elapsed(),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+            options.min_read_interval = Duration::from_secs(3);
+            options
+        }),
+    )?;
+
+    assert_eq!(
+        dht11.effective_min_read_interval(),
+        dhtxx::MIN_DHT11_READ_INTERVAL
+    );
+    assert_eq!(dht22.effective_min_read_interval(), Duration::from_secs(3));
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_negative_temperature_dht22_succeeds(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("negative-temperature-dht22");
+    fake_pin.set_data(create_data_vec([
+        0, 0, 0, 0, 0, 0, 0, 1, /* Byte 0 = 0x01 */
+        0, 0, 0, 0, 0, 0, 0, 0, /* Byte 1 = 0x00 */
+        1, 0, 0, 0, 0, 0, 0, 1, /* Byte 2 = 0x81 */
+        0, 0, 0, 0, 0, 0, 0, 1, /* Byte 3 = 0x01 */
+        1, 0, 0, 0, 0, 0, 1, 1, /* Parity = 0x93 */
+    ]));
+    let mut sensor = dhtxx::Dht22::new(
+        fake_pin,
+        || Instant::now(),
+        |instant| instant.elapsed(),
+        None,
+    )?;
+
+    let result = sensor
+        .read(|duration| tokio::time::sleep(duration.into()))
+        .await?;
+    assert_eq!(result.temperature_tenths(), -257);
+    #[cfg(not(feature = "no-float"))]
+    assert_eq!(result.get_temperature(), -25.7f32);
+    Ok(())
+}
+
+/// Creates the line data for a response that sends the given frame, as from `to_frame`.
+fn create_frame_data_vec(frame: [u8; 5]) -> Vec<u8> {
+    let mut bits = [0; 40];
+    for (i, bit) in bits.iter_mut().enumerate() {
+        *bit = (frame[i / 8] >> (7 - i % 8)) & 1;
+    }
+    create_data_vec(bits)
+}
+
+#[cfg(not(feature = "no-float"))]
+#[tokio::test]
+async fn read_partial_with_out_of_range_humidity_keeps_temperature(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("read-partial-bad-humidity");
+    // 110.0% humidity, and 25.0°C.
+    pin.set_data(create_frame_data_vec([0x04, 0x4C, 0x00, 0xFA, 0x4A]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht22::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor
+        .read_partial(|duration| clock.delay(duration))
+        .await?;
+
+    assert_eq!(
+        result,
+        dhtxx::ValidatedReading {
+            temperature: Ok(25.0),
+            humidity: Err(()),
+        }
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "no-float"))]
+#[tokio::test]
+async fn read_partial_with_bad_parity_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("read-partial-bad-parity");
+    pin.set_data(create_frame_data_vec([0x04, 0x4C, 0x00, 0xFA, 0x4B]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht22::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read_partial(|duration| clock.delay(duration)).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::BadData::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+macro_rules! test_dht22_frame_round_trip {
+    ($name:ident, $response:expr) => {
+        #[tokio::test]
+        async fn $name() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+            let response = $response;
+            let mut fake_pin = fake_digital::Pin::new(stringify!($name));
+            fake_pin.set_data(create_frame_data_vec(response.to_frame()));
+            let clock = fake_clock::Clock::new();
+            let mut sensor = dhtxx::Dht22::new(
+                fake_pin,
+                || clock.now(),
+                |instant| clock.elapsed_since(instant),
+                None,
+            )?;
+
+            let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+            assert_eq!(result, response);
+            Ok(())
+        }
+    };
+}
+
+test_dht22_frame_round_trip!(
+    dht22_frame_round_trip,
+    dhtxx::Dht22Response {
+        humidity_x10: 652,
+        temperature_x10: 351,
+    }
+);
+test_dht22_frame_round_trip!(
+    dht22_negative_frame_round_trip,
+    dhtxx::Dht22Response {
+        humidity_x10: 256,
+        temperature_x10: 0x8000 | 257,
+    }
+);
+
+fn create_out_of_range_dht22_data_vec() -> Vec<u8> {
+    // 160°C is well above the DHT22's range, but the parity is correct.
+    create_frame_data_vec(
+        dhtxx::Dht22Response {
+            humidity_x10: 100,
+            temperature_x10: 1600,
+        }
+        .to_frame(),
+    )
+}
+
+#[tokio::test]
+async fn read_out_of_range_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("out-of-range");
+    fake_pin.set_data(create_out_of_range_dht22_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht22::new(
+        fake_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(result.unwrap_err(), dhtxx::Error::BadData);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_out_of_range_without_enforce_range_succeeds(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("out-of-range-not-enforced");
+    fake_pin.set_data(create_out_of_range_dht22_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht22::new(
+        fake_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT22_OPTIONS;
+            options.enforce_range = false;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(result.temperature_tenths(), 1600);
+    Ok(())
+}
+
+#[test]
+fn dht11_to_frame_includes_parity() {
+    let response = dhtxx::Dht11Response {
+        humidity: 0x11,
+        humidity_decimal: 0x04,
+        temperature: 0x0F,
+        temperature_decimal: 0x03,
+    };
+
+    assert_eq!(response.to_frame(), [0x11, 0x04, 0x0F, 0x03, 0x27]);
+}
+
+#[tokio::test]
+async fn dht11_read_deci() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("dht11-read-deci");
+    fake_pin.set_data(create_data_vec([
+        0, 0, 0, 1, 0, 0, 0, 1, /*0x11*/
+        0, 0, 0, 0, 0, 1, 0, 0, /*0x04*/
+        0, 0, 0, 0, 1, 1, 1, 1, /*0x0F*/
+        0, 0, 0, 0, 0, 0, 1, 1, /*0x03*/
+        0, 0, 1, 0, 0, 1, 1, 1, /*0x27*/
+    ]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        fake_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read_deci(|duration| clock.delay(duration)).await?;
+    assert_eq!(result, (153, 174));
+    Ok(())
+}
+
+#[tokio::test]
+async fn dht22_read_deci_with_negative_temperature() -> Result<(), dhtxx::Error<fake_digital::Error>>
+{
+    let mut fake_pin = fake_digital::Pin::new("dht22-read-deci-negative");
+    fake_pin.set_data(create_data_vec([
+        0, 0, 0, 0, 0, 0, 0, 1, /* Byte 0 = 0x01 */
+        0, 0, 0, 0, 0, 0, 0, 0, /* Byte 1 = 0x00 */
+        1, 0, 0, 0, 0, 0, 0, 1, /* Byte 2 = 0x81 */
+        0, 0, 0, 0, 0, 0, 0, 1, /* Byte 3 = 0x01 */
+        1, 0, 0, 0, 0, 0, 1, 1, /* Parity = 0x93 */
+    ]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht22::new(
+        fake_pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read_deci(|duration| clock.delay(duration)).await?;
+    assert_eq!(result, (-257, 256));
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_imperfect_timing_succeeds() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("imperfect-timing");
+    fake_pin.set_data(vec![
+        /* ACK */
         1, 1, 0, 0, 1, 1, /* Byte 0 = 0x11 */
         0, 0, 1, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1,
         0, 0, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, /* Byte 1 = 0x00 */
@@ -346,38 +1290,405 @@ async fn read_with_bit_timeout_fails() -> Result<(), dhtxx::Error<fake_digital::
         1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
     ]);
     let mut sensor = dhtxx::Dht11::new(
-        fake_pin,
-        || Instant::now(),
-        |instant| instant.elapsed(),
+        fake_pin,
+        || Instant::now(),
+        |instant| instant.elapsed(),
+        None,
+    )?;
+
+    let result = sensor
+        .read(|duration| tokio::time::sleep(duration.into()))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::BadData::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+/// Creates data for a sensor that starts its ACK, but never releases the line.
+///
+/// Use this with a time source that advances by 1ms each time the elapsed time is checked, so that
+/// the line is held low for longer than the ACK timeout.
+fn create_unfinished_ack_data_vec() -> Vec<u8> {
+    let mut data = vec![1, 1];
+    data.extend(vec![0; 4000]);
+    data
+}
+
+#[tokio::test]
+async fn read_with_no_response_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("no_response");
+    // The line drops, but the sensor never finishes the ACK.
+    fake_pin.set_data(create_unfinished_ack_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        fake_pin,
+        || clock.now(),
+        |instant| {
+            clock.advance(Duration::from_millis(1));
+            clock.elapsed_since(instant)
+        },
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::NoResponse::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_line_always_high_is_disconnected(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut fake_pin = fake_digital::Pin::new("disconnected");
+    fake_pin.set_default_data(true);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        fake_pin,
+        || Instant::now(),
+        |instant| instant.elapsed(),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.max_attempts = 2;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(result.unwrap_err(), dhtxx::Error::Disconnected);
+    // The read isn't retried, since the line isn't connected to anything.
+    assert_eq!(sensor.stats().no_response, 1);
+    Ok(())
+}
+
+/// Creates data for an all-zero response whose end pulse is long enough to read as a 1.
+fn create_long_end_data_vec() -> Vec<u8> {
+    let mut data = create_data_vec([0; 40]);
+    let end_start = data.len() - 4;
+    data.splice(end_start..end_start, [0; 6]);
+    data
+}
+
+fn create_short_ack_high_data_vec() -> Vec<u8> {
+    // The ACK's final high is only a single sample, so the full ACK is short relative to the bits.
+    let mut data = vec![1, 1, 0, 0, 0, 0, 1];
+    // 0x01, 0x00, 0x00, 0x00, 0x01
+    for i in 0..40 {
+        data.extend_from_slice(&[0; 8]);
+        if i == 7 || i == 39 {
+            data.extend_from_slice(&[1; 12]);
+        } else {
+            data.extend_from_slice(&[1; 4]);
+        }
+    }
+    // Add END
+    data.extend_from_slice(&[0, 0, 1, 1]);
+    data
+}
+
+#[tokio::test]
+async fn read_with_short_ack_high_succeeds() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("short-ack-high");
+    pin.set_data(create_short_ack_high_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(
+        result,
+        dhtxx::Dht11Response {
+            humidity: 1,
+            humidity_decimal: 0,
+            temperature: 0,
+            temperature_decimal: 0
+        }
+    );
+    Ok(())
+}
+
+/// Creates a response whose humidity and parity bytes each end with a bit of medium length, which
+/// decodes as a 1 with a low threshold and as a 0 with a high threshold. Either way, the parity is
+/// valid.
+fn create_ambiguous_bit_data_vec() -> Vec<u8> {
+    // Start with ACK
+    let mut data = vec![1, 1, 0, 0, 1, 1];
+    // 0x32 (50), 0x00, 0x1E (30), 0x00, 0x50 (80), with the last bit of 50 and 80 ambiguous.
+    for byte in [0x32u8, 0x00, 0x1E, 0x00, 0x50].iter() {
+        for bit in (0..8).rev() {
+            data.extend_from_slice(&[0; 2]);
+            if bit == 0 && (*byte == 0x32 || *byte == 0x50) {
+                data.extend_from_slice(&[1; 4]);
+            } else if (byte >> bit) & 1 == 1 {
+                data.extend_from_slice(&[1; 8]);
+            } else {
+                data.extend_from_slice(&[1; 1]);
+            }
+        }
+    }
+    // Add END
+    data.extend_from_slice(&[0, 0, 1, 1]);
+    data
+}
+
+#[tokio::test]
+async fn read_with_threshold_overrides_detected_threshold(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("read-with-threshold");
+    pin.set_data(
+        [
+            create_ambiguous_bit_data_vec(),
+            create_ambiguous_bit_data_vec(),
+        ]
+        .concat(),
+    );
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let low = sensor
+        .read_with_threshold(|duration| clock.delay(duration), 2)
+        .await?;
+    let high = sensor
+        .read_with_threshold(|duration| clock.delay(duration), 6)
+        .await?;
+
+    assert_eq!(low, dhtxx::Dht11Response::from_bytes([51, 0, 30, 0]));
+    assert_eq!(high, dhtxx::Dht11Response::from_bytes([50, 0, 30, 0]));
+    Ok(())
+}
+
+fn create_slow_bit_data_vec() -> Vec<u8> {
+    // Start with ACK
+    let mut data = vec![1, 1, 0, 0, 1, 1];
+    // 0x01, 0x00, 0x00, 0x00, 0x01
+    for i in 0..40 {
+        data.extend_from_slice(&[0; 8]);
+        if i == 7 || i == 39 {
+            data.extend_from_slice(&[1; 8]);
+        } else {
+            data.extend_from_slice(&[1; 2]);
+        }
+    }
+    // Add END
+    data.extend_from_slice(&[0, 0, 1, 1]);
+    data
+}
+
+#[tokio::test]
+async fn read_slow_bits_with_default_multiplier_fails(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("slow-bits-default-multiplier");
+    pin.set_data(create_slow_bit_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::BadData::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_slow_bits_with_larger_multiplier_succeeds(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("slow-bits-larger-multiplier");
+    pin.set_data(create_slow_bit_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.bit_timeout_multiplier = 5;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(
+        result,
+        dhtxx::Dht11Response {
+            humidity: 1,
+            humidity_decimal: 0,
+            temperature: 0,
+            temperature_decimal: 0
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_long_ack_is_capped_by_max_bit_ticks(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("long-ack-max-bit-ticks");
+    // An abnormally long ACK, followed by a line that's stuck low. Reading past the end of this
+    // data panics, so the read must give up on the first bit within 60 samples.
+    let mut data = vec![1, 1, 0, 0];
+    data.extend_from_slice(&[1; 1000]);
+    data.extend_from_slice(&[0; 60]);
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.max_bit_ticks = 50;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::BadData::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_long_end_pulse_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("long-end-pulse");
+    pin.set_data(create_long_end_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        dhtxx::Error::BadData::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_long_end_pulse_succeeds_if_not_required(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("long-end-pulse-not-required");
+    pin.set_data(create_long_end_data_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.require_end_pulse = false;
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(result, dhtxx::Dht11Response::from_bytes([0; 4]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_unvalidated_returns_rejected_bytes() -> Result<(), dhtxx::Error<fake_digital::Error>>
+{
+    // 200% humidity is out of range, and the parity byte doesn't match.
+    let data = create_data_vec([
+        1, 1, 0, 0, 1, 0, 0, 0, /*0xC8*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 1, /*0x01*/
+    ]);
+    let mut pin = fake_digital::Pin::new("read-unvalidated");
+    pin.set_data([data.clone(), data].concat());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let unvalidated = sensor
+        .read_unvalidated(|duration| clock.delay(duration))
+        .await?;
+    let validated = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(unvalidated, ([0xC8, 0, 0, 0], 0x01));
+    assert!(!dhtxx::Dht11Response::from_bytes(unvalidated.0).is_valid());
+    assert_eq!(
+        validated.unwrap_err(),
+        dhtxx::Error::BadData::<fake_digital::Error>
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_line_low_before_ack_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("line-low-before-ack");
+    // Skip the leading high part of the ACK.
+    pin.set_data(create_data_vec([0; 40])[2..].to_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
         None,
     )?;
 
-    let result = sensor
-        .read(|duration| tokio::time::sleep(duration.into()))
-        .await;
-    assert!(result.is_err());
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
     assert_eq!(
         result.unwrap_err(),
-        dhtxx::Error::BadData::<fake_digital::Error>
+        dhtxx::Error::NoResponse::<fake_digital::Error>
     );
     Ok(())
 }
 
 #[tokio::test]
-async fn read_with_no_response_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
-    let mut fake_pin = fake_digital::Pin::new("no_response");
-    fake_pin.set_default_data(true);
+async fn read_with_line_stuck_low_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("line-stuck-low");
+    pin.set_default_data(false);
+    let clock = fake_clock::Clock::new();
     let mut sensor = dhtxx::Dht11::new(
-        fake_pin,
-        || Instant::now(),
-        |instant| instant.elapsed(),
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
         None,
     )?;
 
-    let result = sensor
-        .read(|duration| tokio::time::sleep(duration.into()))
-        .await;
-    assert!(result.is_err());
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
     assert_eq!(
         result.unwrap_err(),
         dhtxx::Error::NoResponse::<fake_digital::Error>
@@ -386,7 +1697,7 @@ async fn read_with_no_response_fails() -> Result<(), dhtxx::Error<fake_digital::
 }
 
 macro_rules! test_retry_success {
-    ($name:ident, $pin_name: expr, $new_dht_fn:expr) => {
+    ($name:ident, $pin_name: expr, $new_dht_fn:expr, $ping_duration:expr) => {
         #[tokio::test]
         async fn $name() -> Result<(), dhtxx::Error<fake_digital::Error>> {
             let mut pin = fake_digital::Pin::new($pin_name);
@@ -407,30 +1718,137 @@ macro_rules! test_retry_success {
                 0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
             ]));
             pin.set_data(data);
+            let clock = fake_clock::Clock::new();
+            let min_read_interval = std::cmp::max(
+                dhtxx::MIN_DHT11_READ_INTERVAL,
+                dhtxx::MIN_DHT22_READ_INTERVAL,
+            );
             let mut sensor = $new_dht_fn(
                 pin,
-                || Instant::now(),
-                |instant| instant.elapsed(),
-                Some(dhtxx::Options {
-                    min_read_interval: std::cmp::max(
-                        dhtxx::MIN_DHT11_READ_INTERVAL,
-                        dhtxx::MIN_DHT22_READ_INTERVAL,
-                    ),
-                    max_attempts: 2,
+                || clock.now(),
+                |instant| clock.elapsed_since(instant),
+                Some({
+                    let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+                    options.min_read_interval = min_read_interval;
+                    options.max_attempts = 2;
+                    options
                 }),
             )?;
 
-            let result = sensor.read(tokio::time::sleep).await?;
+            let result = sensor.read(|duration| clock.delay(duration)).await?;
 
+            assert_eq!(result.humidity_tenths(), 0);
+            assert_eq!(result.temperature_tenths(), 0);
+            #[cfg(not(feature = "no-float"))]
             assert_eq!(result.get_humidity(), 0.0);
+            #[cfg(not(feature = "no-float"))]
             assert_eq!(result.get_temperature(), 0.0);
+            // Each attempt waits the full interval, since the clock only advances during delays.
+            assert_eq!(
+                clock.get_delays(),
+                [
+                    min_read_interval,
+                    $ping_duration,
+                    min_read_interval,
+                    $ping_duration
+                ]
+            );
             Ok(())
         }
     };
 }
 
-test_retry_success!(dh11_retry_success, "dht11-retry-success", dhtxx::Dht11::new);
-test_retry_success!(dh22_retry_success, "dht22-retry-success", dhtxx::Dht22::new);
+test_retry_success!(
+    dh11_retry_success,
+    "dht11-retry-success",
+    dhtxx::Dht11::new,
+    Duration::from_millis(18)
+);
+test_retry_success!(
+    dh22_retry_success,
+    "dht22-retry-success",
+    dhtxx::Dht22::new,
+    Duration::from_millis(1)
+);
+
+#[tokio::test]
+async fn read_with_observer_sees_each_attempt() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("observer");
+    // Bad parity
+    let mut data = create_data_vec([
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 1, /*0x01*/
+    ]);
+    data.extend(create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.max_attempts = 3;
+            options
+        }),
+    )?;
+
+    let mut attempts = Vec::new();
+    let result = sensor
+        .read_with_observer(
+            |duration| clock.delay(duration),
+            |attempt, result| {
+                attempts.push((
+                    attempt,
+                    matches!(result, Err(dhtxx::Error::BadData)),
+                    result.is_ok(),
+                ))
+            },
+        )
+        .await?;
+
+    assert_eq!(result, dhtxx::Dht11Response::from_bytes([0; 4]));
+    assert_eq!(attempts, [(0, true, false), (1, false, true)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn last_read_retried_after_bad_data() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("last-read-retried");
+    // A clean read, then bad parity followed by a clean read.
+    let mut data = create_data_vec([0; 40]);
+    data.extend(create_data_vec([
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 0, 0, 0, 0, 1, /*0x01*/
+    ]));
+    data.extend(create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.max_attempts = 3;
+            options
+        }),
+    )?;
+    assert!(!sensor.last_read_retried());
+
+    sensor.read(|duration| clock.delay(duration)).await?;
+    assert!(!sensor.last_read_retried());
+
+    sensor.read(|duration| clock.delay(duration)).await?;
+    assert!(sensor.last_read_retried());
+    Ok(())
+}
 
 macro_rules! test_retry_bad_data {
     ($name:ident, $pin_name: expr, $new_dht_fn:expr) => {
@@ -454,20 +1872,23 @@ macro_rules! test_retry_bad_data {
                 0, 0, 0, 0, 0, 0, 0, 1, /*0x00*/
             ]));
             pin.set_data(data);
+            let clock = fake_clock::Clock::new();
             let mut sensor = $new_dht_fn(
                 pin,
-                || Instant::now(),
-                |instant| instant.elapsed(),
-                Some(dhtxx::Options {
-                    min_read_interval: std::cmp::max(
+                || clock.now(),
+                |instant| clock.elapsed_since(instant),
+                Some({
+                    let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+                    options.min_read_interval = std::cmp::max(
                         dhtxx::MIN_DHT11_READ_INTERVAL,
                         dhtxx::MIN_DHT22_READ_INTERVAL,
-                    ),
-                    max_attempts: 2,
+                    );
+                    options.max_attempts = 2;
+                    options
                 }),
             )?;
 
-            let result = sensor.read(tokio::time::sleep).await;
+            let result = sensor.read(|duration| clock.delay(duration)).await;
             assert!(result.is_err());
             assert_eq!(
                 result.unwrap_err(),
@@ -494,21 +1915,28 @@ macro_rules! test_retry_fail_on_no_response {
         #[tokio::test]
         async fn $name() -> Result<(), dhtxx::Error<fake_digital::Error>> {
             let mut pin = fake_digital::Pin::new($pin_name);
-            pin.set_default_data(true);
+            // The line drops, but the sensor never finishes the ACK.
+            pin.set_data(create_unfinished_ack_data_vec());
+            let clock = fake_clock::Clock::new();
             let mut sensor = $new_dht_fn(
                 pin,
-                || Instant::now(),
-                |instant| instant.elapsed(),
-                Some(dhtxx::Options {
-                    min_read_interval: std::cmp::max(
+                || clock.now(),
+                |instant| {
+                    clock.advance(Duration::from_millis(1));
+                    clock.elapsed_since(instant)
+                },
+                Some({
+                    let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+                    options.min_read_interval = std::cmp::max(
                         dhtxx::MIN_DHT11_READ_INTERVAL,
                         dhtxx::MIN_DHT22_READ_INTERVAL,
-                    ),
-                    max_attempts: 2,
+                    );
+                    options.max_attempts = 2;
+                    options
                 }),
             )?;
 
-            let result = sensor.read(tokio::time::sleep).await;
+            let result = sensor.read(|duration| clock.delay(duration)).await;
             assert!(result.is_err());
             assert_eq!(
                 result.unwrap_err(),
@@ -529,3 +1957,533 @@ test_retry_fail_on_no_response!(
     "dht22-retry-fail-on-no-response",
     dhtxx::Dht22::new
 );
+
+#[tokio::test]
+async fn shared_pin_drives_dht11() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("shared-dht11");
+    pin.set_data(create_data_vec([
+        0, 0, 1, 1, 0, 1, 1, 0, /*0x36*/
+        0, 0, 0, 0, 0, 0, 0, 0, /*0x00*/
+        0, 0, 0, 1, 0, 1, 1, 1, /*0x17*/
+        0, 0, 0, 0, 0, 0, 1, 0, /*0x02*/
+        0, 1, 0, 0, 1, 1, 1, 1, /*0x4F*/
+    ]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        shared_pin::SharedIoPin::new(pin),
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(
+        result,
+        dhtxx::Dht11Response {
+            humidity: 0x36,
+            humidity_decimal: 0,
+            temperature: 0x17,
+            temperature_decimal: 2,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn shared_pin_releases_line_in_input_mode() -> Result<(), fake_digital::Error> {
+    let pin = shared_pin::SharedIoPin::new(fake_digital::Pin::new("shared-release"));
+
+    let pin = pin.into_output_pin(PinState::Low)?;
+    assert_eq!(
+        pin.into_input_pin()?.into_inner().get_output_high(),
+        Some(true)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_timestamped_advances() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("timestamped");
+    let mut data = create_data_vec([0; 40]);
+    data.extend(create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let first = sensor
+        .read_timestamped(|duration| clock.delay(duration))
+        .await?;
+    let second = sensor
+        .read_timestamped(|duration| clock.delay(duration))
+        .await?;
+
+    assert_eq!(first.value, second.value);
+    assert!(second.at >= first.at + dhtxx::MIN_DHT11_READ_INTERVAL);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_busy_line_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("busy-line");
+    pin.set_data(vec![1, 1, 0, 1, 0, 1]);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.quiet_period = Some(Duration::from_millis(1));
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(result.unwrap_err(), dhtxx::Error::LineBusy);
+    Ok(())
+}
+
+#[tokio::test]
+async fn busy_line_does_not_reset_read_interval() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("busy-line-interval");
+    pin.set_data(vec![0, 0]);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.quiet_period = Some(Duration::from_millis(1));
+            options
+        }),
+    )?;
+
+    let first = sensor.read(|duration| clock.delay(duration)).await;
+    let second = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(first.unwrap_err(), dhtxx::Error::LineBusy);
+    assert_eq!(second.unwrap_err(), dhtxx::Error::LineBusy);
+    // Only the first attempt waits, since the quiet-period check sends no request.
+    assert_eq!(clock.get_delays().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn quiet_period_with_frozen_clock_finishes() -> Result<(), dhtxx::Error<fake_digital::Error>>
+{
+    let mut pin = fake_digital::Pin::new("quiet-frozen-clock");
+    let mut data = vec![1; 11];
+    data.append(&mut create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || Duration::from_millis(0),
+        |_| Duration::from_millis(0),
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.quiet_period = Some(Duration::from_millis(1));
+            options
+        }),
+    )?;
+
+    let first = sensor.read(|duration| clock.delay(duration)).await;
+    let second = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(first.unwrap_err(), dhtxx::Error::StalledClock);
+    assert!(second.is_ok());
+    // The quiet period is sampled across ten waits, after the minimum read interval.
+    assert_eq!(clock.get_delays()[2..12], [Duration::from_micros(100); 10]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_with_stalled_clock_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("stalled-clock");
+    pin.set_data(create_data_vec([0; 40]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || Duration::from_millis(0),
+        |_| Duration::from_millis(0),
+        None,
+    )?;
+
+    let first = sensor.read(|duration| clock.delay(duration)).await;
+    let second = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(first.unwrap_err(), dhtxx::Error::StalledClock);
+    // The error is only returned once, so later reads still work, if slowly.
+    assert!(second.is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn short_first_wait_skips_stalled_clock_check(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("short-first-wait");
+    let mut data = create_data_vec([0; 40]);
+    data.append(&mut create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+    clock.advance(dhtxx::MIN_DHT11_READ_INTERVAL - Duration::from_micros(500));
+
+    // The delay doesn't advance the clock, as if it were too short for a millisecond clock.
+    let first = sensor.read(|_| core::future::ready(())).await;
+    let second = sensor.read(|_| core::future::ready(())).await;
+
+    assert!(first.is_ok());
+    // The check is deferred to the next wait that's long enough.
+    assert_eq!(second.unwrap_err(), dhtxx::Error::StalledClock);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_past_receive_deadline_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("receive-deadline");
+    // Only the ACK, the first byte, and the start of the next bit are available, so reading any
+    // further would panic.
+    pin.set_data(create_data_vec([0; 40])[..6 + 8 * 4 + 1].to_vec());
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        // Each time check takes a millisecond, as if the line were very slow.
+        |instant| {
+            clock.advance(Duration::from_millis(1));
+            clock.elapsed_since(instant)
+        },
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            options.receive_deadline = Some(Duration::from_millis(1));
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(result.unwrap_err(), dhtxx::Error::Deadline);
+    // The line is released after the abort.
+    sensor.ensure_idle()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_past_receive_deadline_in_last_byte_fails(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("receive-deadline-last-byte");
+    pin.set_data(create_data_vec([0; 40]));
+    let clock = fake_clock::Clock::new();
+    clock.advance(dhtxx::MIN_DHT11_READ_INTERVAL);
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        // Each time check takes a millisecond, as if the line were very slow.
+        |instant| {
+            clock.advance(Duration::from_millis(1));
+            clock.elapsed_since(instant)
+        },
+        Some({
+            let mut options = dhtxx::DEFAULT_DHT11_OPTIONS;
+            // The checks after the ACK and the first four bytes pass, so only the check after the
+            // last byte fails.
+            options.receive_deadline = Some(Duration::from_millis(5));
+            options
+        }),
+    )?;
+
+    let result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(result.unwrap_err(), dhtxx::Error::Deadline);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stats_count_read_outcomes() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("stats");
+    // The line is low before the first request, so the sensor can't have responded.
+    let mut data = vec![0];
+    data.extend(create_data_vec([0; 40]));
+    let mut bad_parity = [0; 40];
+    bad_parity[39] = 1;
+    data.extend(create_data_vec(bad_parity));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let no_response_result = sensor.read(|duration| clock.delay(duration)).await;
+    sensor.read(|duration| clock.delay(duration)).await?;
+    let bad_data_result = sensor.read(|duration| clock.delay(duration)).await;
+
+    assert_eq!(no_response_result.unwrap_err(), dhtxx::Error::NoResponse);
+    assert_eq!(bad_data_result.unwrap_err(), dhtxx::Error::BadData);
+    assert_eq!(
+        sensor.stats(),
+        dhtxx::ReadStats {
+            successes: 1,
+            bad_data: 1,
+            no_response: 1,
+        }
+    );
+    sensor.reset_stats();
+    assert_eq!(sensor.stats(), dhtxx::ReadStats::default());
+    Ok(())
+}
+
+#[cfg(not(feature = "no-float"))]
+#[tokio::test]
+async fn probe_line_returns_fraction_high() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("probe-line");
+    pin.set_data([[1; 9].to_vec(), vec![0]].concat().repeat(2));
+    let clock = fake_clock::Clock::new();
+    let sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let fraction = sensor
+        .probe_line(20, |duration| clock.delay(duration))
+        .await?;
+
+    assert!((fraction - 0.9).abs() < 0.001);
+    // The samples are spaced out rather than taken back-to-back.
+    assert_eq!(clock.get_delays(), [Duration::from_millis(1); 19]);
+    Ok(())
+}
+
+#[cfg(not(feature = "no-float"))]
+#[tokio::test]
+async fn probe_line_zero_samples_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let pin = fake_digital::Pin::new("probe-line-zero-samples");
+    let clock = fake_clock::Clock::new();
+    let sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    assert_eq!(
+        sensor
+            .probe_line(0, |duration| clock.delay(duration))
+            .await
+            .unwrap_err(),
+        dhtxx::Error::InvalidArgument
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn recover_after_abandoned_request() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("recover");
+    pin.set_data(create_data_vec([0; 40]));
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    // Leave the line low, as if the caller was cancelled before calling `receive`.
+    sensor.request(|duration| clock.delay(duration)).await?;
+    sensor.recover()?;
+    let result = sensor.read(|duration| clock.delay(duration)).await?;
+
+    assert_eq!(result, dhtxx::Dht11Response::from_bytes([0; 4]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn recover_after_losing_pin_fails() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("recover-lost-pin");
+    pin.set_fail_mode_changes(true);
+    let clock = fake_clock::Clock::new();
+    let mut sensor = dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?;
+
+    let _ = sensor.read(|duration| clock.delay(duration)).await;
+    let result = sensor.recover();
+
+    assert_eq!(result.unwrap_err(), dhtxx::Error::InvalidState);
+    Ok(())
+}
+
+struct AsyncLock<T>(tokio::sync::Mutex<T>);
+
+impl<T> dhtxx::Lock for AsyncLock<T> {
+    type Target = T;
+    type Guard<'a>
+        = tokio::sync::MutexGuard<'a, T>
+    where
+        T: 'a;
+
+    async fn lock(&self) -> Self::Guard<'_> {
+        self.0.lock().await
+    }
+}
+
+#[tokio::test]
+async fn read_shared_enforces_interval_across_tasks(
+) -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("shared-lock");
+    let mut data = create_data_vec([0; 40]);
+    data.extend(create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let sensor = AsyncLock(tokio::sync::Mutex::new(dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?));
+
+    // Yield during each delay, so the other task tries to read while the lock is held.
+    let clock = &clock;
+    let delay_fn = |duration| async move {
+        clock.delay(duration).await;
+        tokio::task::yield_now().await
+    };
+    let (first, second) = tokio::join!(
+        dhtxx::Dht11::read_shared(&sensor, delay_fn),
+        dhtxx::Dht11::read_shared(&sensor, delay_fn),
+    );
+
+    assert_eq!(first?, second?);
+    // Each task waited for the minimum read interval before sending its start pulse.
+    assert_eq!(
+        clock.get_delays(),
+        [
+            dhtxx::MIN_DHT11_READ_INTERVAL,
+            dhtxx::DHT11_START_PULSE,
+            dhtxx::MIN_DHT11_READ_INTERVAL,
+            dhtxx::DHT11_START_PULSE,
+        ]
+    );
+    Ok(())
+}
+
+struct BlockingLock<T>(std::sync::Mutex<T>);
+
+impl<T> dhtxx::Lock for BlockingLock<T> {
+    type Target = T;
+    type Guard<'a>
+        = std::sync::MutexGuard<'a, T>
+    where
+        T: 'a;
+
+    async fn lock(&self) -> Self::Guard<'_> {
+        self.0.lock().unwrap()
+    }
+}
+
+#[tokio::test]
+async fn read_shared_with_blocking_mutex() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let mut pin = fake_digital::Pin::new("shared-blocking-lock");
+    let mut data = create_data_vec([0; 40]);
+    data.extend(create_data_vec([0; 40]));
+    pin.set_data(data);
+    let clock = fake_clock::Clock::new();
+    let sensor = BlockingLock(std::sync::Mutex::new(dhtxx::Dht11::new(
+        pin,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+        None,
+    )?));
+
+    // The delay completes without yielding, like one from `blocking_delay_fn`, so the guard is
+    // never held while the other task runs.
+    let (first, second) = tokio::join!(
+        dhtxx::Dht11::read_shared(&sensor, |duration| clock.delay(duration)),
+        dhtxx::Dht11::read_shared(&sensor, |duration| clock.delay(duration)),
+    );
+
+    assert_eq!(first?, second?);
+    assert_eq!(
+        clock.get_delays(),
+        [
+            dhtxx::MIN_DHT11_READ_INTERVAL,
+            dhtxx::DHT11_START_PULSE,
+            dhtxx::MIN_DHT11_READ_INTERVAL,
+            dhtxx::DHT11_START_PULSE,
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn staggered_schedule_alternates_sensors() -> Result<(), dhtxx::Error<fake_digital::Error>> {
+    let clock = fake_clock::Clock::new();
+    let mut pins = [
+        fake_digital::Pin::new("staggered-0"),
+        fake_digital::Pin::new("staggered-1"),
+    ];
+    for pin in pins.iter_mut() {
+        let mut data = create_data_vec([0; 40]);
+        data.extend(create_data_vec([0; 40]));
+        pin.set_data(data);
+    }
+    let mut sensors = pins.map(|pin| {
+        dhtxx::Dht11::new(
+            pin,
+            || clock.now(),
+            |instant| clock.elapsed_since(instant),
+            None,
+        )
+        .unwrap()
+    });
+    // Let both sensors finish their initial interval.
+    clock.advance(dhtxx::MIN_DHT11_READ_INTERVAL);
+    let mut schedule = simple_sensors::util::StaggeredSchedule::<_, _, 2>::new(
+        dhtxx::MIN_DHT11_READ_INTERVAL,
+        || clock.now(),
+        |instant| clock.elapsed_since(instant),
+    );
+    assert_eq!(schedule.next_due(1), Duration::from_millis(500));
+
+    let mut reads = Vec::new();
+    for _ in 0..4 {
+        let index = schedule.poll_next(|duration| clock.delay(duration)).await;
+        // The start pulse also advances the clock, so record when the read began.
+        reads.push((index, clock.now()));
+        sensors[index]
+            .read(|duration| clock.delay(duration))
+            .await?;
+    }
+
+    assert_eq!(
+        reads,
+        [
+            (0, Duration::from_millis(1000)),
+            (1, Duration::from_millis(1500)),
+            (0, Duration::from_millis(2000)),
+            (1, Duration::from_millis(2500)),
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,1507 @@
+use simple_sensors::ds18b20;
+#[cfg(not(feature = "no-float"))]
+use simple_sensors::temperature::TemperatureSource;
+use std::time::Duration;
+
+mod fake_hal;
+use fake_hal::clock as fake_clock;
+use fake_hal::delay as fake_delay;
+use fake_hal::digital as fake_digital;
+
+type Error = ds18b20::Error<
+    fake_delay::Error,
+    fake_digital::Error,
+    fake_digital::Error,
+    fake_digital::Error,
+>;
+
+/// A presence pulse, as sampled twice by the reset sequence, then the line released at the end of
+/// the reset.
+const PRESENCE: [u8; 3] = [0, 0, 1];
+
+/// The line data for detecting each power mode: a presence pulse, then the power supply bit.
+const EXTERNAL_POWER: [u8; 4] = [0, 0, 1, 1];
+const PARASITIC_POWER: [u8; 4] = [0, 0, 1, 0];
+
+/// Creates the line data for a full convert-and-read sequence returning the given scratchpad.
+fn create_read_data(scratchpad: [u8; 9]) -> Vec<u8> {
+    // Reset before the conversion, then reset before reading the scratchpad.
+    let mut data = Vec::new();
+    data.extend_from_slice(&PRESENCE);
+    data.extend_from_slice(&PRESENCE);
+    for byte in scratchpad.iter() {
+        for bit in 0..8 {
+            data.push((byte >> bit) & 1);
+        }
+    }
+    data
+}
+
+// 0 degrees, with the factory default alarm thresholds, in 12-bit mode.
+const VALID_SCRATCHPAD: [u8; 9] = [0x00, 0x00, 0x4B, 0x46, 0x7F, 0xFF, 0x0C, 0x10, 0xC8];
+// All zeros, with the wrong CRC.
+const BAD_CRC_SCRATCHPAD: [u8; 9] = [0, 0, 0, 0, 0, 0, 0, 0, 0x01];
+// 85 degrees, with alarm thresholds of 75 and -10 degrees, in 11-bit mode.
+const FULL_SCRATCHPAD: [u8; 9] = [0x50, 0x05, 0x4B, 0xF6, 0x5F, 0xFF, 0x0C, 0x10, 0x23];
+// The same as above, but with a corrupted reserved byte.
+const BAD_RESERVED_SCRATCHPAD: [u8; 9] = [0x50, 0x05, 0x4B, 0xF6, 0x5F, 0xFE, 0x0C, 0x10, 0x88];
+
+#[test]
+fn read_temperature_bad_crc_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-bad-crc");
+    pin.set_data(create_read_data(BAD_CRC_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[cfg(not(feature = "no-float"))]
+#[tokio::test]
+async fn driver_temperature_source_reads_celsius() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-temperature-source");
+    pin.set_data([&EXTERNAL_POWER[..], &create_read_data(FULL_SCRATCHPAD)].concat());
+    let mut sensor = ds18b20::Ds18b20::new(pin, fake_delay::Delay::new());
+
+    assert_eq!(sensor.read_temperature_celsius().await?, 85.0);
+    Ok(())
+}
+
+#[test]
+fn driver_read_after_bad_data_succeeds() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-driver-bad-data");
+    pin.set_data(
+        [
+            &EXTERNAL_POWER[..],
+            &create_read_data(BAD_CRC_SCRATCHPAD),
+            &create_read_data(FULL_SCRATCHPAD),
+        ]
+        .concat(),
+    );
+    let mut sensor = ds18b20::Ds18b20::new(pin, fake_delay::Delay::new());
+
+    assert_eq!(sensor.read_temperature().err(), Some(Error::BadData));
+    assert_eq!(sensor.read_temperature()?.integer_part(), 85);
+    Ok(())
+}
+
+#[test]
+fn driver_recover_after_losing_pin() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-driver-lost-pin");
+    pin.set_data([&EXTERNAL_POWER[..], &[1, 1, 1]].concat());
+    let mut sensor = ds18b20::Ds18b20::new(pin, fake_delay::Delay::new());
+
+    assert_eq!(sensor.read_temperature().err(), Some(Error::NoSensorsFound));
+    assert_eq!(sensor.read_temperature().err(), Some(Error::InvalidState));
+
+    let mut pin = fake_digital::Pin::new("ds18b20-driver-recovered-pin");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    sensor.recover(pin);
+
+    assert_eq!(sensor.read_temperature()?.integer_part(), 85);
+    Ok(())
+}
+
+#[test]
+fn driver_parasitic_power_uses_strong_pullup() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-driver-parasitic");
+    let mut data = [&PARASITIC_POWER[..], &create_read_data(FULL_SCRATCHPAD)].concat();
+    // The power mode is only detected once.
+    data.extend(create_read_data(FULL_SCRATCHPAD));
+    pin.set_data(data);
+    let mut pullup_states = Vec::new();
+    let mut sensor =
+        ds18b20::Ds18b20::with_strong_pullup(pin, fake_delay::Delay::new(), |enabled| {
+            pullup_states.push(enabled)
+        });
+    assert_eq!(sensor.power_mode(), None);
+
+    assert_eq!(sensor.read_temperature()?.integer_part(), 85);
+    assert_eq!(sensor.read_temperature()?.integer_part(), 85);
+
+    assert_eq!(sensor.power_mode(), Some(ds18b20::PowerMode::Parasitic));
+    drop(sensor);
+    assert_eq!(pullup_states, [true, false, true, false]);
+    Ok(())
+}
+
+#[test]
+fn driver_external_power_skips_strong_pullup() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-driver-external");
+    pin.set_data([&EXTERNAL_POWER[..], &create_read_data(FULL_SCRATCHPAD)].concat());
+    let mut pullup_states = Vec::new();
+    let mut sensor =
+        ds18b20::Ds18b20::with_strong_pullup(pin, fake_delay::Delay::new(), |enabled| {
+            pullup_states.push(enabled)
+        });
+
+    assert_eq!(sensor.read_temperature()?.integer_part(), 85);
+
+    assert_eq!(sensor.power_mode(), Some(ds18b20::PowerMode::External));
+    drop(sensor);
+    assert!(pullup_states.is_empty());
+    Ok(())
+}
+
+#[test]
+fn read_temperature_retry_succeeds_on_second_attempt() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-retry-success");
+    let mut data = create_read_data(BAD_CRC_SCRATCHPAD);
+    data.append(&mut create_read_data(VALID_SCRATCHPAD));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_retry(pin, &mut delay, 2)?;
+
+    assert_eq!(result.data.integer_part(), 0);
+    assert_eq!(result.data.decimal_part(), 0);
+    assert_eq!(
+        delay
+            .get_delays_us()
+            .iter()
+            .filter(|&&us| us == 10_000)
+            .count(),
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn read_temperature_retry_bad_data() {
+    let mut pin = fake_digital::Pin::new("ds18b20-retry-bad-data");
+    let mut data = create_read_data(BAD_CRC_SCRATCHPAD);
+    data.append(&mut create_read_data(BAD_CRC_SCRATCHPAD));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_retry(pin, &mut delay, 2);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn read_temperature_retry_fails_on_no_sensors() {
+    let mut pin = fake_digital::Pin::new("ds18b20-retry-no-sensors");
+    pin.set_default_data(true);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_retry(pin, &mut delay, 3);
+
+    assert_eq!(result.err(), Some(Error::NoSensorsFound));
+}
+
+#[test]
+fn read_temperature_fails_on_line_held_low() {
+    let mut pin = fake_digital::Pin::new("ds18b20-line-held-low");
+    pin.set_default_data(false);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::LineHeldLow));
+}
+
+#[test]
+fn bus_reset_fails_on_line_held_low() {
+    let mut pin = fake_digital::Pin::new("ds18b20-bus-reset-held-low");
+    pin.set_data(vec![0, 0, 0]);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::bus_reset(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::LineHeldLow));
+}
+
+#[test]
+fn read_temperature_retry_zero_attempts_fails() {
+    let pin = fake_digital::Pin::new("ds18b20-retry-zero-attempts");
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_retry(pin, &mut delay, 0);
+
+    assert_eq!(result.err(), Some(Error::InvalidArgument));
+}
+
+#[test]
+fn bus_reset_detects_presence() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-bus-reset-presence");
+    pin.set_data(vec![1, 0, 1]);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::bus_reset(pin, &mut delay)?;
+
+    assert!(result.data);
+    Ok(())
+}
+
+#[test]
+fn bus_reset_detects_no_presence() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-bus-reset-no-presence");
+    pin.set_default_data(true);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::bus_reset(pin, &mut delay)?;
+
+    assert!(!result.data);
+    // The full reset sequence should still be timed out.
+    assert_eq!(delay.get_delays_us().iter().sum::<u32>(), 960);
+    Ok(())
+}
+
+/// Creates the line data for a ROM search that finds the given devices.
+///
+/// The devices must be listed in the order they will be discovered: the search takes the 0 branch
+/// first, so IDs are found in ascending order when their bits are read least-significant first.
+fn create_search_data(roms: &[[u8; 8]]) -> Vec<u8> {
+    let get_bit = |rom: &[u8; 8], position: usize| (rom[position / 8] >> (position % 8)) & 1;
+
+    let mut data = Vec::new();
+    for target in roms.iter() {
+        data.extend_from_slice(&PRESENCE);
+        for position in 0..64 {
+            // Only devices that matched every bit so far are still participating.
+            let participating = roms
+                .iter()
+                .filter(|rom| (0..position).all(|p| get_bit(rom, p) == get_bit(target, p)));
+            let mut id_bit = 1;
+            let mut complement_bit = 1;
+            for rom in participating {
+                id_bit &= get_bit(rom, position);
+                complement_bit &= 1 - get_bit(rom, position);
+            }
+            data.push(id_bit);
+            data.push(complement_bit);
+        }
+    }
+    data
+}
+
+const ROM_A: [u8; 8] = [0x28, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10];
+const ROM_B: [u8; 8] = [0x28, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20];
+const ROM_C: [u8; 8] = [0x28, 0x01, 0x00, 0x00, 0x00, 0x00, 0x80, 0x30];
+
+#[test]
+fn search_devices_finds_all_devices() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-search-all");
+    pin.set_data(create_search_data(&[ROM_A, ROM_B, ROM_C]));
+    let mut delay = fake_delay::Delay::new();
+    let mut devices = [ds18b20::DeviceId::default(); 4];
+
+    let result = ds18b20::search_devices(pin, &mut delay, &mut devices)?;
+
+    assert_eq!(result.data, 3);
+    assert_eq!(
+        devices[..3],
+        [
+            ds18b20::DeviceId::from_bytes(ROM_A),
+            ds18b20::DeviceId::from_bytes(ROM_B),
+            ds18b20::DeviceId::from_bytes(ROM_C),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn search_devices_finds_single_device() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-search-single");
+    pin.set_data(create_search_data(&[ROM_B]));
+    let mut delay = fake_delay::Delay::new();
+    let mut devices = [ds18b20::DeviceId::default(); 1];
+
+    let result = ds18b20::search_devices(pin, &mut delay, &mut devices)?;
+
+    assert_eq!(result.data, 1);
+    assert_eq!(devices[0].to_bytes(), ROM_B);
+    Ok(())
+}
+
+#[test]
+fn search_devices_too_many_devices_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-search-too-many");
+    pin.set_data(create_search_data(&[ROM_A, ROM_B, ROM_C]));
+    let mut delay = fake_delay::Delay::new();
+    let mut devices = [ds18b20::DeviceId::default(); 2];
+
+    let result = ds18b20::search_devices(pin, &mut delay, &mut devices);
+
+    assert_eq!(result.err(), Some(Error::TooManyDevices));
+}
+
+#[test]
+fn search_devices_no_sensors_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-search-no-sensors");
+    pin.set_default_data(true);
+    let mut delay = fake_delay::Delay::new();
+    let mut devices = [ds18b20::DeviceId::default(); 2];
+
+    let result = ds18b20::search_devices(pin, &mut delay, &mut devices);
+
+    assert_eq!(result.err(), Some(Error::NoSensorsFound));
+}
+
+#[test]
+fn read_temperature_with_strong_pullup_enables_pullup_for_conversion() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-strong-pullup");
+    pin.set_data(create_read_data(VALID_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+    let mut pullup_states = Vec::new();
+
+    ds18b20::read_temperature_with_strong_pullup(pin, &mut delay, &mut |enabled| {
+        pullup_states.push(enabled)
+    })?;
+
+    assert_eq!(pullup_states, [true, false]);
+    assert!(delay.get_delays_us().contains(&750_000));
+    Ok(())
+}
+
+#[test]
+fn read_conversion_result_waits_if_not_ready() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-conversion-not-ready");
+    pin.set_data(create_read_data(VALID_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+    let clock = fake_clock::Clock::new();
+
+    let result =
+        ds18b20::start_conversion(pin, &mut delay, ds18b20::ResolutionMode::TwelveBit, || {
+            clock.now()
+        })?;
+    let conversion = result.data;
+    clock.advance(Duration::from_millis(100));
+
+    assert!(!conversion.is_ready(|instant| clock.elapsed_since(instant)));
+    assert_eq!(
+        conversion.remaining(|instant| clock.elapsed_since(instant)),
+        Duration::from_millis(650)
+    );
+
+    let result = ds18b20::read_conversion_result(conversion, result.pin, &mut delay, |instant| {
+        clock.elapsed_since(instant)
+    })?;
+
+    assert_eq!(result.data.integer_part(), 0);
+    assert!(delay.get_delays_us().contains(&650_000));
+    Ok(())
+}
+
+#[test]
+fn conversion_ready_after_conversion_time() {
+    let clock = fake_clock::Clock::new();
+    let start = clock.now();
+    let elapsed_since = |instant| clock.elapsed_since(instant);
+
+    clock.advance(Duration::from_millis(187));
+    assert!(!ds18b20::conversion_ready(
+        start,
+        ds18b20::ResolutionMode::TenBit,
+        elapsed_since
+    ));
+    assert_eq!(
+        ds18b20::conversion_remaining(start, ds18b20::ResolutionMode::TenBit, elapsed_since),
+        Duration::from_micros(500)
+    );
+
+    clock.advance(Duration::from_millis(1));
+    assert!(ds18b20::conversion_ready(
+        start,
+        ds18b20::ResolutionMode::TenBit,
+        elapsed_since
+    ));
+    assert_eq!(
+        ds18b20::conversion_remaining(start, ds18b20::ResolutionMode::TenBit, elapsed_since),
+        Duration::from_millis(0)
+    );
+}
+
+#[test]
+fn read_conversion_result_after_conversion_time() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-conversion-ready");
+    pin.set_data(create_read_data(VALID_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+    let clock = fake_clock::Clock::new();
+
+    let result =
+        ds18b20::start_conversion(pin, &mut delay, ds18b20::ResolutionMode::NineBit, || {
+            clock.now()
+        })?;
+    let conversion = result.data;
+    clock.advance(Duration::from_millis(100));
+
+    assert!(conversion.is_ready(|instant| clock.elapsed_since(instant)));
+
+    let result = ds18b20::read_conversion_result(conversion, result.pin, &mut delay, |instant| {
+        clock.elapsed_since(instant)
+    })?;
+
+    assert_eq!(result.data.integer_part(), 0);
+    // Only the line timing delays are used, with no wait for the conversion.
+    assert!(delay.get_delays_us().iter().all(|&us| us <= 480));
+    Ok(())
+}
+
+/// Creates the line data for reading the given ROM code.
+fn create_rom_data(rom: [u8; 8]) -> Vec<u8> {
+    let mut data = PRESENCE.to_vec();
+    for byte in rom.iter() {
+        for bit in 0..8 {
+            data.push((byte >> bit) & 1);
+        }
+    }
+    data
+}
+
+#[test]
+fn read_rom_returns_device_id() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-rom");
+    pin.set_data(create_rom_data(ROM_C));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_rom(pin, &mut delay)?;
+
+    assert_eq!(result.data, ds18b20::DeviceId::from_bytes(ROM_C));
+    Ok(())
+}
+
+#[test]
+fn read_family_code_returns_first_rom_byte() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-family-code");
+    pin.set_data(create_rom_data(ROM_A));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_family_code(pin, &mut delay)?;
+
+    assert_eq!(result.data, 0x28);
+    Ok(())
+}
+
+#[test]
+fn read_auto_decodes_ds18b20() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-auto-ds18b20");
+    let mut data = create_rom_data(ROM_A);
+    data.extend(create_read_data(FULL_SCRATCHPAD));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_auto(pin, &mut delay)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    assert_eq!(result.data.decimal_part(), 0);
+    Ok(())
+}
+
+#[test]
+fn read_auto_decodes_ds18s20() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-auto-ds18s20");
+    let mut data = create_rom_data([0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40]);
+    // 25 degrees in 1/2 degree increments, with 10 of 16 counts remaining, which extends the
+    // reading to 25 - 0.25 + 6/16 = 25.125 degrees.
+    let mut scratchpad = [0x32, 0x00, 0x4B, 0x46, 0xFF, 0xFF, 0x0A, 0x10, 0x00];
+    scratchpad[8] = compute_crc(&scratchpad[..8]);
+    data.extend(create_read_data(scratchpad));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_auto(pin, &mut delay)?;
+
+    assert_eq!(result.data.integer_part(), 25);
+    assert_eq!(result.data.decimal_part(), 2);
+    Ok(())
+}
+
+#[test]
+fn read_auto_unsupported_family_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-auto-unsupported");
+    pin.set_data(create_rom_data([
+        0x3B, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50,
+    ]));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_auto(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::UnsupportedFamily(0x3B)));
+}
+
+#[test]
+fn read_full_returns_scratchpad() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-full");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_full(pin, &mut delay)?;
+
+    assert_eq!(result.data.temperature.integer_part(), 85);
+    assert_eq!(result.data.alarm_high, 75);
+    assert_eq!(result.data.alarm_low, -10);
+    assert_eq!(result.data.resolution, ds18b20::ResolutionMode::ElevenBit);
+    Ok(())
+}
+
+#[test]
+fn read_full_above_alarm_high_triggers_alarm() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-full-alarm");
+    // 85 degrees is above the alarm high threshold of 75 degrees.
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_full(pin, &mut delay)?;
+
+    assert!(result.data.alarm_triggered);
+    assert_eq!(result.data.alarm(), Some(ds18b20::AlarmKind::High));
+    Ok(())
+}
+
+#[test]
+fn read_full_between_thresholds_does_not_trigger_alarm() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-full-no-alarm");
+    // 72 degrees, between the factory default thresholds of 70 and 75 degrees.
+    let mut scratchpad = [0x80, 0x04, 0x4B, 0x46, 0x7F, 0xFF, 0x0C, 0x10, 0x00];
+    scratchpad[8] = compute_crc(&scratchpad[..8]);
+    pin.set_data(create_read_data(scratchpad));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_full(pin, &mut delay)?;
+
+    assert_eq!(result.data.temperature.integer_part(), 72);
+    assert!(!result.data.alarm_triggered);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_all_ones_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-all-ones");
+    pin.set_data(create_read_data([0xFF; 9]));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn read_temperature_all_zeros_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-all-zeros");
+    pin.set_data(create_read_data([0x00; 9]));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn read_temperature_with_crc_check_accepts_all_zeros() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-all-zeros-crc-only");
+    // All zeros has a matching CRC of 0.
+    pin.set_data(create_read_data([0x00; 9]));
+    let mut delay = fake_delay::Delay::new();
+
+    let result =
+        ds18b20::read_temperature_with_check(pin, &mut delay, ds18b20::ScratchpadCheck::Crc)?;
+
+    assert_eq!(result.data.raw(), 0);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_with_crc_check_rejects_bad_crc() {
+    let mut pin = fake_digital::Pin::new("ds18b20-bad-crc-crc-only");
+    pin.set_data(create_read_data(BAD_CRC_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result =
+        ds18b20::read_temperature_with_check(pin, &mut delay, ds18b20::ScratchpadCheck::Crc);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn read_full_bad_crc_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-full-bad-crc");
+    pin.set_data(create_read_data(BAD_CRC_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_full(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn device_ids_sort_by_family_code_then_serial() {
+    let ds18s20 = ds18b20::DeviceId::from_bytes([0x10, 0x01, 0, 0, 0, 0, 0, 0xFF]);
+    let low_serial = ds18b20::DeviceId::from_bytes([0x28, 0xFF, 0, 0, 0, 0, 0x00, 0x00]);
+    let high_serial = ds18b20::DeviceId::from_bytes([0x28, 0x00, 0, 0, 0, 0, 0x01, 0x00]);
+    let mut devices = vec![high_serial, ds18s20, low_serial];
+
+    devices.sort();
+
+    assert_eq!(devices, [ds18s20, low_serial, high_serial]);
+}
+
+#[test]
+fn device_id_serial_byte_orders() {
+    let id = ds18b20::DeviceId::from_bytes([0x28, 0xFF, 0x5B, 0x79, 0xA2, 0x16, 0x03, 0x9E]);
+
+    assert_eq!(id.serial_le(), [0xFF, 0x5B, 0x79, 0xA2, 0x16, 0x03]);
+    assert_eq!(id.serial_be(), [0x03, 0x16, 0xA2, 0x79, 0x5B, 0xFF]);
+    assert_eq!(id.serial(), 0x0316_A279_5BFF);
+}
+
+#[test]
+fn device_id_canonical_string_matches_sysfs() {
+    // As listed in /sys/bus/w1/devices for this sensor.
+    let id = ds18b20::DeviceId::from_bytes([0x28, 0xFF, 0x5B, 0x79, 0xA2, 0x16, 0x03, 0x9E]);
+
+    assert_eq!(id.canonical_string().as_str(), "28-0316a2795bff");
+    assert_eq!(id.canonical_string().to_string(), "28-0316a2795bff");
+}
+
+#[test]
+fn read_temperature_polled_reports_conversion_time() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-polled");
+    let mut data = PRESENCE.to_vec();
+    // The sensor is busy for two read slots, then signals completion.
+    data.extend_from_slice(&[0, 0, 1]);
+    data.extend_from_slice(&create_read_data(FULL_SCRATCHPAD)[PRESENCE.len()..]);
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let clock = fake_clock::Clock::new();
+
+    let result = ds18b20::read_temperature_polled(
+        pin,
+        &mut delay,
+        || clock.now(),
+        |instant| {
+            // Each check happens 10ms after the last.
+            clock.advance(Duration::from_millis(10));
+            clock.elapsed_since(instant)
+        },
+    )?;
+
+    assert_eq!(result.data.temperature.integer_part(), 85);
+    assert_eq!(result.data.conversion_time, Duration::from_millis(30));
+    assert!(!delay.get_delays_us().contains(&750_000));
+    Ok(())
+}
+
+#[test]
+fn read_temperature_polled_never_completing_times_out() {
+    let mut pin = fake_digital::Pin::new("ds18b20-polled-timeout");
+    let mut data = PRESENCE.to_vec();
+    // The sensor never signals completion.
+    data.extend_from_slice(&[0; 100]);
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let clock = fake_clock::Clock::new();
+
+    let result = ds18b20::read_temperature_polled(
+        pin,
+        &mut delay,
+        || clock.now(),
+        |instant| {
+            clock.advance(Duration::from_millis(10));
+            clock.elapsed_since(instant)
+        },
+    );
+
+    assert_eq!(result.err().unwrap(), ds18b20::Error::Timeout);
+}
+
+#[test]
+fn read_temperature_polled_cancellable_cancels_between_slots() {
+    let mut pin = fake_digital::Pin::new("ds18b20-polled-cancel");
+    let mut data = PRESENCE.to_vec();
+    // The sensor never signals completion.
+    data.extend_from_slice(&[0; 100]);
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let clock = fake_clock::Clock::new();
+    let mut checks = 0;
+
+    let result = ds18b20::read_temperature_polled_cancellable(
+        pin,
+        &mut delay,
+        || clock.now(),
+        |instant| {
+            clock.advance(Duration::from_millis(10));
+            clock.elapsed_since(instant)
+        },
+        || {
+            checks += 1;
+            checks == 3
+        },
+    );
+
+    assert_eq!(result.err().unwrap(), ds18b20::Error::Cancelled);
+    assert_eq!(checks, 3);
+    // The conversion time was checked once per slot, and no more slots were sent once cancelled.
+    assert_eq!(clock.now(), Duration::from_millis(30));
+}
+
+#[test]
+fn read_temperature_ref_reads_repeatedly() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-ref");
+    let mut data = create_read_data(FULL_SCRATCHPAD);
+    data.extend(create_read_data(FULL_SCRATCHPAD));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    for _ in 0..2 {
+        let temperature = ds18b20::read_temperature_ref(&mut pin, &mut delay)?;
+        assert_eq!(temperature.integer_part(), 85);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "no-float"))]
+#[test]
+fn read_temperature_f32_matches_read_temperature() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-f32");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+    let mut f32_pin = fake_digital::Pin::new("ds18b20-f32-converted");
+    f32_pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut f32_delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature(pin, &mut delay)?;
+    let f32_result = ds18b20::read_temperature_f32(f32_pin, &mut f32_delay)?;
+
+    assert_eq!(f32_result.data, f32::from(result.data));
+    assert_eq!(f32_result.data, 85.0);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_strict_succeeds() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-strict");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_strict(pin, &mut delay)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_strict_bad_reserved_byte_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-strict-bad-reserved");
+    pin.set_data(create_read_data(BAD_RESERVED_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_strict(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn read_temperature_ignores_reserved_bytes() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-not-strict-bad-reserved");
+    pin.set_data(create_read_data(BAD_RESERVED_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature(pin, &mut delay)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_unchecked_reads_only_temperature_bytes() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-unchecked");
+    let mut data = create_read_data(FULL_SCRATCHPAD);
+    data.truncate(PRESENCE.len() * 2 + 16);
+    data.extend_from_slice(&PRESENCE);
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let mut checked_pin = fake_digital::Pin::new("ds18b20-unchecked-checked");
+    checked_pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut checked_delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_unchecked(pin, &mut delay)?;
+    let checked_result = ds18b20::read_temperature(checked_pin, &mut checked_delay)?;
+
+    assert_eq!(result.data.raw(), checked_result.data.raw());
+    // Each read slot samples the line after a 14us delay.
+    let count_read_slots = |delays: &[u32]| delays.iter().filter(|&&d| d == 14).count();
+    assert_eq!(count_read_slots(delay.get_delays_us()), 16);
+    assert_eq!(count_read_slots(checked_delay.get_delays_us()), 72);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_unchecked_ignores_bad_crc() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-unchecked-bad-crc");
+    let mut data = create_read_data(BAD_CRC_SCRATCHPAD);
+    data.truncate(PRESENCE.len() * 2 + 16);
+    data.extend_from_slice(&PRESENCE);
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_unchecked(pin, &mut delay)?;
+
+    assert_eq!(result.data.raw(), 0);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_median_ignores_outlier() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-median");
+    let mut data = create_read_data([0x90, 0x01, 0x4B, 0x46, 0x7F, 0xFF, 0x0C, 0x10, 0x33]);
+    data.extend(create_read_data(FULL_SCRATCHPAD));
+    data.extend(create_read_data([
+        0xA0, 0x01, 0x4B, 0x46, 0x7F, 0xFF, 0x0C, 0x10, 0xCF,
+    ]));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_median(pin, &mut delay, 3)?;
+
+    assert_eq!(result.data, ds18b20::Temperature::from_bytes(0xA0, 0x01));
+    assert_eq!(result.data.integer_part(), 26);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_median_even_samples_fails() {
+    let pin = fake_digital::Pin::new("ds18b20-median-even");
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_median(pin, &mut delay, 2);
+
+    assert_eq!(result.err(), Some(Error::InvalidArgument));
+}
+
+#[test]
+fn read_temperature_with_timing_applies_recovery_time() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-timing");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+    let timing = ds18b20::BusTiming {
+        recovery_us: 5,
+        ..ds18b20::DEFAULT_BUS_TIMING
+    };
+
+    let result = ds18b20::read_temperature_with_timing(pin, &mut delay, &timing)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    // Four command bytes are written, and nine scratchpad bytes are read.
+    let recovery_count = delay.get_delays_us().iter().filter(|&&d| d == 5).count();
+    assert_eq!(recovery_count, (4 + 9) * 8);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_with_timing_invalid_timing_fails() {
+    let pin = fake_digital::Pin::new("ds18b20-timing-invalid");
+    let mut delay = fake_delay::Delay::new();
+    let timing = ds18b20::BusTiming {
+        write_1_us: 16,
+        ..ds18b20::DEFAULT_BUS_TIMING
+    };
+
+    let result = ds18b20::read_temperature_with_timing(pin, &mut delay, &timing);
+
+    assert_eq!(result.err(), Some(Error::InvalidArgument));
+}
+
+#[test]
+fn read_temperature_with_timing_invalid_write_0_fails() {
+    let pin = fake_digital::Pin::new("ds18b20-timing-invalid-write-0");
+    let mut delay = fake_delay::Delay::new();
+    let timing = ds18b20::BusTiming {
+        write_0_us: 121,
+        ..ds18b20::DEFAULT_BUS_TIMING
+    };
+
+    let result = ds18b20::read_temperature_with_timing(pin, &mut delay, &timing);
+
+    assert_eq!(result.err(), Some(Error::InvalidArgument));
+}
+
+#[test]
+fn read_temperature_with_timing_longest_write_slots() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-timing-longest-write-slots");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+    let timing = ds18b20::BusTiming {
+        write_0_us: 120,
+        write_1_us: 15,
+        ..ds18b20::DEFAULT_BUS_TIMING
+    };
+
+    let result = ds18b20::read_temperature_with_timing(pin, &mut delay, &timing)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    assert!(delay.get_delays_us().contains(&120));
+    assert!(delay.get_delays_us().contains(&15));
+    Ok(())
+}
+
+#[test]
+fn read_temperature_with_timing_detects_late_presence() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-timing-late-presence");
+    // The presence pulse is only seen by the third of four samples, then the line is released,
+    // after each reset.
+    let late_presence = [1, 1, 0, 1, 1];
+    let mut data = Vec::new();
+    data.extend_from_slice(&late_presence);
+    data.extend_from_slice(&late_presence);
+    data.extend(
+        create_read_data(FULL_SCRATCHPAD)
+            .into_iter()
+            .skip(2 * PRESENCE.len()),
+    );
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let timing = ds18b20::BusTiming {
+        presence_samples: 4,
+        presence_interval_us: 30,
+        ..ds18b20::DEFAULT_BUS_TIMING
+    };
+
+    let result = ds18b20::read_temperature_with_timing(pin, &mut delay, &timing)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    assert_eq!(&delay.get_delays_us()[..6], [480, 30, 30, 30, 30, 360]);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_with_timing_settles_after_reset() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-timing-settle");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+    let timing = ds18b20::BusTiming {
+        post_reset_settle_us: 100,
+        ..ds18b20::DEFAULT_BUS_TIMING
+    };
+
+    let result = ds18b20::read_temperature_with_timing(pin, &mut delay, &timing)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    assert_eq!(&delay.get_delays_us()[..5], [480, 30, 30, 420, 100]);
+    // Once after each of the two resets.
+    let settle_count = delay.get_delays_us().iter().filter(|&&d| d == 100).count();
+    assert_eq!(settle_count, 2);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_with_timing_presence_window_too_long_fails() {
+    let pin = fake_digital::Pin::new("ds18b20-timing-long-presence");
+    let mut delay = fake_delay::Delay::new();
+    let timing = ds18b20::BusTiming {
+        presence_samples: 5,
+        presence_interval_us: 100,
+        ..ds18b20::DEFAULT_BUS_TIMING
+    };
+
+    let result = ds18b20::read_temperature_with_timing(pin, &mut delay, &timing);
+
+    assert_eq!(result.err(), Some(Error::InvalidArgument));
+}
+
+#[test]
+fn read_temperature_with_resolution_waits_for_resolution() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-with-resolution");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_with_resolution(
+        pin,
+        &mut delay,
+        ds18b20::ResolutionMode::NineBit,
+    )?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    assert!(delay.get_delays_us().contains(&93_750));
+    assert!(!delay.get_delays_us().contains(&750_000));
+    Ok(())
+}
+
+#[test]
+fn highest_within_picks_fastest_fitting_resolution() {
+    assert_eq!(
+        ds18b20::ResolutionMode::highest_within(Duration::from_millis(200)),
+        Some(ds18b20::ResolutionMode::TenBit)
+    );
+    assert_eq!(
+        ds18b20::ResolutionMode::highest_within(Duration::from_millis(750)),
+        Some(ds18b20::ResolutionMode::TwelveBit)
+    );
+    assert_eq!(
+        ds18b20::ResolutionMode::highest_within(Duration::from_millis(50)),
+        None
+    );
+}
+
+#[test]
+fn read_within_configures_resolution_for_budget() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-within");
+    // Read the current scratchpad, write the configuration, then convert and read.
+    let mut data = create_read_data(FULL_SCRATCHPAD).split_off(PRESENCE.len());
+    data.extend_from_slice(&PRESENCE);
+    data.extend(create_read_data(FULL_SCRATCHPAD));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_within(Duration::from_millis(200), pin, &mut delay)?;
+
+    assert_eq!(result.data.integer_part(), 85);
+    assert!(delay.get_delays_us().contains(&187_500));
+    assert!(!delay.get_delays_us().contains(&375_000));
+    // The alarm thresholds are rewritten unchanged, followed by the 10-bit configuration.
+    let mut config_writes = Vec::new();
+    for byte in [0x4E, 0x4B, 0xF6, 0x3F].iter() {
+        config_writes.append(&mut create_write_delays(*byte));
+    }
+    assert!(delay
+        .get_delays_us()
+        .windows(config_writes.len())
+        .any(|window| window == config_writes.as_slice()));
+    Ok(())
+}
+
+#[test]
+fn read_within_too_short_budget_fails() {
+    let pin = fake_digital::Pin::new("ds18b20-read-within-too-short");
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_within(Duration::from_millis(50), pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::InvalidArgument));
+}
+
+#[test]
+fn read_temperature_timestamped_advances() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-timestamped");
+    let mut data = create_read_data(FULL_SCRATCHPAD);
+    data.extend(create_read_data(FULL_SCRATCHPAD));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let clock = fake_clock::Clock::new();
+
+    let first = ds18b20::read_temperature_timestamped(pin, &mut delay, || clock.now())?;
+    clock.advance(Duration::from_secs(1));
+    let second = ds18b20::read_temperature_timestamped(first.pin, &mut delay, || clock.now())?;
+
+    assert_eq!(first.data.value.integer_part(), 85);
+    assert_eq!(first.data.at, Duration::from_secs(0));
+    assert_eq!(second.data.at, Duration::from_secs(1));
+    Ok(())
+}
+
+#[test]
+fn read_all_into_skips_bad_devices() -> Result<(), Error> {
+    let ids = [
+        ds18b20::DeviceId::from_bytes([0x28, 1, 0, 0, 0, 0, 0, 0]),
+        ds18b20::DeviceId::from_bytes([0x28, 2, 0, 0, 0, 0, 0, 0]),
+    ];
+    let mut pin = fake_digital::Pin::new("ds18b20-read-all-into");
+    // One conversion for both devices, then one read of each device.
+    let mut data = create_read_data(FULL_SCRATCHPAD);
+    data.extend_from_slice(&PRESENCE);
+    data.extend(create_read_data(BAD_CRC_SCRATCHPAD).split_off(PRESENCE.len() * 2));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let mut out = [(ds18b20::DeviceId::from_bytes([0; 8]), None); 2];
+
+    ds18b20::read_all_into(&ids, pin, &mut delay, &mut out)?;
+
+    assert_eq!(out[0].0, ids[0]);
+    assert_eq!(out[0].1.map(|t| t.integer_part()), Some(85));
+    assert_eq!(out[1], (ids[1], None));
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn discover_and_read_reads_each_device() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-discover-and-read");
+    // A search, then one conversion for both devices, then one read of each device.
+    let mut data = create_search_data(&[ROM_A, ROM_B]);
+    data.extend(create_read_data(FULL_SCRATCHPAD));
+    data.extend_from_slice(&PRESENCE);
+    data.extend(create_read_data(VALID_SCRATCHPAD).split_off(PRESENCE.len() * 2));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::discover_and_read(pin, &mut delay)?;
+
+    let readings: Vec<_> = result
+        .data
+        .iter()
+        .map(|(id, temperature)| (id.to_bytes(), temperature.integer_part()))
+        .collect();
+    assert_eq!(readings, [(ROM_A, 85), (ROM_B, 0)]);
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn discover_and_read_no_sensors_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-discover-and-read-no-sensors");
+    pin.set_default_data(true);
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::discover_and_read(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::NoSensorsFound));
+}
+
+#[test]
+fn monitor_keeps_previous_value_after_bad_read() -> Result<(), Error> {
+    let ids = [
+        ds18b20::DeviceId::from_bytes([0x28, 1, 0, 0, 0, 0, 0, 0]),
+        ds18b20::DeviceId::from_bytes([0x28, 2, 0, 0, 0, 0, 0, 0]),
+    ];
+    let mut pin = fake_digital::Pin::new("ds18b20-monitor");
+    // The first poll reads both devices, then the second device fails its CRC check.
+    let mut data = create_read_data(FULL_SCRATCHPAD);
+    data.extend_from_slice(&PRESENCE);
+    data.extend(create_read_data(VALID_SCRATCHPAD).split_off(PRESENCE.len() * 2));
+    data.extend(create_read_data(VALID_SCRATCHPAD));
+    data.extend_from_slice(&PRESENCE);
+    data.extend(create_read_data(BAD_CRC_SCRATCHPAD).split_off(PRESENCE.len() * 2));
+    pin.set_data(data);
+    let mut delay = fake_delay::Delay::new();
+    let clock = fake_clock::Clock::new();
+    let mut monitor = ds18b20::Monitor::new(ids, clock.now());
+
+    clock.advance(Duration::from_secs(1));
+    let first = monitor.poll(pin, &mut delay, || clock.now())?;
+    clock.advance(Duration::from_secs(1));
+    let second = monitor.poll(first.pin, &mut delay, || clock.now())?;
+
+    assert_eq!(first.data, 2);
+    assert_eq!(second.data, 1);
+    let latest = monitor.latest();
+    assert_eq!(latest[0].0, ids[0]);
+    assert_eq!(latest[0].1.map(|t| t.integer_part()), Some(0));
+    assert_eq!(latest[0].2, Duration::from_secs(2));
+    assert_eq!(latest[1].0, ids[1]);
+    assert_eq!(latest[1].1.map(|t| t.integer_part()), Some(0));
+    assert_eq!(latest[1].2, Duration::from_secs(1));
+    Ok(())
+}
+
+#[test]
+fn read_all_into_mismatched_lengths_fails() {
+    let ids = [ds18b20::DeviceId::from_bytes([0x28, 1, 0, 0, 0, 0, 0, 0])];
+    let pin = fake_digital::Pin::new("ds18b20-read-all-into-mismatched");
+    let mut delay = fake_delay::Delay::new();
+    let mut out = [];
+
+    let result = ds18b20::read_all_into(&ids, pin, &mut delay, &mut out);
+
+    assert_eq!(result.err(), Some(Error::InvalidArgument));
+}
+
+// ROM codes with valid CRCs.
+const VALID_ROM_A: [u8; 8] = [0x02, 0x1C, 0xB8, 0x01, 0x00, 0x00, 0x00, 0xA2];
+const VALID_ROM_B: [u8; 8] = [0x28, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x9E];
+
+#[test]
+fn device_id_is_valid() {
+    assert!(ds18b20::DeviceId::from_bytes(VALID_ROM_B).is_valid());
+}
+
+#[test]
+fn device_id_with_corrupted_byte_is_not_valid() {
+    let mut rom = VALID_ROM_B;
+    rom[3] ^= 0x10;
+
+    assert!(!ds18b20::DeviceId::from_bytes(rom).is_valid());
+}
+
+#[test]
+fn validate_devices_succeeds() {
+    let devices = [
+        ds18b20::DeviceId::from_bytes(VALID_ROM_A),
+        ds18b20::DeviceId::from_bytes(VALID_ROM_B),
+    ];
+
+    assert_eq!(ds18b20::validate_devices(&devices), Ok(()));
+}
+
+#[test]
+fn validate_devices_duplicate_fails() {
+    let devices = [
+        ds18b20::DeviceId::from_bytes(VALID_ROM_A),
+        ds18b20::DeviceId::from_bytes(VALID_ROM_B),
+        ds18b20::DeviceId::from_bytes(VALID_ROM_A),
+    ];
+
+    assert_eq!(
+        ds18b20::validate_devices(&devices),
+        Err(ds18b20::SearchError::Duplicate(devices[0]))
+    );
+}
+
+#[test]
+fn validate_devices_bad_crc_fails() {
+    let devices = [
+        ds18b20::DeviceId::from_bytes(VALID_ROM_A),
+        ds18b20::DeviceId::from_bytes(ROM_A),
+    ];
+
+    assert_eq!(
+        ds18b20::validate_devices(&devices),
+        Err(ds18b20::SearchError::BadCrc(devices[1]))
+    );
+}
+
+#[test]
+fn read_temperature_raw_returns_register() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-raw");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_raw(pin, &mut delay)?;
+
+    assert_eq!(result.data, 0x0550);
+    Ok(())
+}
+
+#[test]
+fn read_temperature_raw_bad_crc_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-raw-bad-crc");
+    pin.set_data(create_read_data(BAD_CRC_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_raw(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn read_temperature_with_raw_returns_scratchpad() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-with-raw");
+    pin.set_data(create_read_data(FULL_SCRATCHPAD));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_temperature_with_raw(pin, &mut delay)?;
+
+    assert_eq!(result.data.scratchpad, FULL_SCRATCHPAD);
+    assert_eq!(
+        result.data.temperature,
+        ds18b20::Temperature::from_bytes(0x50, 0x05)
+    );
+    Ok(())
+}
+
+/// The delays used to reset the line.
+fn create_reset_delays() -> Vec<u32> {
+    vec![480, 30, 30, 420]
+}
+
+/// The delays used to write the given byte to the line.
+fn create_write_delays(byte: u8) -> Vec<u32> {
+    let mut delays = Vec::new();
+    for bit in 0..8 {
+        if (byte >> bit) & 1 == 1 {
+            delays.extend_from_slice(&[1, 1, 59]);
+        } else {
+            delays.extend_from_slice(&[1, 60]);
+        }
+    }
+    delays
+}
+
+#[test]
+fn configure_writes_scratchpad() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-configure");
+    pin.set_data(PRESENCE.to_vec());
+    let mut delay = fake_delay::Delay::new();
+
+    ds18b20::configure(
+        pin,
+        &mut delay,
+        ds18b20::ResolutionMode::TenBit,
+        30,
+        -10,
+        false,
+    )?;
+
+    let mut expected = create_reset_delays();
+    // Skip ROM, Write Scratchpad, TH, TL, and the configuration register.
+    for byte in [0xCC, 0x4E, 30, -10i8 as u8, 0x3F].iter() {
+        expected.append(&mut create_write_delays(*byte));
+    }
+    assert_eq!(delay.get_delays_us(), expected.as_slice());
+    Ok(())
+}
+
+#[test]
+fn configure_with_persist_copies_scratchpad() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-configure-persist");
+    pin.set_data([PRESENCE, PRESENCE].concat());
+    let mut delay = fake_delay::Delay::new();
+
+    ds18b20::configure(
+        pin,
+        &mut delay,
+        ds18b20::ResolutionMode::TwelveBit,
+        0x7F,
+        0,
+        true,
+    )?;
+
+    let mut expected = create_reset_delays();
+    for byte in [0xCC, 0x4E, 0x7F, 0, 0x7F].iter() {
+        expected.append(&mut create_write_delays(*byte));
+    }
+    expected.append(&mut create_reset_delays());
+    // Skip ROM and Copy Scratchpad, then wait for the EEPROM write.
+    for byte in [0xCC, 0x48].iter() {
+        expected.append(&mut create_write_delays(*byte));
+    }
+    expected.push(10_000);
+    assert_eq!(delay.get_delays_us(), expected.as_slice());
+    Ok(())
+}
+
+#[test]
+fn reset_all_to_default_writes_factory_defaults() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-reset-all-to-default");
+    pin.set_data([PRESENCE, PRESENCE].concat());
+    let mut delay = fake_delay::Delay::new();
+
+    ds18b20::reset_all_to_default(pin, &mut delay)?;
+
+    let mut expected = create_reset_delays();
+    // Skip ROM, Write Scratchpad, TH, TL, and the 12-bit configuration register.
+    for byte in [0xCC, 0x4E, 0x4B, 0x46, 0x7F].iter() {
+        expected.append(&mut create_write_delays(*byte));
+    }
+    expected.append(&mut create_reset_delays());
+    for byte in [0xCC, 0x48].iter() {
+        expected.append(&mut create_write_delays(*byte));
+    }
+    expected.push(10_000);
+    assert_eq!(delay.get_delays_us(), expected.as_slice());
+    Ok(())
+}
+
+/// Computes the Dallas/Maxim CRC-8 of the given bytes.
+fn compute_crc(bytes: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for byte in bytes.iter() {
+        for bit in 0..8 {
+            let mix = (crc ^ (byte >> bit)) & 1;
+            crc >>= 1;
+            if mix == 1 {
+                crc ^= 0x8C;
+            }
+        }
+    }
+    crc
+}
+
+/// Decodes the bytes written to the line from the delays used to write them.
+fn decode_written_bytes(delays: &[u32]) -> Vec<u8> {
+    let mut bits = Vec::new();
+    let mut i = 0;
+    while i < delays.len() {
+        if delays[i..].starts_with(&[1, 60]) {
+            bits.push(0);
+            i += 2;
+        } else if delays[i..].starts_with(&[1, 1, 59]) {
+            bits.push(1);
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    bits.chunks(8)
+        .map(|byte| {
+            byte.iter()
+                .enumerate()
+                .fold(0, |acc, (bit, value)| acc | (value << bit))
+        })
+        .collect()
+}
+
+#[test]
+fn write_config_then_read_config_round_trips() -> Result<(), Error> {
+    let config = ds18b20::DeviceConfig {
+        resolution: ds18b20::ResolutionMode::ElevenBit,
+        alarm_high: 75,
+        alarm_low: -10,
+    };
+    let mut pin = fake_digital::Pin::new("ds18b20-write-config");
+    pin.set_data(PRESENCE.to_vec());
+    let mut delay = fake_delay::Delay::new();
+
+    ds18b20::write_config(&config, pin, &mut delay)?;
+
+    // Skip ROM, Write Scratchpad, then the bytes to echo back.
+    let written = decode_written_bytes(&delay.get_delays_us()[create_reset_delays().len()..]);
+    assert_eq!(written[..2], [0xCC, 0x4E]);
+    let mut scratchpad = FULL_SCRATCHPAD;
+    scratchpad[2..5].copy_from_slice(&written[2..]);
+    scratchpad[8] = compute_crc(&scratchpad[..8]);
+    let mut pin = fake_digital::Pin::new("ds18b20-read-config");
+    pin.set_data(create_read_data(scratchpad).split_off(PRESENCE.len()));
+    let result = ds18b20::read_config(pin, &mut delay)?;
+
+    assert_eq!(result.data, config);
+    Ok(())
+}
+
+#[test]
+fn read_config_bad_crc_fails() {
+    let mut pin = fake_digital::Pin::new("ds18b20-read-config-bad-crc");
+    pin.set_data(create_read_data(BAD_CRC_SCRATCHPAD).split_off(PRESENCE.len()));
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_config(pin, &mut delay);
+
+    assert_eq!(result.err(), Some(Error::BadData));
+}
+
+#[test]
+fn read_power_mode_external() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-power-external");
+    pin.set_data([&PRESENCE[..], &[1]].concat());
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_power_mode(pin, &mut delay)?;
+
+    assert_eq!(result.data, ds18b20::PowerMode::External);
+    Ok(())
+}
+
+#[test]
+fn read_power_mode_parasitic() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-power-parasitic");
+    pin.set_data([&PRESENCE[..], &[0]].concat());
+    let mut delay = fake_delay::Delay::new();
+
+    let result = ds18b20::read_power_mode(pin, &mut delay)?;
+
+    assert_eq!(result.data, ds18b20::PowerMode::Parasitic);
+    Ok(())
+}
+
+#[test]
+fn power_mode_cache_only_detects_once() -> Result<(), Error> {
+    let mut pin = fake_digital::Pin::new("ds18b20-power-cache");
+    // Only enough data for a single detection.
+    pin.set_data([&PRESENCE[..], &[0]].concat());
+    let mut delay = fake_delay::Delay::new();
+    let mut cache = ds18b20::PowerModeCache::new();
+    assert_eq!(cache.power_mode(), None);
+
+    let result = cache.get_or_detect(pin, &mut delay)?;
+    assert_eq!(result.data, ds18b20::PowerMode::Parasitic);
+    let num_delays = delay.get_delays_us().len();
+
+    let result = cache.get_or_detect(result.pin, &mut delay)?;
+
+    assert_eq!(result.data, ds18b20::PowerMode::Parasitic);
+    assert_eq!(cache.power_mode(), Some(ds18b20::PowerMode::Parasitic));
+    assert_eq!(delay.get_delays_us().len(), num_delays);
+    Ok(())
+}
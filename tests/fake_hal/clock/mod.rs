@@ -0,0 +1,45 @@
+use std::cell::{Cell, RefCell};
+use std::future::{ready, Ready};
+use std::time::Duration;
+
+/// A manually-advanced time source.
+///
+/// Instants are represented as the duration since the clock was created. Time only moves forward
+/// when `advance` or `delay` is called, so tests can verify waits without actually sleeping.
+#[derive(Debug)]
+pub struct Clock {
+    now: Cell<Duration>,
+    delays: RefCell<Vec<Duration>>,
+}
+
+impl Clock {
+    pub fn new() -> Clock {
+        Clock {
+            now: Cell::new(Duration::ZERO),
+            delays: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    pub fn elapsed_since(&self, instant: Duration) -> Duration {
+        self.now.get() - instant
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    /// Advances the clock by the given duration, and records the delay.
+    pub fn delay(&self, duration: Duration) -> Ready<()> {
+        self.delays.borrow_mut().push(duration);
+        self.advance(duration);
+        ready(())
+    }
+
+    pub fn get_delays(&self) -> Vec<Duration> {
+        self.delays.borrow().clone()
+    }
+}
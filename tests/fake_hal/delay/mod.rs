@@ -0,0 +1,30 @@
+use embedded_hal::delay::blocking::DelayUs;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {}
+
+#[derive(Debug)]
+pub struct Delay {
+    delays_us: Vec<u32>,
+}
+
+impl Delay {
+    pub fn new() -> Delay {
+        Delay {
+            delays_us: Vec::new(),
+        }
+    }
+
+    pub fn get_delays_us(&self) -> &[u32] {
+        self.delays_us.as_slice()
+    }
+}
+
+impl DelayUs for Delay {
+    type Error = Error;
+
+    fn delay_us(&mut self, us: u32) -> Result<(), Self::Error> {
+        self.delays_us.push(us);
+        Ok(())
+    }
+}
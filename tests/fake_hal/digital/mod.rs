@@ -3,13 +3,19 @@ use embedded_hal::digital::PinState;
 use embedded_hal::digital::blocking::{InputPin, IoPin, OutputPin};
 
 #[derive(Debug, PartialEq)]
-pub enum Error {}
+pub enum Error {
+    ModeChangeFailed,
+    ReadFailed,
+}
 
 #[derive(Debug)]
 pub struct Pin {
     data_to_read: Option<Vec<u8>>,
     name: &'static str,
     default_data: bool,
+    fail_mode_changes: bool,
+    fail_read_at: Option<usize>,
+    output_high: Option<bool>,
 }
 
 impl Pin {
@@ -19,6 +25,9 @@ impl Pin {
             data_to_read: None,
             name: name,
             default_data: false,
+            fail_mode_changes: false,
+            fail_read_at: None,
+            output_high: None,
         }
     }
 
@@ -27,6 +36,20 @@ impl Pin {
         self.data_to_read = None;
     }
 
+    pub fn set_fail_mode_changes(&mut self, fail: bool) {
+        self.fail_mode_changes = fail;
+    }
+
+    /// Makes the read at the given index of the data return an error instead.
+    pub fn set_fail_read_at(&mut self, index: usize) {
+        self.fail_read_at = Some(index);
+    }
+
+    /// Whether the pin was last set high or low, or `None` if it was never set.
+    pub fn get_output_high(&self) -> Option<bool> {
+        self.output_high
+    }
+
     pub fn set_data(&mut self, data: Vec<u8>) {
         self.data_to_read = Some(data);
         concurrent::set_named_value(&self.name, 0);
@@ -42,6 +65,9 @@ impl InputPin for Pin {
         }
 
         let data_index = concurrent::get_and_increment_named_value(&self.name);
+        if self.fail_read_at == Some(data_index) {
+            return Err(Error::ReadFailed);
+        }
         Ok(self.data_to_read.as_ref().unwrap()[data_index] > 0)
     }
 
@@ -51,6 +77,9 @@ impl InputPin for Pin {
         }
 
         let data_index = concurrent::get_and_increment_named_value(&self.name);
+        if self.fail_read_at == Some(data_index) {
+            return Err(Error::ReadFailed);
+        }
         Ok(self.data_to_read.as_ref().unwrap()[data_index] == 0)
     }
 }
@@ -59,10 +88,12 @@ impl OutputPin for Pin {
     type Error = Error;
 
     fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.output_high = Some(false);
         return Ok(());
     }
 
     fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.output_high = Some(true);
         return Ok(());
     }
 }
@@ -71,10 +102,16 @@ impl IoPin<Pin, Pin> for Pin {
     type Error = Error;
 
     fn into_input_pin(self) -> Result<Pin, Self::Error> {
+        if self.fail_mode_changes {
+            return Err(Error::ModeChangeFailed);
+        }
         Ok(self)
     }
 
     fn into_output_pin(self, _state: PinState) -> Result<Pin, Self::Error> {
+        if self.fail_mode_changes {
+            return Err(Error::ModeChangeFailed);
+        }
         Ok(self)
     }
 }
@@ -1,5 +1,7 @@
 #![allow(unused)]
 mod concurrent;
 
+pub mod clock;
+pub mod delay;
 pub mod digital;
 pub mod spi;
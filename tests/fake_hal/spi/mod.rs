@@ -71,6 +71,19 @@ impl TransferInplace<u8> for SPI {
     }
 }
 
+/// Transfers each 16-bit word as two bytes, most-significant byte first.
+impl TransferInplace<u16> for SPI {
+    type Error = SpiError;
+    fn transfer_inplace(&mut self, words: &mut [u16]) -> Result<(), Self::Error> {
+        for word in words.iter_mut() {
+            let mut bytes = word.to_be_bytes();
+            self.transfer_inplace(&mut bytes)?;
+            *word = u16::from_be_bytes(bytes);
+        }
+        Ok(())
+    }
+}
+
 impl FullDuplex<u8> for SPI {
     type Error = SpiError;
     fn read(&mut self) -> nb::Result<u8, SpiError> {
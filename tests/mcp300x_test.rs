@@ -69,6 +69,12 @@ test_synchronous_read_success!(
     mcp300x::Request::Differential(mcp300x::DifferentialMode::ThreeMinusTwo)
 );
 
+test_synchronous_read_success!(
+    read_synchronous_3008_channel_enum,
+    mcp300x::read_mcp3008,
+    mcp300x::Channel::Ch5.into()
+);
+
 #[test]
 fn read_ignores_noise() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
     let mut spi = fake_spi::SPI::new(
@@ -114,6 +120,59 @@ fn read_missing_preceding_null_fails() -> Result<(), mcp300x::Error<fake_spi::Sp
     Ok(())
 }
 
+#[test]
+fn read_raw_returns_unchecked_bytes() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = fake_spi::SPI::new(
+        vec![
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0x12),
+        ],
+        vec![
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::Success(),
+        ],
+    );
+
+    let result = mcp300x::read_raw(mcp300x::Request::SingleEnded(2), &mut spi)?;
+
+    assert_eq!(result, [0xFF, 0xFF, 0x12]);
+    assert_eq!(spi.get_written_data(), [0x1, 0xA0, 0x0]);
+    Ok(())
+}
+
+#[test]
+fn read_raw_aliased_channel_fails() {
+    let mut spi = fake_spi::SPI::new(vec![], vec![]);
+
+    let result = mcp300x::read_raw(mcp300x::Request::SingleEnded(8), &mut spi);
+
+    assert_eq!(
+        result.unwrap_err(),
+        mcp300x::Error::ChannelOutOfRange::<fake_spi::SpiError>(8)
+    );
+    assert!(spi.get_written_data().is_empty());
+}
+
+#[test]
+fn consecutive_reads_send_each_request() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = fake_spi::SPI::new(
+        (0..6).map(|_| fake_spi::FakeRead::Success(0xFF)).collect(),
+        (0..6).map(|_| fake_spi::FakeWrite::Success()).collect(),
+    );
+
+    mcp300x::read_raw(mcp300x::Request::SingleEnded(7), &mut spi)?;
+    mcp300x::read_raw(
+        mcp300x::Request::Differential(mcp300x::DifferentialMode::ZeroMinusOne),
+        &mut spi,
+    )?;
+
+    // The response to the first read must not leak into the second request.
+    assert_eq!(spi.get_written_data(), [0x1, 0xF0, 0x0, 0x1, 0x00, 0x0]);
+    Ok(())
+}
+
 #[test]
 fn sends_expected_request() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
     let mut spi = fake_spi::SPI::new(
@@ -160,8 +219,548 @@ fn asynchronous_writes_succeed() -> Result<(), mcp300x::Error<fake_spi::SpiError
     Ok(())
 }
 
+macro_rules! test_16_bit_read_success {
+    ($name:ident, $read_fn:expr, $request:expr, $expected_request:expr) => {
+        #[test]
+        fn $name() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+            let mut spi = fake_spi::SPI::new(
+                vec![
+                    fake_spi::FakeRead::Success(0),
+                    fake_spi::FakeRead::Success(0),
+                    fake_spi::FakeRead::Success(0xF9),
+                    fake_spi::FakeRead::Success(0xF1),
+                ],
+                vec![
+                    fake_spi::FakeWrite::Success(),
+                    fake_spi::FakeWrite::Success(),
+                    fake_spi::FakeWrite::Success(),
+                    fake_spi::FakeWrite::Success(),
+                ],
+            );
+
+            let result = $read_fn($request, &mut spi)?;
+
+            assert_eq!(result, 0x1F1);
+            assert_eq!(spi.get_written_data(), $expected_request);
+            Ok(())
+        }
+    };
+}
+
+test_16_bit_read_success!(
+    read_16_bit_3008_se1,
+    mcp300x::read_mcp3008_u16,
+    mcp300x::Request::SingleEnded(1),
+    [0x0, 0x1, 0x90, 0x0]
+);
+test_16_bit_read_success!(
+    read_16_bit_3004_d32,
+    mcp300x::read_mcp3004_u16,
+    mcp300x::Request::Differential(mcp300x::DifferentialMode::ThreeMinusTwo),
+    [0x0, 0x1, 0x30, 0x0]
+);
+
+#[test]
+fn read_16_bit_missing_preceding_null_fails() {
+    let mut spi = fake_spi::SPI::new(
+        vec![
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0x0),
+        ],
+        vec![
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::Success(),
+        ],
+    );
+
+    let result = mcp300x::read_mcp3008_u16(mcp300x::Request::SingleEnded(0), &mut spi);
+
+    assert_eq!(
+        result.unwrap_err(),
+        mcp300x::Error::BadData::<fake_spi::SpiError>
+    );
+}
+
+/// Creates an SPI that responds to consecutive 8-bit reads with the given values.
+fn create_spi_for_values(values: &[u16]) -> fake_spi::SPI {
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    for value in values.iter() {
+        reads.push(fake_spi::FakeRead::Success(0));
+        reads.push(fake_spi::FakeRead::Success((value >> 8) as u8));
+        reads.push(fake_spi::FakeRead::Success(*value as u8));
+        for _ in 0..3 {
+            writes.push(fake_spi::FakeWrite::Success());
+        }
+    }
+    fake_spi::SPI::new(reads, writes)
+}
+
+#[test]
+fn scan_mcp3008_finds_active_channels() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0, 3, 0x200, 5, 0, 0x3FF, 2, 6]);
+    let mut active = Vec::new();
+
+    mcp300x::scan_mcp3008(&mut spi, 5, |channel, value| active.push((channel, value)))?;
+
+    assert_eq!(active, [(2, 0x200), (5, 0x3FF), (7, 6)]);
+    Ok(())
+}
+
+#[test]
+fn scan_mcp3004_reads_only_its_channels() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x100, 0, 0, 0x101]);
+    let mut active = Vec::new();
+
+    mcp300x::scan_mcp3004(&mut spi, 0, |channel, value| active.push((channel, value)))?;
+
+    assert_eq!(active, [(0, 0x100), (3, 0x101)]);
+    assert_eq!(
+        spi.get_written_data(),
+        [0x1, 0x80, 0x0, 0x1, 0x90, 0x0, 0x1, 0xA0, 0x0, 0x1, 0xB0, 0x0]
+    );
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn scan_mcp3008_vec_finds_active_channels() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0, 3, 0x200, 5, 0, 0x3FF, 2, 6]);
+
+    let active = mcp300x::scan_mcp3008_vec(&mut spi, 5)?;
+
+    assert_eq!(active, [(2, 0x200), (5, 0x3FF), (7, 6)]);
+    Ok(())
+}
+
+#[test]
+fn read_validated_matches_read() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let request = mcp300x::Request::SingleEnded(5);
+    let validated = request.validate_for_mcp3008()?;
+    let mut spi = create_spi_for_values(&[0x2A5, 0x2A5]);
+
+    let result = mcp300x::read_mcp3008(request, &mut spi)?;
+    let validated_result = mcp300x::read_validated(validated, &mut spi)?;
+
+    assert_eq!(validated_result, result);
+    assert_eq!(spi.get_written_data(), [0x1, 0xD0, 0x0, 0x1, 0xD0, 0x0]);
+    Ok(())
+}
+
+#[test]
+fn read_settled_discards_readings() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let validated = mcp300x::Request::SingleEnded(2).validate_for_mcp3004()?;
+    let mut spi = create_spi_for_values(&[0x10, 0x20, 0x30]);
+
+    let result = mcp300x::read_settled(validated, &mut spi, 2)?;
+
+    assert_eq!(result, 0x30);
+    assert_eq!(
+        spi.get_written_data(),
+        [0x1, 0xA0, 0x0, 0x1, 0xA0, 0x0, 0x1, 0xA0, 0x0]
+    );
+    Ok(())
+}
+
+#[test]
+fn read_validated_retry_succeeds_after_bad_data() -> Result<(), mcp300x::Error<fake_spi::SpiError>>
+{
+    let validated = mcp300x::Request::SingleEnded(2).validate_for_mcp3004()?;
+    let mut spi = fake_spi::SPI::new(
+        vec![
+            // Missing null bit.
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0xFF),
+            fake_spi::FakeRead::Success(0),
+            fake_spi::FakeRead::Success(0x02),
+            fake_spi::FakeRead::Success(0x00),
+        ],
+        (0..6).map(|_| fake_spi::FakeWrite::Success()).collect(),
+    );
+
+    let result = mcp300x::read_validated_retry(validated, &mut spi, 3)?;
+
+    assert_eq!(result, 0x200);
+    assert_eq!(spi.get_written_data().len(), 6);
+    Ok(())
+}
+
+#[test]
+fn read_validated_retry_returns_last_bad_data() {
+    let validated = mcp300x::Request::SingleEnded(2)
+        .validate_for_mcp3004::<fake_spi::SpiError>()
+        .unwrap();
+    let mut spi = fake_spi::SPI::new(
+        (0..6).map(|_| fake_spi::FakeRead::Success(0xFF)).collect(),
+        (0..6).map(|_| fake_spi::FakeWrite::Success()).collect(),
+    );
+
+    let result = mcp300x::read_validated_retry(validated, &mut spi, 2);
+
+    assert_eq!(result, Err(mcp300x::Error::BadData));
+    assert_eq!(spi.get_written_data().len(), 6);
+}
+
+#[test]
+fn read_settled_without_discard() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let validated = mcp300x::Request::SingleEnded(2).validate_for_mcp3004()?;
+    let mut spi = create_spi_for_values(&[0x10]);
+
+    let result = mcp300x::read_settled(validated, &mut spi, 0)?;
+
+    assert_eq!(result, 0x10);
+    assert_eq!(spi.get_written_data(), [0x1, 0xA0, 0x0]);
+    Ok(())
+}
+
+#[test]
+fn read_until_stable_skips_glitch() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let validated = mcp300x::Request::SingleEnded(2).validate_for_mcp3004()?;
+    let mut spi = create_spi_for_values(&[0x100, 0x3FF, 0x102, 0x101]);
+
+    let result = mcp300x::read_until_stable(validated, &mut spi, 2, 5)?;
+
+    assert_eq!(result, 0x101);
+    assert_eq!(spi.get_written_data().len(), 4 * 3);
+    Ok(())
+}
+
+#[test]
+fn read_until_stable_never_stable_fails() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let validated = mcp300x::Request::SingleEnded(2).validate_for_mcp3004()?;
+    let mut spi = create_spi_for_values(&[0x100, 0x200, 0x300]);
+
+    let result = mcp300x::read_until_stable(validated, &mut spi, 2, 3);
+
+    assert_eq!(result, Err(mcp300x::Error::BadData));
+    Ok(())
+}
+
+#[test]
+fn read_until_stable_too_few_reads_fails() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let validated = mcp300x::Request::SingleEnded(2).validate_for_mcp3004()?;
+    let mut spi = create_spi_for_values(&[]);
+
+    let result = mcp300x::read_until_stable(validated, &mut spi, 2, 1);
+
+    assert_eq!(result, Err(mcp300x::Error::InvalidArgument));
+    Ok(())
+}
+
+#[test]
+fn read_validated_lsb_first() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let validated = mcp300x::Request::SingleEnded(5).validate_for_mcp3008()?;
+    // 0x1F1, as received by an SPI interface that reads least-significant-bit first.
+    let mut spi = fake_spi::SPI::new(
+        vec![
+            fake_spi::FakeRead::Success(0),
+            fake_spi::FakeRead::Success(0x80),
+            fake_spi::FakeRead::Success(0x8F),
+        ],
+        vec![
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::Success(),
+        ],
+    );
+
+    let result = mcp300x::read_validated_lsb_first(validated, &mut spi)?;
+
+    assert_eq!(result, 0x1F1);
+    assert_eq!(spi.get_written_data(), [0x80, 0x0B, 0x0]);
+    Ok(())
+}
+
+#[test]
+fn to_millivolts() {
+    assert_eq!(mcp300x::to_millivolts(0, 3300), 0);
+    assert_eq!(mcp300x::to_millivolts(512, 3300), 1650);
+    assert_eq!(mcp300x::to_millivolts(mcp300x::MAX_VALUE, 3300), 3296);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn read_channels_voltage() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let requests = [
+        mcp300x::Request::SingleEnded(0).validate_for_mcp3004()?,
+        mcp300x::Request::SingleEnded(3).validate_for_mcp3004()?,
+        mcp300x::Request::Differential(mcp300x::DifferentialMode::OneMinusZero)
+            .validate_for_mcp3004()?,
+    ];
+    let mut spi = create_spi_for_values(&[0x100, 0x3FF, 0x0]);
+
+    let voltages = mcp300x::read_channels_voltage(&requests, &mut spi, 3300)?;
+
+    assert_eq!(voltages, [825, 3296, 0]);
+    assert_eq!(
+        spi.get_written_data(),
+        [0x1, 0x80, 0x0, 0x1, 0xB0, 0x0, 0x1, 0x10, 0x0]
+    );
+    Ok(())
+}
+
+#[test]
+fn read_detailed_single_ended() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x2A]);
+
+    let result = mcp300x::read_mcp3004_detailed(mcp300x::Request::SingleEnded(3), &mut spi)?;
+
+    assert_eq!(result, mcp300x::Measurement::SingleEnded(0x2A));
+    assert_eq!(result.value(), 0x2A);
+    Ok(())
+}
+
+#[test]
+fn read_detailed_differential() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x2A]);
+
+    let result = mcp300x::read_mcp3008_detailed(
+        mcp300x::Request::Differential(mcp300x::DifferentialMode::SixMinusSeven),
+        &mut spi,
+    )?;
+
+    assert_eq!(result, mcp300x::Measurement::Differential(0x2A));
+    assert_eq!(result.value(), 0x2A);
+    Ok(())
+}
+
+#[test]
+fn scan_plan_execute() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let plan = mcp300x::ScanPlan::new([
+        mcp300x::Request::SingleEnded(0).validate_for_mcp3008()?,
+        mcp300x::Request::SingleEnded(3).validate_for_mcp3008()?,
+        mcp300x::Request::SingleEnded(7).validate_for_mcp3008()?,
+    ]);
+    let mut spi = create_spi_for_values(&[0x100, 0x3FF, 0x2A]);
+    let mut out = [0u16; 3];
+
+    plan.execute(&mut spi, &mut out)?;
+
+    assert_eq!(out, [0x100, 0x3FF, 0x2A]);
+    assert_eq!(
+        spi.get_written_data(),
+        [0x1, 0x80, 0x0, 0x1, 0xB0, 0x0, 0x1, 0xF0, 0x0]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_channels_async() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let requests = [
+        mcp300x::Request::SingleEnded(0).validate_for_mcp3008()?,
+        mcp300x::Request::SingleEnded(7).validate_for_mcp3008()?,
+        mcp300x::Request::Differential(mcp300x::DifferentialMode::OneMinusZero)
+            .validate_for_mcp3008()?,
+    ];
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    for value in [0x100u16, 0x3FF, 0x0].iter() {
+        reads.extend(vec![
+            fake_spi::FakeRead::AsyncSuccess(0),
+            fake_spi::FakeRead::Success((value >> 8) as u8),
+            fake_spi::FakeRead::AsyncSuccess(*value as u8),
+        ]);
+        writes.extend(vec![
+            fake_spi::FakeWrite::Success(),
+            fake_spi::FakeWrite::AsyncSuccess(),
+            fake_spi::FakeWrite::Success(),
+        ]);
+    }
+    let mut spi = fake_spi::SPI::new(reads, writes);
+
+    let values = mcp300x::read_channels_async(requests, &mut spi).await?;
+
+    assert_eq!(values, [0x100, 0x3FF, 0x0]);
+    assert_eq!(
+        spi.get_written_data(),
+        [0x1, 0x80, 0x0, 0x1, 0xF0, 0x0, 0x1, 0x10, 0x0]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_validated_async_error() {
+    let request = mcp300x::Request::SingleEnded(0)
+        .validate_for_mcp3008::<fake_spi::SpiError>()
+        .unwrap();
+    let mut spi = fake_spi::SPI::new(
+        vec![fake_spi::FakeRead::AsyncError()],
+        vec![fake_spi::FakeWrite::Success()],
+    );
+
+    let result = mcp300x::read_validated_async(request, &mut spi).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        mcp300x::Error::Wrapped(fake_spi::SpiError())
+    );
+}
+
+#[test]
+fn display_single_ended_request() {
+    assert_eq!(
+        mcp300x::Request::SingleEnded(3).to_string(),
+        "CH3 (single-ended)"
+    );
+}
+
+#[test]
+fn display_differential_request() {
+    assert_eq!(
+        mcp300x::Request::Differential(mcp300x::DifferentialMode::ZeroMinusOne).to_string(),
+        "CH0-CH1 (differential)"
+    );
+    assert_eq!(
+        mcp300x::Request::Differential(mcp300x::DifferentialMode::SevenMinusSix).to_string(),
+        "CH7-CH6 (differential)"
+    );
+}
+
+#[test]
+fn display_differential_mode() {
+    assert_eq!(
+        mcp300x::DifferentialMode::FiveMinusFour.to_string(),
+        "CH5-CH4"
+    );
+}
+
+#[test]
+fn validate_for_mcp3004_invalid_request_fails() {
+    let result = mcp300x::Request::Differential(mcp300x::DifferentialMode::FourMinusFive)
+        .validate_for_mcp3004::<fake_spi::SpiError>();
+
+    assert_eq!(
+        result.unwrap_err(),
+        mcp300x::Error::UnsupportedDifferentialMode::<fake_spi::SpiError>(
+            mcp300x::DifferentialMode::FourMinusFive
+        )
+    );
+}
+
+#[test]
+fn validate_for_mcp3008_aliased_channel_fails() {
+    let result = mcp300x::Request::SingleEnded(15).validate_for_mcp3008::<fake_spi::SpiError>();
+
+    assert_eq!(
+        result.unwrap_err(),
+        mcp300x::Error::ChannelOutOfRange::<fake_spi::SpiError>(15)
+    );
+}
+
+#[test]
+fn self_test_stable_values_succeeds() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x200, 0x205]);
+
+    mcp300x::self_test(&mut spi)?;
+
+    assert_eq!(spi.get_written_data(), [0x1, 0x80, 0x0, 0x1, 0x80, 0x0]);
+    Ok(())
+}
+
+#[test]
+fn self_test_unstable_values_fails() {
+    let mut spi = create_spi_for_values(&[0x10, 0x3F0]);
+
+    let result = mcp300x::self_test(&mut spi);
+
+    assert_eq!(
+        result.unwrap_err(),
+        mcp300x::Error::BadData::<fake_spi::SpiError>
+    );
+}
+
+#[test]
+fn self_test_missing_null_bit_fails() {
+    let mut spi = create_spi_for_values(&[0x200, 0x600]);
+
+    let result = mcp300x::self_test(&mut spi);
+
+    assert_eq!(
+        result.unwrap_err(),
+        mcp300x::Error::BadData::<fake_spi::SpiError>
+    );
+}
+
+#[test]
+fn calibration_check_near_rails_succeeds() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x3FD, 0x2]);
+
+    assert!(mcp300x::calibration_check(&mut spi, 6, 7)?);
+    assert_eq!(spi.get_written_data(), [0x1, 0xE0, 0x0, 0x1, 0xF0, 0x0]);
+    Ok(())
+}
+
+#[test]
+fn calibration_check_mid_scale_fails() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x200, 0x2]);
+
+    assert!(!mcp300x::calibration_check(&mut spi, 6, 7)?);
+    Ok(())
+}
+
+#[test]
+fn calibration_check_swapped_fails() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x2, 0x3FD]);
+
+    assert!(!mcp300x::calibration_check(&mut spi, 6, 7)?);
+    Ok(())
+}
+
+#[cfg(not(feature = "no-float"))]
+#[test]
+fn read_calibrated_uses_channel_scale() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x200, 0x200]);
+    let mut calibration = mcp300x::Calibration {
+        per_channel: [mcp300x::LinearScale {
+            slope: 1.0,
+            offset: 0.0,
+        }; 8],
+    };
+    calibration.per_channel[2] = mcp300x::LinearScale {
+        slope: 10.0,
+        offset: -1.0,
+    };
+
+    let ch1 = mcp300x::read_calibrated(mcp300x::Channel::Ch1, &mut spi, &calibration, 4.0)?;
+    let ch2 = mcp300x::read_calibrated(mcp300x::Channel::Ch2, &mut spi, &calibration, 4.0)?;
+
+    assert_eq!(ch1, 2.0);
+    assert_eq!(ch2, 19.0);
+    assert_eq!(spi.get_written_data(), [0x1, 0x90, 0x0, 0x1, 0xA0, 0x0]);
+    Ok(())
+}
+
+#[test]
+fn estimate_noise_returns_peak_to_peak() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let mut spi = create_spi_for_values(&[0x200, 0x1FC, 0x205, 0x201]);
+
+    let noise = mcp300x::estimate_noise(mcp300x::Channel::Ch3, &mut spi, 4)?;
+
+    assert_eq!(noise, 9);
+    assert_eq!(
+        spi.get_written_data(),
+        [0x1, 0xB0, 0x0, 0x1, 0xB0, 0x0, 0x1, 0xB0, 0x0, 0x1, 0xB0, 0x0]
+    );
+    Ok(())
+}
+
+#[test]
+fn estimate_noise_zero_samples_fails() {
+    let mut spi = create_spi_for_values(&[]);
+
+    assert_eq!(
+        mcp300x::estimate_noise(mcp300x::Channel::Ch0, &mut spi, 0),
+        Err(mcp300x::Error::InvalidArgument)
+    );
+}
+
 macro_rules! test_invalid_request {
-    ($name:ident, $read_fn:expr, $request:expr) => {
+    ($name:ident, $read_fn:expr, $request:expr, $expected_error:expr) => {
         #[test]
         fn $name() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
             let mut spi = fake_spi::SPI::new(
@@ -179,10 +778,7 @@ macro_rules! test_invalid_request {
 
             let result = $read_fn($request, &mut spi);
             assert!(result.is_err());
-            assert_eq!(
-                result.unwrap_err(),
-                mcp300x::Error::InvalidArgument::<fake_spi::SpiError>
-            );
+            assert_eq!(result.unwrap_err(), $expected_error);
             Ok(())
         }
     };
@@ -191,30 +787,115 @@ macro_rules! test_invalid_request {
 test_invalid_request!(
     invalid_channel_mcp3008,
     mcp300x::read_mcp3008,
-    mcp300x::Request::SingleEnded(8)
+    mcp300x::Request::SingleEnded(8),
+    mcp300x::Error::ChannelOutOfRange(8)
 );
 test_invalid_request!(
     invalid_channel_mcp3004,
     mcp300x::read_mcp3004,
-    mcp300x::Request::SingleEnded(4)
+    mcp300x::Request::SingleEnded(4),
+    mcp300x::Error::ChannelOutOfRange(4)
 );
 test_invalid_request!(
     invalid_differential_mode_mcp3004_4m5,
     mcp300x::read_mcp3004,
-    mcp300x::Request::Differential(mcp300x::DifferentialMode::FourMinusFive)
+    mcp300x::Request::Differential(mcp300x::DifferentialMode::FourMinusFive),
+    mcp300x::Error::UnsupportedDifferentialMode(mcp300x::DifferentialMode::FourMinusFive)
 );
 test_invalid_request!(
     invalid_differential_mode_mcp3004_5m4,
     mcp300x::read_mcp3004,
-    mcp300x::Request::Differential(mcp300x::DifferentialMode::FiveMinusFour)
+    mcp300x::Request::Differential(mcp300x::DifferentialMode::FiveMinusFour),
+    mcp300x::Error::UnsupportedDifferentialMode(mcp300x::DifferentialMode::FiveMinusFour)
 );
 test_invalid_request!(
     invalid_differential_mode_mcp3004_6m7,
     mcp300x::read_mcp3004,
-    mcp300x::Request::Differential(mcp300x::DifferentialMode::SixMinusSeven)
+    mcp300x::Request::Differential(mcp300x::DifferentialMode::SixMinusSeven),
+    mcp300x::Error::UnsupportedDifferentialMode(mcp300x::DifferentialMode::SixMinusSeven)
 );
 test_invalid_request!(
     invalid_differential_mode_mcp3004_7m6,
     mcp300x::read_mcp3004,
-    mcp300x::Request::Differential(mcp300x::DifferentialMode::SevenMinusSix)
+    mcp300x::Request::Differential(mcp300x::DifferentialMode::SevenMinusSix),
+    mcp300x::Error::UnsupportedDifferentialMode(mcp300x::DifferentialMode::SevenMinusSix)
 );
+test_invalid_request!(
+    invalid_aliased_channel_mcp3008,
+    mcp300x::read_mcp3008,
+    mcp300x::Request::SingleEnded(15),
+    mcp300x::Error::ChannelOutOfRange(15)
+);
+test_invalid_request!(
+    invalid_aliased_channel_mcp3004,
+    mcp300x::read_mcp3004,
+    mcp300x::Request::SingleEnded(12),
+    mcp300x::Error::ChannelOutOfRange(12)
+);
+
+#[test]
+fn mcp3004_resolution() {
+    let resolution = mcp300x::mcp3004_resolution();
+
+    assert_eq!(
+        resolution,
+        mcp300x::Resolution {
+            bits: 10,
+            signed: false
+        }
+    );
+    assert_eq!(resolution.min_value(), 0);
+    assert_eq!(resolution.max_value(), mcp300x::MAX_VALUE as i32);
+}
+
+#[test]
+fn mcp3008_resolution() {
+    let resolution = mcp300x::mcp3008_resolution();
+
+    assert_eq!(
+        resolution,
+        mcp300x::Resolution {
+            bits: 10,
+            signed: false
+        }
+    );
+    assert_eq!(resolution.min_value(), 0);
+    assert_eq!(resolution.max_value(), mcp300x::MAX_VALUE as i32);
+}
+
+#[test]
+fn mcp3004_spec() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let spec = mcp300x::mcp3004_spec();
+
+    assert_eq!(spec.num_channels, mcp300x::NUM_CHANNELS_MCP3004);
+    assert_eq!(spec.resolution, mcp300x::mcp3004_resolution());
+    assert_eq!(spec.differential_modes.len(), 4);
+    for mode in spec.differential_modes.iter() {
+        mcp300x::Request::Differential(*mode).validate_for_mcp3004()?;
+    }
+    Ok(())
+}
+
+#[test]
+fn mcp3008_spec() -> Result<(), mcp300x::Error<fake_spi::SpiError>> {
+    let spec = mcp300x::mcp3008_spec();
+
+    assert_eq!(spec.num_channels, mcp300x::NUM_CHANNELS_MCP3008);
+    assert_eq!(spec.resolution, mcp300x::mcp3008_resolution());
+    assert_eq!(spec.differential_modes.len(), 8);
+    for mode in spec.differential_modes.iter() {
+        mcp300x::Request::Differential(*mode).validate_for_mcp3008()?;
+    }
+    Ok(())
+}
+
+#[test]
+fn signed_resolution_range() {
+    let resolution = mcp300x::Resolution {
+        bits: 13,
+        signed: true,
+    };
+
+    assert_eq!(resolution.min_value(), -4096);
+    assert_eq!(resolution.max_value(), 4095);
+}